@@ -0,0 +1,458 @@
+//! Workspace backups: zip archives of a consistent app.db snapshot (SQLite online backup API), february-dir.txt and
+//! the data JSON files, written on demand or on a schedule to `<data root>/backups`, plus restore with an integrity
+//! check and a safety copy of the current database.
+
+use crate::error::{AppError, AppResult};
+use chrono::{DateTime, Utc};
+use rusqlite::{Connection, DatabaseName, OpenFlags};
+use serde::{Deserialize, Serialize};
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+use ts_rs::TS;
+
+/// Directory under the data root for backups (shared with the pre-migration copies made by db::run_migrations).
+pub const BACKUP_DIR: &str = "backups";
+const DB_FILE: &str = "app.db";
+const MANIFEST_FILE: &str = "manifest.json";
+const SCHEDULED_PREFIX: &str = "scheduled-";
+const MANUAL_PREFIX: &str = "manual-";
+const PRE_RESTORE_PREFIX: &str = "pre-restore-";
+const PRE_REPAIR_PREFIX: &str = "pre-repair-";
+const MIGRATION_PREFIX: &str = "app-v";
+
+/// How a backup file came to be, from its file name.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "snake_case")]
+#[ts(export)]
+pub enum BackupKind {
+    Manual,
+    Scheduled,
+    /// Copy of app.db taken before a schema migration.
+    Migration,
+    /// Copy of app.db taken before restore_backup replaced it.
+    PreRestore,
+    /// Copy of app.db taken before repair_db changed it.
+    PreRepair,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct BackupInfo {
+    pub path: String,
+    pub file_name: String,
+    pub kind: BackupKind,
+    /// File modification time (RFC 3339).
+    pub created_at: String,
+    #[ts(as = "f64")]
+    pub size_bytes: u64,
+}
+
+fn backup_kind(file_name: &str) -> Option<BackupKind> {
+    let kind = if file_name.starts_with(SCHEDULED_PREFIX) {
+        BackupKind::Scheduled
+    } else if file_name.starts_with(PRE_RESTORE_PREFIX) {
+        BackupKind::PreRestore
+    } else if file_name.starts_with(PRE_REPAIR_PREFIX) {
+        BackupKind::PreRepair
+    } else if file_name.starts_with(MIGRATION_PREFIX) {
+        BackupKind::Migration
+    } else {
+        BackupKind::Manual
+    };
+    let zip = file_name.ends_with(".zip");
+    let db = file_name.ends_with(".db");
+    match kind {
+        BackupKind::Manual | BackupKind::Scheduled if zip => Some(kind),
+        BackupKind::Migration | BackupKind::PreRestore | BackupKind::PreRepair if db => Some(kind),
+        _ => None,
+    }
+}
+
+fn backup_info(path: &Path) -> AppResult<Option<BackupInfo>> {
+    let file_name = path.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default();
+    let Some(kind) = backup_kind(&file_name) else {
+        return Ok(None);
+    };
+    let meta = std::fs::metadata(path)?;
+    let created_at: DateTime<Utc> = meta.modified().map(DateTime::from).unwrap_or_else(|_| Utc::now());
+    Ok(Some(BackupInfo {
+        path: path.to_string_lossy().to_string(),
+        file_name,
+        kind,
+        created_at: created_at.to_rfc3339(),
+        size_bytes: meta.len(),
+    }))
+}
+
+pub fn backup_dir(data_root: &Path) -> PathBuf {
+    data_root.join(BACKUP_DIR)
+}
+
+fn stamp() -> String {
+    Utc::now().format("%Y%m%dT%H%M%S%.3fZ").to_string()
+}
+
+/// Backups in the data root's backup directory, newest first.
+pub fn list_backups(data_root: &Path) -> AppResult<Vec<BackupInfo>> {
+    let dir = backup_dir(data_root);
+    if !dir.is_dir() {
+        return Ok(vec![]);
+    }
+    let mut backups = vec![];
+    for entry in std::fs::read_dir(&dir)? {
+        let path = entry?.path();
+        if path.is_file() {
+            backups.extend(backup_info(&path)?);
+        }
+    }
+    backups.sort_by(|a, b| b.created_at.cmp(&a.created_at).then_with(|| b.file_name.cmp(&a.file_name)));
+    Ok(backups)
+}
+
+/// Delete the oldest backups of `kind` so `keep` remain, counting `newest` (which is never deleted).
+pub fn prune_backups(data_root: &Path, kind: BackupKind, keep: usize, newest: &Path) -> AppResult<()> {
+    let backups = list_backups(data_root)?.into_iter().filter(|b| b.kind == kind);
+    for old in backups.filter(|b| Path::new(&b.path) != newest).skip(keep.saturating_sub(1)) {
+        let _ = std::fs::remove_file(&old.path);
+    }
+    Ok(())
+}
+
+/// Consistent copy of the live database at `dest` through the online backup API (safe while the app writes).
+fn snapshot_db(conn: &Connection, dest: &Path) -> AppResult<()> {
+    conn.backup(DatabaseName::Main, dest, None)
+        .map_err(|e| AppError::internal(format!("Database snapshot failed: {}", e)))
+}
+
+/// Files bundled next to app.db: february-dir.txt and the top-level *.json files of the data root.
+fn data_files(data_root: &Path) -> AppResult<Vec<PathBuf>> {
+    let mut files = vec![];
+    for entry in std::fs::read_dir(data_root)? {
+        let path = entry?.path();
+        let name = path.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default();
+        if path.is_file() && (name == "february-dir.txt" || name.ends_with(".json")) {
+            files.push(path);
+        }
+    }
+    files.sort();
+    Ok(files)
+}
+
+/// A database snapshot waiting to be zipped. Taking it needs the database connection; `finish` does not, so callers
+/// can release the DB lock while the archive is written.
+pub struct PendingBackup {
+    snapshot: tempfile::TempPath,
+    schema_version: i64,
+    dest: PathBuf,
+    kind: BackupKind,
+    /// Scheduled backups to keep, counting this one; older ones are pruned once it is written.
+    keep: Option<usize>,
+}
+
+/// Snapshot app.db for a backup zip at `dest` (a .zip path, or a directory to put a `manual-<time>.zip` in; the backup
+/// directory when None). The archive holds app.db, february-dir.txt, the data JSON files and a manifest.
+pub fn start_backup(conn: &Connection, data_root: &Path, dest: Option<&Path>) -> AppResult<PendingBackup> {
+    let dest = match dest {
+        Some(d) if d.extension().is_some_and(|e| e.eq_ignore_ascii_case("zip")) => d.to_path_buf(),
+        Some(d) => d.join(format!("{}{}.zip", MANUAL_PREFIX, stamp())),
+        None => backup_dir(data_root).join(format!("{}{}.zip", MANUAL_PREFIX, stamp())),
+    };
+    pending_backup(conn, dest, BackupKind::Manual, None)
+}
+
+/// Snapshot app.db for a scheduled backup if the schedule is on and the newest one is older than the interval.
+pub fn start_scheduled_backup(conn: &Connection, data_root: &Path) -> AppResult<Option<PendingBackup>> {
+    let schedule = crate::db::get_backup_schedule(conn)?;
+    if !schedule.enabled {
+        return Ok(None);
+    }
+    let newest = list_backups(data_root)?.into_iter().find(|b| b.kind == BackupKind::Scheduled);
+    let due = newest.is_none_or(|newest| {
+        DateTime::parse_from_rfc3339(&newest.created_at)
+            .map(|t| Utc::now() - t.with_timezone(&Utc) >= chrono::Duration::hours(schedule.interval_hours))
+            .unwrap_or(true)
+    });
+    if !due {
+        return Ok(None);
+    }
+    let dest = backup_dir(data_root).join(format!("{}{}.zip", SCHEDULED_PREFIX, stamp()));
+    let keep = schedule.keep.max(1) as usize;
+    pending_backup(conn, dest, BackupKind::Scheduled, Some(keep)).map(Some)
+}
+
+fn pending_backup(conn: &Connection, dest: PathBuf, kind: BackupKind, keep: Option<usize>) -> AppResult<PendingBackup> {
+    let dir = dest.parent().unwrap_or(Path::new("."));
+    std::fs::create_dir_all(dir)?;
+    let snapshot = tempfile::Builder::new().prefix(".snapshot-").suffix(".db.tmp").tempfile_in(dir)?.into_temp_path();
+    snapshot_db(conn, &snapshot)?;
+    let schema_version: i64 = conn.query_row("PRAGMA user_version", [], |row| row.get(0))?;
+    Ok(PendingBackup { snapshot, schema_version, dest, kind, keep })
+}
+
+impl PendingBackup {
+    /// Write the backup zip (and prune old scheduled backups). The snapshot file is removed either way.
+    pub fn finish(self, data_root: &Path) -> AppResult<BackupInfo> {
+        if let Err(e) = self.write_zip(data_root) {
+            let _ = std::fs::remove_file(&self.dest);
+            return Err(e);
+        }
+        if let Some(keep) = self.keep {
+            prune_backups(data_root, BackupKind::Scheduled, keep, &self.dest)?;
+        }
+        let meta = std::fs::metadata(&self.dest)?;
+        Ok(BackupInfo {
+            path: self.dest.to_string_lossy().to_string(),
+            file_name: self.dest.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default(),
+            kind: self.kind,
+            created_at: Utc::now().to_rfc3339(),
+            size_bytes: meta.len(),
+        })
+    }
+
+    fn write_zip(&self, data_root: &Path) -> AppResult<()> {
+        let files = data_files(data_root)?;
+        let manifest = serde_json::json!({
+            "created_at": Utc::now().to_rfc3339(),
+            "schema_version": self.schema_version,
+            "app_version": env!("CARGO_PKG_VERSION"),
+            "files": std::iter::once(DB_FILE.to_string())
+                .chain(files.iter().filter_map(|f| f.file_name().map(|n| n.to_string_lossy().to_string())))
+                .collect::<Vec<_>>(),
+        });
+        let mut zip = zip::ZipWriter::new(std::fs::File::create(&self.dest)?);
+        let options = zip::write::SimpleFileOptions::default().compression_method(zip::CompressionMethod::Deflated);
+        zip.start_file(MANIFEST_FILE, options)?;
+        zip.write_all(serde_json::to_string_pretty(&manifest)?.as_bytes())?;
+        zip.start_file(DB_FILE, options.large_file(true))?;
+        std::io::copy(&mut std::fs::File::open(&self.snapshot)?, &mut zip)?;
+        for file in files {
+            let name = file.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default();
+            zip.start_file(name, options)?;
+            zip.write_all(&std::fs::read(&file)?)?;
+        }
+        zip.finish()?;
+        Ok(())
+    }
+}
+
+/// Database file inside a backup: app.db extracted from a zip into `scratch`, or the .db file itself.
+fn backup_database(path: &Path, scratch: &Path) -> AppResult<PathBuf> {
+    if !path.extension().is_some_and(|e| e.eq_ignore_ascii_case("zip")) {
+        return Ok(path.to_path_buf());
+    }
+    let mut archive = zip::ZipArchive::new(std::fs::File::open(path)?)?;
+    let mut entry = archive
+        .by_name(DB_FILE)
+        .map_err(|_| AppError::invalid(format!("{} has no {}", path.display(), DB_FILE)))?;
+    let mut buf = Vec::new();
+    entry.read_to_end(&mut buf)?;
+    std::fs::write(scratch, &buf)?;
+    Ok(scratch.to_path_buf())
+}
+
+/// Open a backup read-only and check it: PRAGMA integrity_check must pass and its schema may not be newer than this
+/// build's. Returns its schema version.
+fn verify_backup_database(path: &Path, latest_version: i64) -> AppResult<i64> {
+    let not_sqlite = |e: rusqlite::Error| AppError::invalid(format!("Not a SQLite database: {}", e));
+    let conn = Connection::open_with_flags(path, OpenFlags::SQLITE_OPEN_READ_ONLY).map_err(not_sqlite)?;
+    let problems: Vec<String> = conn
+        .prepare("PRAGMA integrity_check")
+        .and_then(|mut stmt| stmt.query_map([], |row| row.get(0))?.collect())
+        .map_err(not_sqlite)?;
+    if problems != ["ok"] {
+        return Err(AppError::invalid("Backup failed the integrity check")
+            .with_details(serde_json::json!({ "problems": problems })));
+    }
+    let has_kv: bool =
+        conn.query_row("SELECT COUNT(*) FROM sqlite_master WHERE type = 'table' AND name = 'kv_store'", [], |row| {
+            row.get::<_, i64>(0)
+        })? > 0;
+    if !has_kv {
+        return Err(AppError::invalid("Backup is not an app database"));
+    }
+    let version: i64 = conn.query_row("PRAGMA user_version", [], |row| row.get(0))?;
+    if version > latest_version {
+        return Err(AppError::invalid(format!(
+            "Backup has schema version {}, newer than this app's {}; update the app first",
+            version, latest_version
+        )));
+    }
+    Ok(version)
+}
+
+/// Result of restore_backup.
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct RestoreResult {
+    /// Copy of the database as it was before the restore.
+    pub safety_copy: BackupInfo,
+    /// Schema version of the restored backup; newer migrations run when the database is reopened.
+    #[ts(as = "f64")]
+    pub schema_version: i64,
+}
+
+/// Copy of the live database as `pre-repair-<time>.db` in the backup directory, taken before repair_db.
+pub fn pre_repair_copy(conn: &Connection, data_root: &Path) -> AppResult<BackupInfo> {
+    let dir = backup_dir(data_root);
+    std::fs::create_dir_all(&dir)?;
+    let dest = dir.join(format!("{}{}.db", PRE_REPAIR_PREFIX, stamp()));
+    snapshot_db(conn, &dest)?;
+    backup_info(&dest)?.ok_or_else(|| AppError::internal("Pre-repair copy missing"))
+}
+
+/// Verify the backup at `path` and hand its database file and a safety copy path to `replace`.
+fn restore_with<F>(data_root: &Path, path: &Path, latest_version: i64, replace: F) -> AppResult<RestoreResult>
+where
+    F: FnOnce(&Path, &Path) -> AppResult<()>,
+{
+    if !path.is_file() {
+        return Err(AppError::not_found(format!("Backup not found: {}", path.display())));
+    }
+    let dir = backup_dir(data_root);
+    std::fs::create_dir_all(&dir)?;
+    let scratch = dir.join(format!("restore-{}.db.tmp", stamp()));
+    let result = (|| -> AppResult<RestoreResult> {
+        let source = backup_database(path, &scratch)?;
+        let schema_version = verify_backup_database(&source, latest_version)?;
+        let safety = dir.join(format!("{}{}.db", PRE_RESTORE_PREFIX, stamp()));
+        replace(&source, &safety)?;
+        let safety_copy = backup_info(&safety)?.ok_or_else(|| AppError::internal("Safety copy missing"))?;
+        Ok(RestoreResult { safety_copy, schema_version })
+    })();
+    let _ = std::fs::remove_file(&scratch);
+    result
+}
+
+/// Replace the live database with the one in `path` (a backup zip or a .db copy). The backup is verified first and the
+/// current database is copied to `pre-restore-<time>.db`. Only app.db is restored; february-dir.txt and the JSON
+/// files in a zip are left for manual recovery. The caller must reopen the connection afterwards so migrations run.
+pub fn restore_backup(
+    conn: &mut Connection,
+    data_root: &Path,
+    path: &Path,
+    latest_version: i64,
+) -> AppResult<RestoreResult> {
+    restore_with(data_root, path, latest_version, |source, safety| {
+        snapshot_db(conn, safety)?;
+        conn.restore(DatabaseName::Main, source, None::<fn(rusqlite::backup::Progress)>)
+            .map_err(|e| AppError::internal(format!("Restore failed: {}", e)))
+    })
+}
+
+/// restore_backup for a database that cannot be opened: the backup is copied next to `db_path` first, then the file
+/// at `db_path` (with its WAL and shared-memory files) is moved to `pre-restore-<time>.db` and the copy renamed into
+/// its place. On any failure the moved files are put back, so `db_path` is never left missing. Nothing may hold
+/// `db_path` open.
+pub fn restore_backup_file(
+    db_path: &Path,
+    data_root: &Path,
+    path: &Path,
+    latest_version: i64,
+) -> AppResult<RestoreResult> {
+    restore_with(data_root, path, latest_version, |source, safety| {
+        let staged = PathBuf::from(format!("{}.restore-tmp", db_path.display()));
+        let mut moved: Vec<(PathBuf, PathBuf)> = vec![];
+        let result = (|| -> AppResult<()> {
+            std::fs::copy(source, &staged)?;
+            for suffix in ["", "-wal", "-shm"] {
+                let from = PathBuf::from(format!("{}{}", db_path.display(), suffix));
+                if suffix.is_empty() || from.exists() {
+                    let to = PathBuf::from(format!("{}{}", safety.display(), suffix));
+                    std::fs::rename(&from, &to)?;
+                    moved.push((from, to));
+                }
+            }
+            std::fs::rename(&staged, db_path)?;
+            Ok(())
+        })();
+        if result.is_err() {
+            let _ = std::fs::remove_file(&staged);
+            for (from, to) in moved.iter().rev() {
+                let _ = std::fs::rename(to, from);
+            }
+        }
+        result
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn backup_zip_is_written_from_the_snapshot_without_the_connection() {
+        let dir = tempfile::tempdir().unwrap();
+        let data = dir.path();
+        let conn = crate::db::open_db(&data.join(DB_FILE)).unwrap();
+        conn.execute("INSERT INTO projects (id, name) VALUES ('p', 'Before')", []).unwrap();
+        std::fs::write(data.join("february-dir.txt"), "/repo").unwrap();
+
+        let pending = start_backup(&conn, data, None).unwrap();
+        // Writes after the snapshot (another command holding the lock) are not in the archive.
+        conn.execute("UPDATE projects SET name = 'After' WHERE id = 'p'", []).unwrap();
+        drop(conn);
+        let info = pending.finish(data).unwrap();
+        assert_eq!(info.kind, BackupKind::Manual);
+        let left: Vec<_> = std::fs::read_dir(backup_dir(data)).unwrap().map(|e| e.unwrap().file_name()).collect();
+        assert_eq!(left, vec![std::ffi::OsString::from(&info.file_name)]);
+
+        let scratch = data.join("extracted.db");
+        let db = Connection::open(backup_database(Path::new(&info.path), &scratch).unwrap()).unwrap();
+        let name: String = db.query_row("SELECT name FROM projects WHERE id = 'p'", [], |row| row.get(0)).unwrap();
+        assert_eq!(name, "Before");
+        let mut archive = zip::ZipArchive::new(std::fs::File::open(&info.path).unwrap()).unwrap();
+        assert!(archive.by_name("february-dir.txt").is_ok());
+    }
+
+    #[test]
+    fn restoring_over_a_closed_database_keeps_it_when_the_copy_fails() {
+        let dir = tempfile::tempdir().unwrap();
+        let data = dir.path();
+        let db_path = data.join(DB_FILE);
+        let conn = crate::db::open_db(&db_path).unwrap();
+        conn.execute("INSERT INTO projects (id, name) VALUES ('p', 'Backed up')", []).unwrap();
+        let info = start_backup(&conn, data, None).unwrap().finish(data).unwrap();
+        conn.execute("UPDATE projects SET name = 'Live' WHERE id = 'p'", []).unwrap();
+        drop(conn);
+        let latest = crate::db::latest_schema_version();
+        let name = |path: &Path| -> String {
+            let db = Connection::open(path).unwrap();
+            db.query_row("SELECT name FROM projects WHERE id = 'p'", [], |row| row.get(0)).unwrap()
+        };
+
+        // Something in the way of the staged copy: the restore fails and app.db stays where it was.
+        let staged = PathBuf::from(format!("{}.restore-tmp", db_path.display()));
+        std::fs::create_dir(&staged).unwrap();
+        assert!(restore_backup_file(&db_path, data, Path::new(&info.path), latest).is_err());
+        assert_eq!(name(&db_path), "Live");
+        std::fs::remove_dir(&staged).unwrap();
+
+        let restored = restore_backup_file(&db_path, data, Path::new(&info.path), latest).unwrap();
+        assert_eq!(name(&db_path), "Backed up");
+        assert_eq!(name(Path::new(&restored.safety_copy.path)), "Live");
+        assert!(!staged.exists());
+    }
+
+    #[test]
+    fn scheduled_backups_are_pruned_to_keep() {
+        let dir = tempfile::tempdir().unwrap();
+        let data = dir.path();
+        let conn = crate::db::open_db(&data.join(DB_FILE)).unwrap();
+        let schedule = crate::db::BackupSchedule { enabled: true, interval_hours: 1, keep: 2 };
+        crate::db::set_backup_schedule(&conn, &schedule).unwrap();
+        std::fs::create_dir_all(backup_dir(data)).unwrap();
+        for old in ["scheduled-20200101T000000.000Z.zip", "scheduled-20200102T000000.000Z.zip"] {
+            std::fs::write(backup_dir(data).join(old), "old").unwrap();
+        }
+        // Age the old backups so the schedule is due.
+        let stale = std::time::SystemTime::now() - std::time::Duration::from_secs(3 * 3600);
+        for entry in std::fs::read_dir(backup_dir(data)).unwrap() {
+            std::fs::File::options().write(true).open(entry.unwrap().path()).unwrap().set_modified(stale).unwrap();
+        }
+
+        let info = start_scheduled_backup(&conn, data).unwrap().unwrap().finish(data).unwrap();
+        assert_eq!(list_backups(data).unwrap().len(), 2);
+        assert!(Path::new(&info.path).exists());
+        assert!(start_scheduled_backup(&conn, data).unwrap().is_none());
+    }
+}
@@ -1,30 +1,206 @@
 //! SQLite database for app data (projects, tickets, features).
 //! Migrates from existing JSON files on first run.
 
+use crate::error::{AppError, AppResult};
 use rusqlite::{Connection, params};
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use ts_rs::TS;
 use std::path::{Path, PathBuf};
 
 const KV_ALL_PROJECTS: &str = "all_projects";
 const KV_CURSOR_PROJECTS: &str = "cursor_projects";
 const KV_DATA_DIR: &str = "data_dir";
 const KV_PROJECTS: &str = "projects";
+const KV_COMMIT_TEMPLATE_PREFIX: &str = "commit_template:";
+const KV_BACKUP_SCHEDULE: &str = "backup_schedule";
 
-pub fn open_db(db_path: &Path) -> Result<Connection, String> {
+pub fn open_db(db_path: &Path) -> AppResult<Connection> {
     if let Some(parent) = db_path.parent() {
-        std::fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+        std::fs::create_dir_all(parent)?;
     }
-    let conn = Connection::open(db_path).map_err(|e| e.to_string())?;
+    let open_failed = |e: AppError| open_failed(db_path, e);
+    let conn = Connection::open(db_path).map_err(|e| open_failed(e.into()))?;
+    // A corrupted or non-SQLite file fails here ("file is not a database").
     conn.execute_batch(
         "PRAGMA foreign_keys = ON;
          PRAGMA journal_mode = WAL;",
     )
-    .map_err(|e| e.to_string())?;
-    init_schema(&conn)?;
+    .map_err(|e| open_failed(e.into()))?;
+    run_migrations(&conn, db_path).map_err(open_failed)?;
     Ok(conn)
 }
 
-fn init_schema(conn: &Connection) -> Result<(), String> {
+/// Name the file and point at recovery when the database cannot be opened or migrated.
+fn open_failed(db_path: &Path, e: AppError) -> AppError {
+    AppError::db_unavailable(format!("Could not open database {}: {}", db_path.display(), e.message)).with_details(
+        serde_json::json!({
+            "path": db_path.to_string_lossy(),
+            "cause": e.code,
+            "hint": "Restore a backup (list_backups / restore_backup) or move the file aside to start with a new database",
+        }),
+    )
+}
+
+/// Schema version this build migrates to.
+pub fn latest_schema_version() -> i64 {
+    MIGRATIONS.last().map(|m| m.version).unwrap_or(0)
+}
+
+/// One schema change. `version` is stored in `PRAGMA user_version` once applied; never renumber or edit a released migration.
+struct Migration {
+    version: i64,
+    name: &'static str,
+    up: fn(&Connection) -> AppResult<()>,
+}
+
+/// Ordered schema migrations. Append new ones at the end with the next version number.
+const MIGRATIONS: &[Migration] = &[
+    Migration { version: 1, name: "initial_schema", up: m001_initial_schema },
+    Migration { version: 2, name: "implementation_log_status", up: m002_implementation_log_status },
+    Migration { version: 3, name: "plan_ticket_commits", up: m003_plan_ticket_commits },
+    Migration { version: 4, name: "relational_projects", up: m004_relational_projects },
+    Migration { version: 5, name: "plan_ticket_md_sync", up: m005_plan_ticket_md_sync },
+    Migration { version: 6, name: "kanban_workflow", up: m006_kanban_workflow },
+    Migration { version: 7, name: "plan_ticket_dependencies", up: m007_plan_ticket_dependencies },
+    Migration { version: 8, name: "plan_ticket_events", up: m008_plan_ticket_events },
+    Migration { version: 9, name: "milestone_schedule", up: m009_milestone_schedule },
+    Migration { version: 10, name: "idea_status", up: m010_idea_status },
+    Migration { version: 11, name: "idea_scoring", up: m011_idea_scoring },
+    Migration { version: 12, name: "search_index", up: m012_search_index },
+    Migration { version: 13, name: "soft_delete", up: m013_soft_delete },
+    Migration { version: 14, name: "quarantine", up: m014_quarantine },
+    Migration { version: 15, name: "search_update_guards", up: m015_search_update_guards },
+];
+
+/// Applied migration as recorded in schema_migrations (shown on the Configuration page).
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct MigrationRecord {
+    #[ts(as = "f64")]
+    pub version: i64,
+    pub name: String,
+    pub applied_at: String,
+    /// Copy of app.db taken right before this migration ran (None for a fresh database).
+    pub backup_path: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct SchemaStatus {
+    #[ts(as = "f64")]
+    pub current_version: i64,
+    #[ts(as = "f64")]
+    pub latest_version: i64,
+    pub history: Vec<MigrationRecord>,
+}
+
+fn schema_version(conn: &Connection) -> AppResult<i64> {
+    conn.query_row("PRAGMA user_version", [], |row| row.get(0))
+        .map_err(AppError::from)
+}
+
+/// Migration backups (`backups/app-v*-to-v*.db`) kept after a successful upgrade, counting the new one.
+const MIGRATION_BACKUPS_KEEP: usize = 3;
+
+/// Bring the schema up to the latest version. The database is backed up once before the first pending migration
+/// (skipped for a new, empty file) and each migration is applied together with its user_version bump in one
+/// transaction. Older migration backups are pruned afterwards.
+fn run_migrations(conn: &Connection, db_path: &Path) -> AppResult<()> {
+    let latest = latest_schema_version();
+    let current = schema_version(conn)?;
+    if current >= latest {
+        return Ok(());
+    }
+    conn.execute_batch(
+        "CREATE TABLE IF NOT EXISTS schema_migrations (
+            version INTEGER PRIMARY KEY,
+            name TEXT NOT NULL,
+            applied_at TEXT NOT NULL,
+            backup_path TEXT
+        );",
+    )?;
+    let has_data: bool = conn
+        .query_row(
+            "SELECT COUNT(*) FROM sqlite_master WHERE type = 'table' AND name != 'schema_migrations'",
+            [],
+            |row| row.get::<_, i64>(0),
+        )?
+        > 0;
+    let backup_path = if has_data {
+        Some(backup_before_migration(conn, db_path, current, latest)?)
+    } else {
+        None
+    };
+    let pending: Vec<&Migration> = MIGRATIONS.iter().filter(|m| m.version > current).collect();
+    for m in pending {
+        let tx = conn.unchecked_transaction()?;
+        (m.up)(&tx).map_err(|e| format!("Migration {} ({}) failed: {}", m.version, m.name, e))?;
+        tx.execute(
+            "INSERT OR REPLACE INTO schema_migrations (version, name, applied_at, backup_path) VALUES (?1, ?2, ?3, ?4)",
+            params![
+                m.version,
+                m.name,
+                chrono::Utc::now().to_rfc3339(),
+                backup_path.as_ref().map(|p| p.to_string_lossy().to_string()),
+            ],
+        )?;
+        tx.execute_batch(&format!("PRAGMA user_version = {}", m.version))?;
+        tx.commit()?;
+    }
+    if let (Some(path), Some(data_root)) = (&backup_path, db_path.parent()) {
+        if let Err(e) = crate::backup::prune_backups(
+            data_root,
+            crate::backup::BackupKind::Migration,
+            MIGRATION_BACKUPS_KEEP,
+            path,
+        ) {
+            eprintln!("Pruning migration backups failed: {}", e);
+        }
+    }
+    Ok(())
+}
+
+/// Snapshot the database to `<db dir>/backups/` with VACUUM INTO (consistent even in WAL mode).
+fn backup_before_migration(conn: &Connection, db_path: &Path, from: i64, to: i64) -> AppResult<PathBuf> {
+    let dir = db_path
+        .parent()
+        .map(|p| p.join("backups"))
+        .ok_or_else(|| AppError::invalid("Database path has no parent directory"))?;
+    std::fs::create_dir_all(&dir)?;
+    let stamp = chrono::Utc::now().format("%Y%m%dT%H%M%S%.3fZ");
+    let path = dir.join(format!("app-v{}-to-v{}-{}.db", from, to, stamp));
+    conn.execute("VACUUM INTO ?1", params![path.to_string_lossy().to_string()])
+        .map_err(|e| format!("Backup before migrating to version {} failed: {}", to, e))?;
+    Ok(path)
+}
+
+/// Current and latest schema version plus applied migrations, newest first.
+pub fn get_schema_status(conn: &Connection) -> AppResult<SchemaStatus> {
+    let mut stmt = conn
+        .prepare_cached("SELECT version, name, applied_at, backup_path FROM schema_migrations ORDER BY version DESC")?;
+    let rows = stmt
+        .query_map([], |row| {
+            Ok(MigrationRecord {
+                version: row.get(0)?,
+                name: row.get(1)?,
+                applied_at: row.get(2)?,
+                backup_path: row.get(3)?,
+            })
+        })?;
+    let mut history = vec![];
+    for row in rows {
+        history.push(row?);
+    }
+    Ok(SchemaStatus {
+        current_version: schema_version(conn)?,
+        latest_version: latest_schema_version(),
+        history,
+    })
+}
+
+/// Tables as they existed before versioned migrations. IF NOT EXISTS so databases created by older builds pass through.
+fn m001_initial_schema(conn: &Connection) -> AppResult<()> {
     conn.execute_batch(
         "
         CREATE TABLE IF NOT EXISTS kv_store (
@@ -123,8 +299,536 @@ fn init_schema(conn: &Connection) -> Result<(), String> {
             status TEXT NOT NULL DEFAULT 'pending'
         );
         ",
+    )?;
+    Ok(())
+}
+
+/// implementation_log.status was added to CREATE TABLE without an upgrade path; add it to older databases.
+fn m002_implementation_log_status(conn: &Connection) -> AppResult<()> {
+    add_column_if_missing(conn, "implementation_log", "status", "TEXT NOT NULL DEFAULT 'pending'")
+}
+
+/// Commit → ticket links from git log scanning, and the commit hash on implementation_log.
+fn m003_plan_ticket_commits(conn: &Connection) -> AppResult<()> {
+    conn.execute_batch(
+        "
+        CREATE TABLE IF NOT EXISTS plan_ticket_commits (
+            project_id TEXT NOT NULL,
+            ticket_number INTEGER NOT NULL,
+            commit_hash TEXT NOT NULL,
+            subject TEXT NOT NULL DEFAULT '',
+            closes INTEGER NOT NULL DEFAULT 0,
+            committed_at TEXT NOT NULL,
+            created_at TEXT NOT NULL,
+            PRIMARY KEY (project_id, ticket_number, commit_hash)
+        );
+        ",
+    )?;
+    add_column_if_missing(conn, "implementation_log", "commit_hash", "TEXT")?;
+    Ok(())
+}
+
+/// Projects move from the kv_store "projects" JSON array into a `projects` table with link tables, and plan_tickets /
+/// milestones / ideas get a foreign key to it. Plan tickets and milestones of projects missing from the blob (left
+/// behind by the old delete_project, which never cleaned up) get a placeholder "Recovered project" so nothing is lost;
+/// ideas of missing projects become global. Links to missing tickets/features/prompts/ideas/designs are dropped.
+fn m004_relational_projects(conn: &Connection) -> AppResult<()> {
+    conn.execute_batch(
+        "
+        CREATE TABLE IF NOT EXISTS projects (
+            id TEXT PRIMARY KEY,
+            name TEXT NOT NULL,
+            description TEXT,
+            repo_path TEXT,
+            run_port INTEGER,
+            architecture_ids TEXT,
+            entity_categories TEXT,
+            spec_files TEXT,
+            spec_files_tickets TEXT,
+            spec_files_features TEXT,
+            created_at TEXT,
+            updated_at TEXT
+        );
+        ",
+    )?;
+
+    let blob: Option<String> = conn
+        .query_row("SELECT value FROM kv_store WHERE key = ?1", params![KV_PROJECTS], |row| row.get(0))
+        .ok();
+    let legacy: Vec<serde_json::Value> = blob
+        .and_then(|s| serde_json::from_str(&s).ok())
+        .unwrap_or_default();
+    // Legacy links, inserted once the link tables exist: (table, project_id, target id, position).
+    let mut legacy_links: Vec<(&str, String, String, i64)> = vec![];
+    for p in &legacy {
+        let Some(id) = p.get("id").and_then(|v| v.as_str()).filter(|s| !s.is_empty()) else {
+            continue;
+        };
+        let text = |key: &str| p.get(key).and_then(|v| v.as_str()).map(String::from);
+        let json = |key: &str| p.get(key).filter(|v| !v.is_null()).map(|v| v.to_string());
+        conn.execute(
+            "INSERT OR IGNORE INTO projects (id, name, description, repo_path, run_port, architecture_ids, entity_categories,
+                spec_files, spec_files_tickets, spec_files_features, created_at, updated_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12)",
+            params![
+                id,
+                text("name").unwrap_or_default(),
+                text("description"),
+                text("repoPath"),
+                p.get("runPort").and_then(|v| v.as_i64()),
+                json("architectureIds"),
+                json("entityCategories"),
+                json("specFiles"),
+                json("specFilesTickets"),
+                json("specFilesFeatures"),
+                text("createdAt"),
+                text("updatedAt"),
+            ],
+        )?;
+        for (key, table) in [
+            ("ticketIds", "project_tickets"),
+            ("featureIds", "project_features"),
+            ("promptIds", "project_prompts"),
+            ("ideaIds", "project_ideas"),
+            ("designIds", "project_designs"),
+        ] {
+            let ids = p.get(key).and_then(|v| v.as_array()).cloned().unwrap_or_default();
+            for (pos, v) in ids.iter().enumerate() {
+                let target = match v {
+                    serde_json::Value::String(s) => s.clone(),
+                    serde_json::Value::Number(n) => n.to_string(),
+                    _ => continue,
+                };
+                legacy_links.push((table, id.to_string(), target, pos as i64));
+            }
+        }
+    }
+
+    let orphan_rows: i64 = conn.query_row(
+        "SELECT (SELECT COUNT(*) FROM plan_tickets WHERE project_id NOT IN (SELECT id FROM projects))
+              + (SELECT COUNT(*) FROM milestones WHERE project_id NOT IN (SELECT id FROM projects))",
+        [],
+        |row| row.get(0),
+    )?;
+    if orphan_rows > 0 {
+        let now = chrono::Utc::now().to_rfc3339();
+        let recovered = conn.execute(
+            "INSERT INTO projects (id, name, description, created_at, updated_at)
+             SELECT project_id, 'Recovered project (' || project_id || ')',
+                 'Created by the relational projects migration for plan tickets and milestones whose project was missing.',
+                 ?1, ?1
+             FROM (SELECT project_id FROM plan_tickets UNION SELECT project_id FROM milestones)
+             WHERE project_id NOT IN (SELECT id FROM projects)",
+            params![now],
+        )?;
+        eprintln!(
+            "Migration 4: {} plan ticket/milestone rows referenced missing projects; created {} placeholder project(s)",
+            orphan_rows, recovered
+        );
+    }
+
+    // SQLite cannot add a foreign key to an existing table: rebuild plan_tickets, milestones and ideas.
+    conn.execute_batch(
+        "
+        CREATE TABLE plan_tickets_new (
+            id TEXT PRIMARY KEY,
+            project_id TEXT NOT NULL REFERENCES projects(id) ON DELETE CASCADE,
+            number INTEGER NOT NULL,
+            title TEXT NOT NULL,
+            description TEXT,
+            priority TEXT NOT NULL DEFAULT 'P1',
+            feature_name TEXT NOT NULL DEFAULT 'General',
+            done INTEGER NOT NULL DEFAULT 0,
+            status TEXT NOT NULL DEFAULT 'Todo',
+            milestone_id INTEGER,
+            idea_id INTEGER,
+            agents TEXT,
+            created_at TEXT NOT NULL,
+            updated_at TEXT NOT NULL,
+            UNIQUE(project_id, number)
+        );
+        INSERT INTO plan_tickets_new (id, project_id, number, title, description, priority, feature_name, done, status,
+            milestone_id, idea_id, agents, created_at, updated_at)
+            SELECT id, project_id, number, title, description, priority, feature_name, done, status,
+                milestone_id, idea_id, agents, created_at, updated_at
+            FROM plan_tickets WHERE project_id IN (SELECT id FROM projects);
+        DROP TABLE plan_tickets;
+        ALTER TABLE plan_tickets_new RENAME TO plan_tickets;
+
+        CREATE TABLE milestones_new (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            project_id TEXT NOT NULL REFERENCES projects(id) ON DELETE CASCADE,
+            name TEXT NOT NULL,
+            slug TEXT NOT NULL,
+            content TEXT,
+            created_at TEXT NOT NULL,
+            updated_at TEXT NOT NULL
+        );
+        INSERT INTO milestones_new (id, project_id, name, slug, content, created_at, updated_at)
+            SELECT id, project_id, name, slug, content, created_at, updated_at
+            FROM milestones WHERE project_id IN (SELECT id FROM projects);
+        DROP TABLE milestones;
+        ALTER TABLE milestones_new RENAME TO milestones;
+
+        CREATE TABLE ideas_new (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            project_id TEXT REFERENCES projects(id) ON DELETE CASCADE,
+            title TEXT NOT NULL,
+            description TEXT NOT NULL,
+            category TEXT NOT NULL DEFAULT 'other',
+            body TEXT,
+            source TEXT NOT NULL DEFAULT 'manual',
+            created_at TEXT NOT NULL,
+            updated_at TEXT NOT NULL
+        );
+        INSERT INTO ideas_new (id, project_id, title, description, category, body, source, created_at, updated_at)
+            SELECT id, CASE WHEN project_id IN (SELECT id FROM projects) THEN project_id END,
+                title, description, category, body, source, created_at, updated_at
+            FROM ideas;
+        DROP TABLE ideas;
+        ALTER TABLE ideas_new RENAME TO ideas;
+
+        CREATE INDEX IF NOT EXISTS idx_milestones_project ON milestones(project_id);
+        CREATE INDEX IF NOT EXISTS idx_ideas_project ON ideas(project_id);
+
+        CREATE TABLE IF NOT EXISTS project_tickets (
+            project_id TEXT NOT NULL REFERENCES projects(id) ON DELETE CASCADE,
+            ticket_id TEXT NOT NULL REFERENCES tickets(id) ON DELETE CASCADE,
+            position INTEGER NOT NULL DEFAULT 0,
+            PRIMARY KEY (project_id, ticket_id)
+        );
+        CREATE TABLE IF NOT EXISTS project_features (
+            project_id TEXT NOT NULL REFERENCES projects(id) ON DELETE CASCADE,
+            feature_id TEXT NOT NULL REFERENCES features(id) ON DELETE CASCADE,
+            position INTEGER NOT NULL DEFAULT 0,
+            PRIMARY KEY (project_id, feature_id)
+        );
+        CREATE TABLE IF NOT EXISTS project_prompts (
+            project_id TEXT NOT NULL REFERENCES projects(id) ON DELETE CASCADE,
+            prompt_id TEXT NOT NULL REFERENCES prompts(id) ON DELETE CASCADE,
+            position INTEGER NOT NULL DEFAULT 0,
+            PRIMARY KEY (project_id, prompt_id)
+        );
+        CREATE TABLE IF NOT EXISTS project_ideas (
+            project_id TEXT NOT NULL REFERENCES projects(id) ON DELETE CASCADE,
+            idea_id INTEGER NOT NULL REFERENCES ideas(id) ON DELETE CASCADE,
+            position INTEGER NOT NULL DEFAULT 0,
+            PRIMARY KEY (project_id, idea_id)
+        );
+        CREATE TABLE IF NOT EXISTS project_designs (
+            project_id TEXT NOT NULL REFERENCES projects(id) ON DELETE CASCADE,
+            design_id TEXT NOT NULL REFERENCES designs(id) ON DELETE CASCADE,
+            position INTEGER NOT NULL DEFAULT 0,
+            PRIMARY KEY (project_id, design_id)
+        );
+        ",
+    )?;
+
+    for (table, project_id, target, pos) in legacy_links {
+        let (column, target_table) = project_link_columns(table);
+        conn.execute(
+            &format!(
+                "INSERT OR IGNORE INTO {table} (project_id, {column}, position)
+                 SELECT ?1, ?2, ?3 WHERE EXISTS (SELECT 1 FROM {target_table} WHERE id = ?2)",
+                table = table,
+                column = column,
+                target_table = target_table
+            ),
+            params![project_id, target, pos],
+        )?;
+    }
+    conn.execute("DELETE FROM kv_store WHERE key = ?1", params![KV_PROJECTS])?;
+    Ok(())
+}
+
+/// Last-synced state of each plan ticket in .cursor/7. planner/tickets.md, so a sync can tell which side changed.
+fn m005_plan_ticket_md_sync(conn: &Connection) -> AppResult<()> {
+    conn.execute_batch(
+        "
+        CREATE TABLE IF NOT EXISTS plan_ticket_md_sync (
+            project_id TEXT NOT NULL REFERENCES projects(id) ON DELETE CASCADE,
+            number INTEGER NOT NULL,
+            fingerprint TEXT NOT NULL,
+            synced_at TEXT NOT NULL,
+            PRIMARY KEY (project_id, number)
+        );
+        ",
+    )?;
+    Ok(())
+}
+
+/// Per-project workflow columns and transitions; tickets get a position within their column, and the in-progress
+/// list from plan_kanban_state becomes the "In progress" status. plan_kanban_state stays for the web API.
+fn m006_kanban_workflow(conn: &Connection) -> AppResult<()> {
+    conn.execute_batch(
+        "
+        CREATE TABLE IF NOT EXISTS plan_workflow_columns (
+            project_id TEXT NOT NULL REFERENCES projects(id) ON DELETE CASCADE,
+            name TEXT NOT NULL,
+            position INTEGER NOT NULL,
+            wip_limit INTEGER,
+            done INTEGER NOT NULL DEFAULT 0,
+            PRIMARY KEY (project_id, name)
+        );
+        CREATE TABLE IF NOT EXISTS plan_workflow_transitions (
+            project_id TEXT NOT NULL,
+            from_status TEXT NOT NULL,
+            to_status TEXT NOT NULL,
+            PRIMARY KEY (project_id, from_status, to_status),
+            FOREIGN KEY (project_id, from_status) REFERENCES plan_workflow_columns(project_id, name) ON DELETE CASCADE,
+            FOREIGN KEY (project_id, to_status) REFERENCES plan_workflow_columns(project_id, name) ON DELETE CASCADE
+        );
+        ",
+    )?;
+    add_column_if_missing(conn, "plan_tickets", "position", "INTEGER NOT NULL DEFAULT 0")?;
+    conn.execute_batch(
+        "
+        UPDATE plan_tickets SET status = CASE WHEN done = 1 THEN 'Done' ELSE 'Todo' END;
+        UPDATE plan_tickets SET status = 'In progress'
+            WHERE done = 0 AND id IN (
+                SELECT j.value FROM plan_kanban_state k, json_each(k.in_progress_ids) j
+                WHERE k.project_id = plan_tickets.project_id
+            );
+        UPDATE plan_tickets SET position = (
+            SELECT COUNT(*) FROM plan_tickets t
+            WHERE t.project_id = plan_tickets.project_id AND t.status = plan_tickets.status AND t.number < plan_tickets.number
+        );
+        CREATE INDEX IF NOT EXISTS idx_plan_tickets_column ON plan_tickets(project_id, status, position);
+        ",
+    )?;
+    Ok(())
+}
+
+/// "Blocked by" edges between plan tickets of the same project.
+fn m007_plan_ticket_dependencies(conn: &Connection) -> AppResult<()> {
+    conn.execute_batch(
+        "
+        CREATE TABLE IF NOT EXISTS plan_ticket_dependencies (
+            ticket_id TEXT NOT NULL REFERENCES plan_tickets(id) ON DELETE CASCADE,
+            depends_on_id TEXT NOT NULL REFERENCES plan_tickets(id) ON DELETE CASCADE,
+            created_at TEXT NOT NULL,
+            PRIMARY KEY (ticket_id, depends_on_id),
+            CHECK (ticket_id != depends_on_id)
+        );
+        CREATE INDEX IF NOT EXISTS idx_plan_ticket_dependencies_depends_on ON plan_ticket_dependencies(depends_on_id);
+        ",
+    )?;
+    Ok(())
+}
+
+/// Audit trail of plan ticket changes. ticket_id has no foreign key so history outlives the ticket.
+fn m008_plan_ticket_events(conn: &Connection) -> AppResult<()> {
+    conn.execute_batch(
+        "
+        CREATE TABLE IF NOT EXISTS plan_ticket_events (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            project_id TEXT NOT NULL REFERENCES projects(id) ON DELETE CASCADE,
+            ticket_id TEXT NOT NULL,
+            ticket_number INTEGER NOT NULL,
+            kind TEXT NOT NULL,
+            field TEXT,
+            old_value TEXT,
+            new_value TEXT,
+            actor_kind TEXT NOT NULL DEFAULT 'user',
+            actor_id TEXT,
+            created_at TEXT NOT NULL
+        );
+        CREATE INDEX IF NOT EXISTS idx_plan_ticket_events_ticket ON plan_ticket_events(ticket_id, id);
+        CREATE INDEX IF NOT EXISTS idx_plan_ticket_events_project ON plan_ticket_events(project_id, id);
+        ",
+    )?;
+    Ok(())
+}
+
+/// Milestone dates (YYYY-MM-DD), explicit order (seeded from the old name order) and status.
+fn m009_milestone_schedule(conn: &Connection) -> AppResult<()> {
+    add_column_if_missing(conn, "milestones", "start_date", "TEXT")?;
+    add_column_if_missing(conn, "milestones", "due_date", "TEXT")?;
+    add_column_if_missing(conn, "milestones", "position", "INTEGER NOT NULL DEFAULT 0")?;
+    add_column_if_missing(conn, "milestones", "status", "TEXT NOT NULL DEFAULT 'planned'")?;
+    conn.execute_batch(
+        "
+        UPDATE milestones SET position = (
+            SELECT COUNT(*) FROM milestones o
+            WHERE o.project_id = milestones.project_id
+                AND (o.name < milestones.name OR (o.name = milestones.name AND o.id < milestones.id))
+        );
+        ",
+    )?;
+    Ok(())
+}
+
+/// Idea lifecycle: new, accepted, rejected, converted (into a milestone and tickets).
+fn m010_idea_status(conn: &Connection) -> AppResult<()> {
+    add_column_if_missing(conn, "ideas", "status", "TEXT NOT NULL DEFAULT 'new'")
+}
+
+/// Optional RICE/ICE factors on ideas.
+fn m011_idea_scoring(conn: &Connection) -> AppResult<()> {
+    for column in ["reach", "impact", "confidence", "effort", "ease"] {
+        add_column_if_missing(conn, "ideas", column, "REAL")?;
+    }
+    Ok(())
+}
+
+/// Indexed columns per search kind: (kind, source table, project id, title, body). `r.` stands for the NEW/OLD row in
+/// triggers and the table itself in the backfill.
+const SEARCH_SOURCES: &[(&str, &str, &str, &str, &str)] = &[
+    (
+        "plan_ticket",
+        "plan_tickets",
+        "r.project_id",
+        "'#' || r.number || ' ' || r.title",
+        "COALESCE(r.description, '') || ' ' || r.feature_name",
+    ),
+    ("ticket", "tickets", "NULL", "r.title", "r.description"),
+    ("idea", "ideas", "r.project_id", "r.title", "r.description || ' ' || COALESCE(r.body, '')"),
+    ("milestone", "milestones", "r.project_id", "r.name", "COALESCE(r.content, '')"),
+    ("prompt", "prompts", "NULL", "r.title", "r.content"),
+    ("run_log", "implementation_log", "r.project_id", "r.ticket_title", "r.summary || ' ' || r.files_changed"),
+];
+
+/// `INSERT INTO search_index ... SELECT` for one search source, reading the row as `row` (NEW, or the table itself).
+fn search_insert_sql(source: &(&str, &str, &str, &str, &str), row: &str) -> String {
+    let (kind, _, project_id, title, body) = source;
+    let column = |expr: &str| expr.replace("r.", &format!("{}.", row));
+    format!(
+        "INSERT INTO search_index (kind, ref_id, project_id, title, body) SELECT '{}', {}.id, {}, {}, {}",
+        kind,
+        row,
+        column(project_id),
+        column(title),
+        column(body),
     )
-    .map_err(|e| e.to_string())?;
+}
+
+/// Source columns a search source reads (the `r.` references), plus the id.
+fn search_columns(source: &(&str, &str, &str, &str, &str)) -> Vec<String> {
+    let (_, _, project_id, title, body) = source;
+    let mut columns = vec!["id".to_string()];
+    for expr in [project_id, title, body] {
+        for part in expr.split("r.").skip(1) {
+            let name: String = part.chars().take_while(|c| c.is_alphanumeric() || *c == '_').collect();
+            if !columns.contains(&name) {
+                columns.push(name);
+            }
+        }
+    }
+    columns
+}
+
+/// FTS5 index over plan tickets, tickets, ideas, milestones, prompts and implementation log entries, kept in sync by
+/// triggers on each source table and backfilled from existing rows.
+fn m012_search_index(conn: &Connection) -> AppResult<()> {
+    conn.execute_batch(
+        "CREATE VIRTUAL TABLE IF NOT EXISTS search_index USING fts5(
+            kind UNINDEXED,
+            ref_id UNINDEXED,
+            project_id UNINDEXED,
+            title,
+            body,
+            tokenize = 'unicode61 remove_diacritics 2'
+        );",
+    )?;
+    for source in SEARCH_SOURCES {
+        let (kind, table, ..) = source;
+        let delete = format!("DELETE FROM search_index WHERE kind = '{}' AND ref_id = old.id", kind);
+        conn.execute_batch(&format!(
+            "
+            CREATE TRIGGER IF NOT EXISTS search_{kind}_insert AFTER INSERT ON {table} BEGIN {new}; END;
+            CREATE TRIGGER IF NOT EXISTS search_{kind}_update AFTER UPDATE ON {table} BEGIN {delete}; {new}; END;
+            CREATE TRIGGER IF NOT EXISTS search_{kind}_delete AFTER DELETE ON {table} BEGIN {delete}; END;
+            {backfill} FROM {table};
+            ",
+            new = search_insert_sql(source, "new"),
+            backfill = search_insert_sql(source, table),
+        ))?;
+    }
+    Ok(())
+}
+
+/// Tables whose deletes go to the trash.
+const SOFT_DELETE_TABLES: &[&str] = &["projects", "plan_tickets", "tickets", "features", "prompts", "designs"];
+
+/// Soft deletion: a deleted_at timestamp on projects, plan tickets, tickets, features, prompts and designs. Rows in the
+/// trash drop out of the search index (their update trigger only re-indexes live rows).
+fn m013_soft_delete(conn: &Connection) -> AppResult<()> {
+    for table in SOFT_DELETE_TABLES {
+        add_column_if_missing(conn, table, "deleted_at", "TEXT")?;
+    }
+    for source in SEARCH_SOURCES.iter().filter(|(_, table, ..)| SOFT_DELETE_TABLES.contains(table)) {
+        let (kind, table, ..) = source;
+        conn.execute_batch(&format!(
+            "
+            DROP TRIGGER IF EXISTS search_{kind}_update;
+            CREATE TRIGGER search_{kind}_update AFTER UPDATE ON {table} BEGIN
+                DELETE FROM search_index WHERE kind = '{kind}' AND ref_id = old.id;
+                {new} WHERE new.deleted_at IS NULL;
+            END;
+            ",
+            new = search_insert_sql(source, "new"),
+        ))?;
+    }
+    Ok(())
+}
+
+/// Rows repair_db took out of their table (orphans, foreign key violations) as JSON, for inspection or manual recovery.
+fn m014_quarantine(conn: &Connection) -> AppResult<()> {
+    conn.execute_batch(
+        "
+        CREATE TABLE IF NOT EXISTS quarantine (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            source_table TEXT NOT NULL,
+            row_id INTEGER NOT NULL,
+            reason TEXT NOT NULL,
+            data TEXT NOT NULL,
+            quarantined_at TEXT NOT NULL
+        );
+        ",
+    )?;
+    Ok(())
+}
+
+/// Re-index a row only when an update changes a column the index reads (or moves the row in or out of the trash).
+/// The delete filters on ref_id, which FTS5 cannot index, so it scans search_index; board moves and column
+/// renumbering touch only status and position and skip it.
+fn m015_search_update_guards(conn: &Connection) -> AppResult<()> {
+    for source in SEARCH_SOURCES {
+        let (kind, table, ..) = source;
+        let soft_delete = SOFT_DELETE_TABLES.contains(table);
+        let mut columns = search_columns(source);
+        if soft_delete {
+            columns.push("deleted_at".to_string());
+        }
+        let changed: Vec<String> = columns.iter().map(|c| format!("old.{c} IS NOT new.{c}")).collect();
+        conn.execute_batch(&format!(
+            "
+            DROP TRIGGER IF EXISTS search_{kind}_update;
+            CREATE TRIGGER search_{kind}_update AFTER UPDATE ON {table} WHEN {changed} BEGIN
+                DELETE FROM search_index WHERE kind = '{kind}' AND ref_id = old.id;
+                {new}{live};
+            END;
+            ",
+            changed = changed.join(" OR "),
+            new = search_insert_sql(source, "new"),
+            live = if soft_delete { " WHERE new.deleted_at IS NULL" } else { "" },
+        ))?;
+    }
+    Ok(())
+}
+
+/// ALTER TABLE ADD COLUMN for databases created before the column existed.
+fn add_column_if_missing(conn: &Connection, table: &str, column: &str, decl: &str) -> AppResult<()> {
+    let exists: bool = conn
+        .query_row(
+            &format!("SELECT COUNT(*) FROM pragma_table_info('{}') WHERE name = ?1", table),
+            params![column],
+            |row| row.get::<_, i64>(0),
+        )?
+        > 0;
+    if !exists {
+        conn.execute_batch(&format!("ALTER TABLE {} ADD COLUMN {} {}", table, column, decl))?;
+    }
     Ok(())
 }
 
@@ -157,7 +861,7 @@ pub fn get_data_dir(conn: &Connection, fallback: &Path) -> PathBuf {
 }
 
 
-pub fn get_all_projects(conn: &Connection) -> Result<Vec<String>, String> {
+pub fn get_all_projects(conn: &Connection) -> AppResult<Vec<String>> {
     let content: String = match conn.query_row(
         "SELECT value FROM kv_store WHERE key = ?1",
         params![KV_ALL_PROJECTS],
@@ -167,89 +871,375 @@ pub fn get_all_projects(conn: &Connection) -> Result<Vec<String>, String> {
         Err(rusqlite::Error::QueryReturnedNoRows) => {
             return Ok(vec![]);
         }
-        Err(e) => return Err(e.to_string()),
-    };
-    serde_json::from_str(&content).map_err(|e| e.to_string())
-}
-
-/// Full projects list as JSON array (same shape as data/projects.json). Default "[]".
-pub fn get_projects_json(conn: &Connection) -> Result<String, String> {
-    let content: String = match conn.query_row(
-        "SELECT value FROM kv_store WHERE key = ?1",
-        params![KV_PROJECTS],
-        |row| row.get(0),
-    ) {
-        Ok(v) => v,
-        Err(rusqlite::Error::QueryReturnedNoRows) => return Ok("[]".to_string()),
-        Err(e) => return Err(e.to_string()),
+        Err(e) => return Err(e.into()),
     };
-    Ok(content)
+    serde_json::from_str(&content).map_err(AppError::from)
 }
 
-pub fn save_projects_json(conn: &Connection, json: &str) -> Result<(), String> {
-    conn.execute(
-        "INSERT OR REPLACE INTO kv_store (key, value) VALUES (?1, ?2)",
-        params![KV_PROJECTS, json],
-    )
-    .map_err(|e| e.to_string())?;
-    Ok(())
-}
+/// Project link tables: (table, id column, referenced table).
+const PROJECT_LINK_TABLES: &[(&str, &str, &str)] = &[
+    ("project_tickets", "ticket_id", "tickets"),
+    ("project_features", "feature_id", "features"),
+    ("project_prompts", "prompt_id", "prompts"),
+    ("project_ideas", "idea_id", "ideas"),
+    ("project_designs", "design_id", "designs"),
+];
 
-/// Key-value pair for kv_store table (for Data view).
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct KvEntry {
-    pub key: String,
-    pub value: String,
+fn project_link_columns(table: &str) -> (&'static str, &'static str) {
+    PROJECT_LINK_TABLES
+        .iter()
+        .find(|(t, _, _)| *t == table)
+        .map(|(_, column, target)| (*column, *target))
+        .unwrap_or(("", ""))
 }
 
-pub fn get_kv_store_entries(conn: &Connection) -> Result<Vec<KvEntry>, String> {
+/// Linked ids in the order they were saved.
+fn get_project_links(conn: &Connection, table: &str, project_id: &str) -> AppResult<Vec<String>> {
+    let (column, _) = project_link_columns(table);
     let mut stmt = conn
-        .prepare("SELECT key, value FROM kv_store ORDER BY key")
-        .map_err(|e| e.to_string())?;
+        .prepare_cached(&format!(
+            "SELECT CAST({column} AS TEXT) FROM {table} WHERE project_id = ?1 ORDER BY position, rowid",
+            column = column,
+            table = table
+        ))?;
     let rows = stmt
-        .query_map([], |row| {
-            Ok(KvEntry {
-                key: row.get(0)?,
-                value: row.get(1)?,
-            })
-        })
-        .map_err(|e| e.to_string())?;
+        .query_map(params![project_id], |row| row.get::<_, String>(0))?;
     let mut out = vec![];
     for row in rows {
-        out.push(row.map_err(|e| e.to_string())?);
+        out.push(row?);
     }
     Ok(out)
 }
 
-pub fn get_active_projects(conn: &Connection) -> Result<Vec<String>, String> {
-    let content: String = match conn.query_row(
-        "SELECT value FROM kv_store WHERE key = ?1",
-        params![KV_CURSOR_PROJECTS],
-        |row| row.get(0),
-    ) {
-        Ok(v) => v,
-        Err(rusqlite::Error::QueryReturnedNoRows) => return Ok(vec![]),
-        Err(e) => return Err(e.to_string()),
-    };
-    serde_json::from_str(&content).map_err(|e| e.to_string())
+/// Replace a project's links. Unknown ids are rejected so the link tables only ever point at real rows.
+fn set_project_links(conn: &Connection, table: &str, project_id: &str, ids: &[String]) -> AppResult<()> {
+    let (column, target) = project_link_columns(table);
+    conn.execute(&format!("DELETE FROM {} WHERE project_id = ?1", table), params![project_id])?;
+    for (pos, id) in ids.iter().enumerate() {
+        let inserted = conn
+            .execute(
+                &format!(
+                    "INSERT OR IGNORE INTO {table} (project_id, {column}, position)
+                     SELECT ?1, ?2, ?3 WHERE EXISTS (SELECT 1 FROM {target} WHERE id = ?2)",
+                    table = table,
+                    column = column,
+                    target = target
+                ),
+                params![project_id, id, pos as i64],
+            )?;
+        if inserted == 0 && !ids[..pos].contains(id) {
+            return Err(AppError::invalid(format!("Unknown {} id: {}", target.trim_end_matches('s'), id)));
+        }
+    }
+    Ok(())
 }
 
-pub fn save_active_projects(conn: &Connection, projects: &[String]) -> Result<(), String> {
-    let content = serde_json::to_string(projects).map_err(|e| e.to_string())?;
-    conn.execute(
-        "INSERT OR REPLACE INTO kv_store (key, value) VALUES (?1, ?2)",
-        params![KV_CURSOR_PROJECTS, content],
-    )
-    .map_err(|e| e.to_string())?;
-    Ok(())
+fn json_column<T: serde::de::DeserializeOwned>(value: Option<String>) -> Option<T> {
+    value.and_then(|s| serde_json::from_str(&s).ok())
+}
+
+fn project_from_row(conn: &Connection, row: ProjectRow) -> AppResult<super::Project> {
+    let links = |table: &str| get_project_links(conn, table, &row.id);
+    let designs = links("project_designs")?;
+    Ok(super::Project {
+        prompt_ids: links("project_prompts")?.iter().filter_map(|s| s.parse().ok()).collect(),
+        ticket_ids: links("project_tickets")?,
+        feature_ids: links("project_features")?,
+        idea_ids: links("project_ideas")?.iter().filter_map(|s| s.parse().ok()).collect(),
+        design_ids: if designs.is_empty() { None } else { Some(designs) },
+        id: row.id,
+        name: row.name,
+        description: row.description,
+        repo_path: row.repo_path,
+        run_port: row.run_port,
+        architecture_ids: json_column(row.architecture_ids),
+        entity_categories: json_column(row.entity_categories),
+        spec_files: json_column(row.spec_files),
+        spec_files_tickets: json_column(row.spec_files_tickets),
+        spec_files_features: json_column(row.spec_files_features),
+        created_at: row.created_at,
+        updated_at: row.updated_at,
+    })
+}
+
+/// Raw `projects` row; JSON columns are decoded in project_from_row.
+struct ProjectRow {
+    id: String,
+    name: String,
+    description: Option<String>,
+    repo_path: Option<String>,
+    run_port: Option<u16>,
+    architecture_ids: Option<String>,
+    entity_categories: Option<String>,
+    spec_files: Option<String>,
+    spec_files_tickets: Option<String>,
+    spec_files_features: Option<String>,
+    created_at: Option<String>,
+    updated_at: Option<String>,
 }
 
-pub fn get_tickets(conn: &Connection) -> Result<Vec<super::Ticket>, String> {
+const PROJECT_COLUMNS: &str = "id, name, description, repo_path, run_port, architecture_ids, entity_categories, \
+     spec_files, spec_files_tickets, spec_files_features, created_at, updated_at";
+
+fn query_projects(conn: &Connection, filter: &str, args: &[&dyn rusqlite::ToSql]) -> AppResult<Vec<super::Project>> {
     let mut stmt = conn
-        .prepare(
-            "SELECT id, title, description, status, priority, created_at, updated_at, prompt_ids, project_paths FROM tickets ORDER BY updated_at DESC",
-        )
-        .map_err(|e| e.to_string())?;
+        .prepare_cached(&format!(
+            "SELECT {} FROM projects WHERE deleted_at IS NULL {} ORDER BY rowid",
+            PROJECT_COLUMNS, filter
+        ))?;
+    let rows = stmt
+        .query_map(args, |row| {
+            Ok(ProjectRow {
+                id: row.get(0)?,
+                name: row.get(1)?,
+                description: row.get(2)?,
+                repo_path: row.get(3)?,
+                run_port: row.get(4)?,
+                architecture_ids: row.get(5)?,
+                entity_categories: row.get(6)?,
+                spec_files: row.get(7)?,
+                spec_files_tickets: row.get(8)?,
+                spec_files_features: row.get(9)?,
+                created_at: row.get(10)?,
+                updated_at: row.get(11)?,
+            })
+        })?;
+    let mut raw = vec![];
+    for row in rows {
+        raw.push(row?);
+    }
+    raw.into_iter().map(|r| project_from_row(conn, r)).collect()
+}
+
+/// All projects (outside the trash) in creation order, with their linked ids.
+pub fn get_projects(conn: &Connection) -> AppResult<Vec<super::Project>> {
+    query_projects(conn, "", &[])
+}
+
+pub fn get_project(conn: &Connection, id: &str) -> AppResult<Option<super::Project>> {
+    Ok(query_projects(conn, "AND id = ?1", &[&id])?.into_iter().next())
+}
+
+/// Insert or update a project row and replace its links, atomically.
+pub fn save_project(conn: &Connection, p: &super::Project) -> AppResult<()> {
+    let json = |v: Option<serde_json::Value>| v.map(|v| v.to_string());
+    let tx = conn.unchecked_transaction()?;
+    tx.execute(
+        &format!(
+            "INSERT INTO projects ({}) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12)
+             ON CONFLICT(id) DO UPDATE SET name = excluded.name, description = excluded.description,
+                repo_path = excluded.repo_path, run_port = excluded.run_port, architecture_ids = excluded.architecture_ids,
+                entity_categories = excluded.entity_categories, spec_files = excluded.spec_files,
+                spec_files_tickets = excluded.spec_files_tickets, spec_files_features = excluded.spec_files_features,
+                created_at = excluded.created_at, updated_at = excluded.updated_at, deleted_at = NULL",
+            PROJECT_COLUMNS
+        ),
+        params![
+            p.id,
+            p.name,
+            p.description,
+            p.repo_path,
+            p.run_port,
+            json(p.architecture_ids.as_ref().map(|v| serde_json::json!(v))),
+            json(p.entity_categories.clone()),
+            json(p.spec_files.as_ref().map(|v| serde_json::json!(v))),
+            json(p.spec_files_tickets.as_ref().map(|v| serde_json::json!(v))),
+            json(p.spec_files_features.as_ref().map(|v| serde_json::json!(v))),
+            p.created_at,
+            p.updated_at,
+        ],
+    )?;
+    let to_strings = |ids: &[i64]| ids.iter().map(|i| i.to_string()).collect::<Vec<_>>();
+    set_project_links(&tx, "project_tickets", &p.id, &p.ticket_ids)?;
+    set_project_links(&tx, "project_features", &p.id, &p.feature_ids)?;
+    set_project_links(&tx, "project_prompts", &p.id, &to_strings(&p.prompt_ids))?;
+    set_project_links(&tx, "project_ideas", &p.id, &to_strings(&p.idea_ids))?;
+    set_project_links(&tx, "project_designs", &p.id, p.design_ids.as_deref().unwrap_or(&[]))?;
+    tx.commit().map_err(AppError::from)
+}
+
+/// Data owned by a project; with the restrict policy a project can only be deleted when all counts are zero.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct ProjectDependents {
+    #[ts(as = "f64")]
+    pub plan_tickets: i64,
+    /// Excludes an empty "General Development" milestone, which is created automatically.
+    #[ts(as = "f64")]
+    pub milestones: i64,
+    #[ts(as = "f64")]
+    pub ideas: i64,
+    #[ts(as = "f64")]
+    pub implementation_log: i64,
+}
+
+impl ProjectDependents {
+    fn is_empty(&self) -> bool {
+        self.plan_tickets == 0 && self.milestones == 0 && self.ideas == 0 && self.implementation_log == 0
+    }
+}
+
+pub fn get_project_dependents(conn: &Connection, project_id: &str) -> AppResult<ProjectDependents> {
+    let count = |sql: &str| -> AppResult<i64> {
+        conn.query_row(sql, params![project_id], |row| row.get(0))
+            .map_err(AppError::from)
+    };
+    Ok(ProjectDependents {
+        plan_tickets: count("SELECT COUNT(*) FROM plan_tickets WHERE project_id = ?1 AND deleted_at IS NULL")?,
+        milestones: conn
+            .query_row(
+                "SELECT COUNT(*) FROM milestones m WHERE m.project_id = ?1 AND NOT (m.name = ?2
+                    AND NOT EXISTS (
+                        SELECT 1 FROM plan_tickets t WHERE t.milestone_id = m.id AND t.deleted_at IS NULL
+                    ))",
+                params![project_id, GENERAL_DEVELOPMENT_NAME],
+                |row| row.get(0),
+            )?,
+        ideas: count("SELECT COUNT(*) FROM ideas WHERE project_id = ?1")?,
+        implementation_log: count("SELECT COUNT(*) FROM implementation_log WHERE project_id = ?1")?,
+    })
+}
+
+/// Move a project to the trash. Its plan tickets, milestones, ideas, kanban state, implementation log and commit links
+/// stay with it until empty_trash; without `cascade` the delete is refused while any of those exist. Returns false
+/// when the project is unknown.
+pub fn delete_project(conn: &Connection, project_id: &str, cascade: bool) -> AppResult<bool> {
+    if get_project(conn, project_id)?.is_none() {
+        return Ok(false);
+    }
+    let dependents = get_project_dependents(conn, project_id)?;
+    if !cascade && !dependents.is_empty() {
+        return Err(AppError::conflict(format!(
+            "Project still has {} plan ticket(s), {} milestone(s), {} idea(s) and {} implementation log entries; \
+             delete them first or use the cascade policy",
+            dependents.plan_tickets, dependents.milestones, dependents.ideas, dependents.implementation_log
+        ))
+        .with_details(serde_json::json!(dependents)));
+    }
+    conn.execute(
+        "UPDATE projects SET deleted_at = ?1 WHERE id = ?2",
+        params![chrono::Utc::now().to_rfc3339(), project_id],
+    )?;
+    Ok(true)
+}
+
+/// Permanently delete a project and everything it owns.
+fn purge_project(conn: &Connection, project_id: &str) -> AppResult<()> {
+    for table in ["plan_kanban_state", "implementation_log", "plan_ticket_commits"] {
+        conn.execute(&format!("DELETE FROM {} WHERE project_id = ?1", table), params![project_id])?;
+    }
+    conn.execute("DELETE FROM kv_store WHERE key = ?1", params![commit_template_key(project_id)])?;
+    // plan_tickets, milestones, ideas and the link tables follow through ON DELETE CASCADE.
+    conn.execute("DELETE FROM projects WHERE id = ?1", params![project_id])?;
+    Ok(())
+}
+
+/// Key-value pair for kv_store table (for Data view).
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct KvEntry {
+    pub key: String,
+    pub value: String,
+}
+
+pub fn get_kv_store_entries(conn: &Connection) -> AppResult<Vec<KvEntry>> {
+    let mut stmt = conn
+        .prepare_cached("SELECT key, value FROM kv_store ORDER BY key")?;
+    let rows = stmt
+        .query_map([], |row| {
+            Ok(KvEntry {
+                key: row.get(0)?,
+                value: row.get(1)?,
+            })
+        })?;
+    let mut out = vec![];
+    for row in rows {
+        out.push(row?);
+    }
+    Ok(out)
+}
+
+pub fn get_active_projects(conn: &Connection) -> AppResult<Vec<String>> {
+    let content: String = match conn.query_row(
+        "SELECT value FROM kv_store WHERE key = ?1",
+        params![KV_CURSOR_PROJECTS],
+        |row| row.get(0),
+    ) {
+        Ok(v) => v,
+        Err(rusqlite::Error::QueryReturnedNoRows) => return Ok(vec![]),
+        Err(e) => return Err(e.into()),
+    };
+    serde_json::from_str(&content).map_err(AppError::from)
+}
+
+pub fn save_active_projects(conn: &Connection, projects: &[String]) -> AppResult<()> {
+    let content = serde_json::to_string(projects)?;
+    conn.execute(
+        "INSERT OR REPLACE INTO kv_store (key, value) VALUES (?1, ?2)",
+        params![KV_CURSOR_PROJECTS, content],
+    )?;
+    Ok(())
+}
+
+/// Move rows whose id is not in `ids` to the trash. The save_* functions upsert the rest, so a save that leaves rows
+/// out can be undone with restore_from_trash instead of wiping the table. A save that would trash every live row (an
+/// empty or unrelated list, as a buggy frontend sends) is refused unless `clear_all` confirms it.
+fn trash_rows_not_in<'a>(
+    conn: &Connection,
+    table: &str,
+    ids: impl Iterator<Item = &'a str>,
+    clear_all: bool,
+) -> AppResult<()> {
+    let ids = serde_json::to_string(&ids.collect::<Vec<_>>())?;
+    let (live, kept): (i64, i64) = conn.query_row(
+        &format!(
+            "SELECT COUNT(*), COALESCE(SUM(id IN (SELECT value FROM json_each(?1))), 0) FROM {} WHERE deleted_at IS NULL",
+            table
+        ),
+        params![ids],
+        |row| Ok((row.get(0)?, row.get(1)?)),
+    )?;
+    if live > 0 && kept == 0 && !clear_all {
+        return Err(AppError::conflict(format!(
+            "Saving would move all {} {} to the trash; pass clear_all to confirm",
+            live, table
+        ))
+        .with_details(serde_json::json!({ "table": table, "count": live })));
+    }
+    conn.execute(
+        &format!(
+            "UPDATE {} SET deleted_at = ?2 WHERE deleted_at IS NULL AND id NOT IN (SELECT value FROM json_each(?1))",
+            table
+        ),
+        params![ids, chrono::Utc::now().to_rfc3339()],
+    )?;
+    Ok(())
+}
+
+/// Upsert by id for the save_* functions. Rows whose columns are unchanged are not written (no updated triggers or
+/// search re-indexing); a row that was in the trash comes back.
+fn upsert_by_id_sql(table: &str, columns: &[&str]) -> String {
+    let placeholders: Vec<String> = (1..=columns.len()).map(|i| format!("?{}", i)).collect();
+    let updates: Vec<String> = columns[1..].iter().map(|c| format!("{0} = excluded.{0}", c)).collect();
+    let changed: Vec<String> = columns[1..].iter().map(|c| format!("{0} IS NOT excluded.{0}", c)).collect();
+    format!(
+        "INSERT INTO {table} ({columns}) VALUES ({placeholders})
+         ON CONFLICT(id) DO UPDATE SET {updates}, deleted_at = NULL
+         WHERE deleted_at IS NOT NULL OR {changed}",
+        table = table,
+        columns = columns.join(", "),
+        placeholders = placeholders.join(", "),
+        updates = updates.join(", "),
+        changed = changed.join(" OR "),
+    )
+}
+
+pub fn get_tickets(conn: &Connection) -> AppResult<Vec<super::Ticket>> {
+    let mut stmt = conn
+        .prepare_cached(
+            "SELECT id, title, description, status, priority, created_at, updated_at, prompt_ids, project_paths FROM tickets \
+             WHERE deleted_at IS NULL ORDER BY updated_at DESC",
+        )?;
     let rows = stmt
         .query_map([], |row| {
             let prompt_ids: Option<String> = row.get(7)?;
@@ -265,24 +1255,37 @@ pub fn get_tickets(conn: &Connection) -> Result<Vec<super::Ticket>, String> {
                 prompt_ids: prompt_ids.and_then(|s| serde_json::from_str(&s).ok()),
                 project_paths: project_paths.and_then(|s| serde_json::from_str(&s).ok()),
             })
-        })
-        .map_err(|e| e.to_string())?;
+        })?;
     let mut out = vec![];
     for row in rows {
-        out.push(row.map_err(|e| e.to_string())?);
+        out.push(row?);
     }
     Ok(out)
 }
 
-pub fn save_tickets(conn: &Connection, tickets: &[super::Ticket]) -> Result<(), String> {
-    conn.execute("DELETE FROM tickets", []).map_err(|e| e.to_string())?;
-    for t in tickets {
-        let prompt_ids = t.prompt_ids.as_ref().and_then(|v| serde_json::to_string(v).ok());
-        let project_paths = t.project_paths.as_ref().and_then(|v| serde_json::to_string(v).ok());
-        conn.execute(
-            "INSERT INTO tickets (id, title, description, status, priority, created_at, updated_at, prompt_ids, project_paths)
-             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)",
-            params![
+/// Replace the tickets with `tickets` in one transaction (see trash_rows_not_in for rows left out).
+pub fn save_tickets(conn: &Connection, tickets: &[super::Ticket], clear_all: bool) -> AppResult<()> {
+    let tx = conn.unchecked_transaction()?;
+    trash_rows_not_in(&tx, "tickets", tickets.iter().map(|x| x.id.as_str()), clear_all)?;
+    {
+        let mut stmt = tx.prepare_cached(&upsert_by_id_sql(
+            "tickets",
+            &[
+                "id",
+                "title",
+                "description",
+                "status",
+                "priority",
+                "created_at",
+                "updated_at",
+                "prompt_ids",
+                "project_paths",
+            ],
+        ))?;
+        for t in tickets {
+            let prompt_ids = t.prompt_ids.as_ref().and_then(|v| serde_json::to_string(v).ok());
+            let project_paths = t.project_paths.as_ref().and_then(|v| serde_json::to_string(v).ok());
+            stmt.execute(params![
                 t.id,
                 t.title,
                 t.description,
@@ -292,19 +1295,19 @@ pub fn save_tickets(conn: &Connection, tickets: &[super::Ticket]) -> Result<(),
                 t.updated_at,
                 prompt_ids,
                 project_paths,
-            ],
-        )
-        .map_err(|e| e.to_string())?;
+            ])?;
+        }
     }
+    tx.commit()?;
     Ok(())
 }
 
-pub fn get_features(conn: &Connection) -> Result<Vec<super::Feature>, String> {
+pub fn get_features(conn: &Connection) -> AppResult<Vec<super::Feature>> {
     let mut stmt = conn
-        .prepare(
-            "SELECT id, title, ticket_ids, prompt_ids, project_paths, created_at, updated_at FROM features ORDER BY updated_at DESC",
-        )
-        .map_err(|e| e.to_string())?;
+        .prepare_cached(
+            "SELECT id, title, ticket_ids, prompt_ids, project_paths, created_at, updated_at FROM features \
+             WHERE deleted_at IS NULL ORDER BY updated_at DESC",
+        )?;
     let rows = stmt
         .query_map([], |row| {
             Ok(super::Feature {
@@ -316,25 +1319,28 @@ pub fn get_features(conn: &Connection) -> Result<Vec<super::Feature>, String> {
                 created_at: row.get(5)?,
                 updated_at: row.get(6)?,
             })
-        })
-        .map_err(|e| e.to_string())?;
+        })?;
     let mut out = vec![];
     for row in rows {
-        out.push(row.map_err(|e| e.to_string())?);
+        out.push(row?);
     }
     Ok(out)
 }
 
-pub fn save_features(conn: &Connection, features: &[super::Feature]) -> Result<(), String> {
-    conn.execute("DELETE FROM features", []).map_err(|e| e.to_string())?;
-    for f in features {
-        let ticket_ids = serde_json::to_string(&f.ticket_ids).map_err(|e| e.to_string())?;
-        let prompt_ids = serde_json::to_string(&f.prompt_ids).map_err(|e| e.to_string())?;
-        let project_paths = serde_json::to_string(&f.project_paths).map_err(|e| e.to_string())?;
-        conn.execute(
-            "INSERT INTO features (id, title, ticket_ids, prompt_ids, project_paths, created_at, updated_at)
-             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
-            params![
+/// Replace the features with `features` in one transaction (see trash_rows_not_in for rows left out).
+pub fn save_features(conn: &Connection, features: &[super::Feature], clear_all: bool) -> AppResult<()> {
+    let tx = conn.unchecked_transaction()?;
+    trash_rows_not_in(&tx, "features", features.iter().map(|x| x.id.as_str()), clear_all)?;
+    {
+        let mut stmt = tx.prepare_cached(&upsert_by_id_sql(
+            "features",
+            &["id", "title", "ticket_ids", "prompt_ids", "project_paths", "created_at", "updated_at"],
+        ))?;
+        for f in features {
+            let ticket_ids = serde_json::to_string(&f.ticket_ids)?;
+            let prompt_ids = serde_json::to_string(&f.prompt_ids)?;
+            let project_paths = serde_json::to_string(&f.project_paths)?;
+            stmt.execute(params![
                 f.id,
                 f.title,
                 ticket_ids,
@@ -342,19 +1348,19 @@ pub fn save_features(conn: &Connection, features: &[super::Feature]) -> Result<(
                 project_paths,
                 f.created_at,
                 f.updated_at,
-            ],
-        )
-        .map_err(|e| e.to_string())?;
+            ])?;
+        }
     }
+    tx.commit()?;
     Ok(())
 }
 
-pub fn get_prompts(conn: &Connection) -> Result<Vec<super::Prompt>, String> {
+pub fn get_prompts(conn: &Connection) -> AppResult<Vec<super::Prompt>> {
     let mut stmt = conn
-        .prepare(
-            "SELECT id, title, content, created_at, updated_at FROM prompts ORDER BY updated_at DESC",
-        )
-        .map_err(|e| e.to_string())?;
+        .prepare_cached(
+            "SELECT id, title, content, created_at, updated_at FROM prompts \
+             WHERE deleted_at IS NULL ORDER BY updated_at DESC",
+        )?;
     let rows = stmt
         .query_map([], |row| {
             Ok(super::Prompt {
@@ -364,40 +1370,43 @@ pub fn get_prompts(conn: &Connection) -> Result<Vec<super::Prompt>, String> {
                 created_at: row.get(3)?,
                 updated_at: row.get(4)?,
             })
-        })
-        .map_err(|e| e.to_string())?;
+        })?;
     let mut out = vec![];
     for row in rows {
-        out.push(row.map_err(|e| e.to_string())?);
+        out.push(row?);
     }
     Ok(out)
 }
 
-pub fn save_prompts(conn: &Connection, prompts: &[super::Prompt]) -> Result<(), String> {
-    conn.execute("DELETE FROM prompts", []).map_err(|e| e.to_string())?;
-    for p in prompts {
-        conn.execute(
-            "INSERT INTO prompts (id, title, content, created_at, updated_at)
-             VALUES (?1, ?2, ?3, ?4, ?5)",
-            params![
+/// Replace the prompts with `prompts` in one transaction (see trash_rows_not_in for rows left out).
+pub fn save_prompts(conn: &Connection, prompts: &[super::Prompt], clear_all: bool) -> AppResult<()> {
+    let tx = conn.unchecked_transaction()?;
+    trash_rows_not_in(&tx, "prompts", prompts.iter().map(|x| x.id.as_str()), clear_all)?;
+    {
+        let mut stmt = tx.prepare_cached(&upsert_by_id_sql(
+            "prompts",
+            &["id", "title", "content", "created_at", "updated_at"],
+        ))?;
+        for p in prompts {
+            stmt.execute(params![
                 p.id,
                 p.title,
                 p.content,
                 p.created_at,
                 p.updated_at,
-            ],
-        )
-        .map_err(|e| e.to_string())?;
+            ])?;
+        }
     }
+    tx.commit()?;
     Ok(())
 }
 
-pub fn get_designs(conn: &Connection) -> Result<Vec<super::Design>, String> {
+pub fn get_designs(conn: &Connection) -> AppResult<Vec<super::Design>> {
     let mut stmt = conn
-        .prepare(
-            "SELECT id, name, description, image_url, created_at, updated_at FROM designs ORDER BY updated_at DESC",
-        )
-        .map_err(|e| e.to_string())?;
+        .prepare_cached(
+            "SELECT id, name, description, image_url, created_at, updated_at FROM designs \
+             WHERE deleted_at IS NULL ORDER BY updated_at DESC",
+        )?;
     let rows = stmt
         .query_map([], |row| {
             Ok(super::Design {
@@ -408,87 +1417,207 @@ pub fn get_designs(conn: &Connection) -> Result<Vec<super::Design>, String> {
                 created_at: row.get(4)?,
                 updated_at: row.get(5)?,
             })
-        })
-        .map_err(|e| e.to_string())?;
+        })?;
     let mut out = vec![];
     for row in rows {
-        out.push(row.map_err(|e| e.to_string())?);
+        out.push(row?);
     }
     Ok(out)
 }
 
-pub fn save_designs(conn: &Connection, designs: &[super::Design]) -> Result<(), String> {
-    conn.execute("DELETE FROM designs", []).map_err(|e| e.to_string())?;
-    for d in designs {
-        conn.execute(
-            "INSERT INTO designs (id, name, description, image_url, created_at, updated_at)
-             VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
-            params![
+/// Replace the designs with `designs` in one transaction (see trash_rows_not_in for rows left out).
+pub fn save_designs(conn: &Connection, designs: &[super::Design], clear_all: bool) -> AppResult<()> {
+    let tx = conn.unchecked_transaction()?;
+    trash_rows_not_in(&tx, "designs", designs.iter().map(|x| x.id.as_str()), clear_all)?;
+    {
+        let mut stmt = tx.prepare_cached(&upsert_by_id_sql(
+            "designs",
+            &["id", "name", "description", "image_url", "created_at", "updated_at"],
+        ))?;
+        for d in designs {
+            stmt.execute(params![
                 d.id,
                 d.name,
                 d.description,
                 d.image_url,
                 d.created_at,
                 d.updated_at,
-            ],
-        )
-        .map_err(|e| e.to_string())?;
+            ])?;
+        }
     }
+    tx.commit()?;
     Ok(())
 }
 
 // --- Project-scoped data for Worker/Planner (avoid fetch to /api which triggers URL parse error in Tauri) ---
 
-pub fn get_plan_tickets_for_project(conn: &Connection, project_id: &str) -> Result<Vec<serde_json::Value>, String> {
+const PLAN_TICKET_COLUMNS: &str =
+    "id, project_id, number, title, description, priority, feature_name, done, status, milestone_id, idea_id, agents, created_at, updated_at, position";
+
+/// Planner ticket (plan_tickets row). snake_case like the /api/data routes, which return the raw rows.
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct PlanTicket {
+    pub id: String,
+    pub project_id: String,
+    #[ts(as = "f64")]
+    pub number: i64,
+    pub title: String,
+    pub description: Option<String>,
+    /// "P0".."P3".
+    pub priority: String,
+    pub feature_name: String,
+    pub done: bool,
+    /// Workflow column the ticket is in (see Workflow).
+    pub status: String,
+    /// Order within its column.
+    #[ts(as = "f64")]
+    pub position: i64,
+    #[ts(as = "Option<f64>")]
+    pub milestone_id: Option<i64>,
+    #[ts(as = "Option<f64>")]
+    pub idea_id: Option<i64>,
+    pub agents: Option<Vec<String>>,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+/// Milestone (milestones row).
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct Milestone {
+    #[ts(as = "f64")]
+    pub id: i64,
+    pub project_id: String,
+    pub name: String,
+    pub slug: String,
+    pub content: Option<String>,
+    /// YYYY-MM-DD.
+    pub start_date: Option<String>,
+    /// YYYY-MM-DD.
+    pub due_date: Option<String>,
+    /// Order in the milestone list and roadmap.
+    #[ts(as = "f64")]
+    pub position: i64,
+    /// "planned", "active" or "shipped".
+    pub status: String,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+/// Idea (ideas row). project_id None = not tied to a project.
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct Idea {
+    #[ts(as = "f64")]
+    pub id: i64,
+    pub project_id: Option<String>,
+    pub title: String,
+    pub description: String,
+    pub category: String,
+    pub source: String,
+    /// "new", "accepted", "rejected" or "converted".
+    pub status: String,
+    /// People or events affected per period (RICE).
+    pub reach: Option<f64>,
+    pub impact: Option<f64>,
+    /// Percent, 0–100.
+    pub confidence: Option<f64>,
+    /// Person-weeks (RICE).
+    pub effort: Option<f64>,
+    /// 0–10 (ICE).
+    pub ease: Option<f64>,
+    /// reach × impact × confidence% ÷ effort; None while a factor is missing.
+    pub rice_score: Option<f64>,
+    /// impact × confidence (as 0–10) × ease; None while a factor is missing.
+    pub ice_score: Option<f64>,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+/// Planner kanban state: the tickets in the "In progress" column.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct KanbanState {
+    pub in_progress_ids: Vec<String>,
+}
+
+fn plan_ticket_from_row(row: &rusqlite::Row) -> rusqlite::Result<PlanTicket> {
+    let agents: Option<String> = row.get(11)?;
+    Ok(PlanTicket {
+        id: row.get(0)?,
+        project_id: row.get(1)?,
+        number: row.get(2)?,
+        title: row.get(3)?,
+        description: row.get(4)?,
+        priority: row.get(5)?,
+        feature_name: row.get(6)?,
+        done: row.get::<_, i64>(7)? != 0,
+        status: row.get(8)?,
+        position: row.get(14)?,
+        milestone_id: row.get(9)?,
+        idea_id: row.get(10)?,
+        agents: agents.and_then(|s| serde_json::from_str::<Vec<String>>(&s).ok()),
+        created_at: row.get(12)?,
+        updated_at: row.get(13)?,
+    })
+}
+
+pub fn get_plan_tickets_for_project(conn: &Connection, project_id: &str) -> AppResult<Vec<PlanTicket>> {
     let mut stmt = conn
-        .prepare(
-            "SELECT id, project_id, number, title, description, priority, feature_name, done, status, milestone_id, idea_id, agents, created_at, updated_at FROM plan_tickets WHERE project_id = ?1 ORDER BY number ASC",
-        )
-        .map_err(|e| e.to_string())?;
+        .prepare_cached(&format!(
+            "SELECT {} FROM plan_tickets WHERE project_id = ?1 AND deleted_at IS NULL ORDER BY number ASC",
+            PLAN_TICKET_COLUMNS
+        ))?;
     let rows = stmt
-        .query_map(rusqlite::params![project_id.trim()], |row| {
-            let agents: Option<String> = row.get(11)?;
-            Ok(serde_json::json!({
-                "id": row.get::<_, String>(0)?,
-                "project_id": row.get::<_, String>(1)?,
-                "number": row.get::<_, i64>(2)?,
-                "title": row.get::<_, String>(3)?,
-                "description": row.get::<_, Option<String>>(4)?,
-                "priority": row.get::<_, String>(5)?,
-                "feature_name": row.get::<_, String>(6)?,
-                "featureName": row.get::<_, String>(6)?,
-                "done": row.get::<_, i64>(7)? != 0,
-                "status": row.get::<_, String>(8)?,
-                "milestone_id": row.get::<_, Option<i64>>(9)?,
-                "idea_id": row.get::<_, Option<i64>>(10)?,
-                "milestoneId": row.get::<_, Option<i64>>(9)?,
-                "ideaId": row.get::<_, Option<i64>>(10)?,
-                "agents": agents.and_then(|s| serde_json::from_str::<Vec<String>>(&s).ok()),
-                "created_at": row.get::<_, String>(12)?,
-                "updated_at": row.get::<_, String>(13)?,
-            }))
-        })
-        .map_err(|e| e.to_string())?;
+        .query_map(rusqlite::params![project_id.trim()], plan_ticket_from_row)?;
     let mut out = vec![];
     for row in rows {
-        out.push(row.map_err(|e| e.to_string())?);
+        out.push(row?);
     }
     Ok(out)
 }
 
-pub fn get_plan_kanban_state_for_project(conn: &Connection, project_id: &str) -> Result<serde_json::Value, String> {
-    let in_progress_ids: String = conn
-        .query_row(
-            "SELECT in_progress_ids FROM plan_kanban_state WHERE project_id = ?1",
-            rusqlite::params![project_id.trim()],
-            |row| row.get(0),
-        )
-        .unwrap_or_else(|_| "[]".to_string());
-    let ids: Vec<String> = serde_json::from_str(&in_progress_ids).unwrap_or_default();
-    Ok(serde_json::json!({ "inProgressIds": ids }))
+/// One plan ticket by id within a project. None when missing or in the trash.
+pub fn get_plan_ticket(conn: &Connection, project_id: &str, ticket_id: &str) -> AppResult<Option<PlanTicket>> {
+    match conn.query_row(
+        &format!(
+            "SELECT {} FROM plan_tickets WHERE id = ?1 AND project_id = ?2 AND deleted_at IS NULL",
+            PLAN_TICKET_COLUMNS
+        ),
+        rusqlite::params![ticket_id.trim(), project_id.trim()],
+        plan_ticket_from_row,
+    ) {
+        Ok(v) => Ok(Some(v)),
+        Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+        Err(e) => Err(e.into()),
+    }
+}
+
+fn get_plan_ticket_by_number(conn: &Connection, project_id: &str, number: i64) -> AppResult<Option<PlanTicket>> {
+    match conn.query_row(
+        &format!(
+            "SELECT {} FROM plan_tickets WHERE project_id = ?1 AND number = ?2 AND deleted_at IS NULL",
+            PLAN_TICKET_COLUMNS
+        ),
+        params![project_id.trim(), number],
+        plan_ticket_from_row,
+    ) {
+        Ok(v) => Ok(Some(v)),
+        Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// In-progress ticket ids in board order: the tickets in the "In progress" column.
+pub fn get_plan_kanban_state_for_project(conn: &Connection, project_id: &str) -> AppResult<KanbanState> {
+    Ok(KanbanState {
+        in_progress_ids: column_ticket_ids(conn, project_id.trim(), IN_PROGRESS_STATUS)?,
+    })
 }
 
-/// Create a plan ticket for a project. Returns the new ticket as JSON (same shape as API).
+/// Create a plan ticket for a project and return it.
 pub fn create_plan_ticket(
     conn: &Connection,
     project_id: &str,
@@ -499,19 +1628,20 @@ pub fn create_plan_ticket(
     milestone_id: i64,
     idea_id: Option<i64>,
     agents: Option<&str>,
-) -> Result<serde_json::Value, String> {
+    actor: &Actor,
+) -> AppResult<PlanTicket> {
     let project_id = project_id.trim();
     let title = title.trim();
     if title.is_empty() {
-        return Err("title is required".to_string());
+        return Err(AppError::invalid("title is required"));
     }
     let number: i64 = conn
         .query_row(
+            // Includes tickets in the trash, so numbers are never reused.
             "SELECT COALESCE(MAX(number), 0) AS n FROM plan_tickets WHERE project_id = ?1",
             rusqlite::params![project_id],
             |row| row.get(0),
-        )
-        .map_err(|e| e.to_string())?;
+        )?;
     let number = number + 1;
     let id = format!("ticket-{}-{}", project_id, number);
     let now = chrono::Utc::now().to_rfc3339();
@@ -522,8 +1652,11 @@ pub fn create_plan_ticket(
     };
     let desc = description.unwrap_or("");
     let feat = feature_name.trim().if_empty("General");
+    let workflow = get_plan_workflow(conn, project_id)?;
+    let status = workflow.initial_status();
+    let position = next_position(conn, project_id, status)?;
     conn.execute(
-        "INSERT INTO plan_tickets (id, project_id, number, title, description, priority, feature_name, done, status, milestone_id, idea_id, agents, created_at, updated_at) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, 0, 'Todo', ?8, ?9, ?10, ?11, ?12)",
+        "INSERT INTO plan_tickets (id, project_id, number, title, description, priority, feature_name, done, status, position, milestone_id, idea_id, agents, created_at, updated_at) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, 0, ?8, ?9, ?10, ?11, ?12, ?13, ?14)",
         rusqlite::params![
             id,
             project_id,
@@ -532,310 +1665,3480 @@ pub fn create_plan_ticket(
             desc,
             priority,
             feat,
+            status,
+            position,
             milestone_id,
             idea_id,
             agents.unwrap_or(""),
             &now,
             &now,
         ],
-    )
-    .map_err(|e| e.to_string())?;
-    Ok(serde_json::json!({
-        "id": id,
-        "project_id": project_id,
-        "number": number,
-        "title": title,
-        "description": desc,
-        "priority": priority,
-        "feature_name": feat,
-        "featureName": feat,
-        "done": false,
-        "status": "Todo",
-        "milestone_id": milestone_id,
-        "idea_id": idea_id,
-        "milestoneId": milestone_id,
-        "ideaId": idea_id,
-        "agents": agents.and_then(|s| serde_json::from_str::<Vec<String>>(s).ok()),
-        "created_at": now,
-        "updated_at": now,
-    }))
-}
-
-/// Update a plan ticket's done and status (for Worker tab Mark done/Redo; avoids fetch in Tauri).
+    )?;
+    let ticket = PlanTicket {
+        id,
+        project_id: project_id.to_string(),
+        number,
+        title: title.to_string(),
+        description: Some(desc.to_string()),
+        priority: priority.to_string(),
+        feature_name: feat.to_string(),
+        done: false,
+        status: status.to_string(),
+        position,
+        milestone_id: Some(milestone_id),
+        idea_id,
+        agents: agents.and_then(|s| serde_json::from_str::<Vec<String>>(s).ok()),
+        created_at: now.clone(),
+        updated_at: now,
+    };
+    record_plan_ticket_event(conn, &ticket, "created", None, None, Some(serde_json::to_value(&ticket)?), actor)?;
+    Ok(ticket)
+}
+
+/// Update a plan ticket's done and status (for Worker tab Mark done/Redo; avoids fetch in Tauri). A status that is not
+/// a workflow column falls back to the done or initial column; the move follows move_plan_ticket's rules.
 pub fn update_plan_ticket(
     conn: &Connection,
     project_id: &str,
     ticket_id: &str,
     done: bool,
     status: &str,
-) -> Result<(), String> {
-    let project_id = project_id.trim();
-    let ticket_id = ticket_id.trim();
-    let status = match status {
-        "Done" | "Todo" => status,
-        _ => return Err("status must be 'Done' or 'Todo'".to_string()),
+    actor: &Actor,
+) -> AppResult<()> {
+    let workflow = get_plan_workflow(conn, project_id)?;
+    let status = match workflow.column(status) {
+        Some(c) => c.name.as_str(),
+        None if done => workflow.done_status(),
+        None => workflow.initial_status(),
     };
-    let done_int = if done { 1 } else { 0 };
-    let now = chrono::Utc::now().to_rfc3339();
-    let updated = conn
-        .execute(
-            "UPDATE plan_tickets SET done = ?1, status = ?2, updated_at = ?3 WHERE id = ?4 AND project_id = ?5",
-            rusqlite::params![done_int, status, &now, ticket_id, project_id],
-        )
-        .map_err(|e| e.to_string())?;
-    if updated == 0 {
-        return Err("Ticket not found".to_string());
-    }
+    move_plan_ticket(conn, project_id, ticket_id, status, None, actor)?;
     Ok(())
 }
 
-/// Delete a plan ticket (for Worker tab Archive; avoids fetch in Tauri).
-pub fn delete_plan_ticket(
+/// Partial update of a plan ticket: omitted fields are left alone. `description` and `idea_id` can be cleared with null.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct PlanTicketPatch {
+    #[serde(default)]
+    #[ts(optional)]
+    pub title: Option<String>,
+    #[serde(default, deserialize_with = "double_option")]
+    #[ts(optional = nullable, as = "Option<String>")]
+    pub description: Option<Option<String>>,
+    /// "P0".."P3".
+    #[serde(default)]
+    #[ts(optional)]
+    pub priority: Option<String>,
+    #[serde(default, alias = "featureName")]
+    #[ts(optional)]
+    pub feature_name: Option<String>,
+    /// Must be a milestone of the ticket's project.
+    #[serde(default, alias = "milestoneId")]
+    #[ts(optional, as = "Option<f64>")]
+    pub milestone_id: Option<i64>,
+    #[serde(default, alias = "ideaId", deserialize_with = "double_option")]
+    #[ts(optional = nullable, as = "Option<f64>")]
+    pub idea_id: Option<Option<i64>>,
+    /// JSON list of agent names, as in create_plan_ticket; "" clears.
+    #[serde(default)]
+    #[ts(optional)]
+    pub agents: Option<String>,
+}
+
+/// Distinguishes an explicit null (Some(None)) from a missing field (None).
+fn double_option<'de, D, T>(deserializer: D) -> Result<Option<Option<T>>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+    T: Deserialize<'de>,
+{
+    Option::<T>::deserialize(deserializer).map(Some)
+}
+
+/// Apply a patch to one ticket and return the updated ticket.
+pub fn patch_plan_ticket(
+    conn: &Connection,
+    project_id: &str,
+    ticket_id: &str,
+    patch: &PlanTicketPatch,
+    actor: &Actor,
+) -> AppResult<PlanTicket> {
+    let tx = conn.unchecked_transaction()?;
+    let ticket = apply_plan_ticket_patch(&tx, project_id, ticket_id, patch, actor)?;
+    tx.commit()?;
+    Ok(ticket)
+}
+
+fn apply_plan_ticket_patch(
     conn: &Connection,
     project_id: &str,
     ticket_id: &str,
-) -> Result<(), String> {
+    patch: &PlanTicketPatch,
+    actor: &Actor,
+) -> AppResult<PlanTicket> {
     let project_id = project_id.trim();
-    let ticket_id = ticket_id.trim();
-    let updated = conn
-        .execute("DELETE FROM plan_tickets WHERE id = ?1 AND project_id = ?2", rusqlite::params![ticket_id, project_id])
-        .map_err(|e| e.to_string())?;
-    if updated == 0 {
-        return Err("Ticket not found".to_string());
+    let before =
+        get_plan_ticket(conn, project_id, ticket_id)?.ok_or_else(|| AppError::not_found("Ticket not found"))?;
+    let mut after = before.clone();
+    if let Some(title) = &patch.title {
+        if title.trim().is_empty() {
+            return Err(AppError::invalid("title is required"));
+        }
+        after.title = title.trim().to_string();
     }
-    Ok(())
+    if let Some(description) = &patch.description {
+        after.description = Some(description.clone().unwrap_or_default());
+    }
+    if let Some(priority) = &patch.priority {
+        if !["P0", "P1", "P2", "P3"].contains(&priority.as_str()) {
+            return Err(AppError::invalid(format!("Invalid priority '{}' (expected P0–P3)", priority)));
+        }
+        after.priority = priority.clone();
+    }
+    if let Some(feature_name) = &patch.feature_name {
+        after.feature_name = feature_name.trim().if_empty("General").to_string();
+    }
+    if let Some(milestone_id) = patch.milestone_id {
+        let exists: i64 = conn.query_row(
+            "SELECT COUNT(*) FROM milestones WHERE id = ?1 AND project_id = ?2",
+            params![milestone_id, project_id],
+            |row| row.get(0),
+        )?;
+        if exists == 0 {
+            return Err(AppError::invalid(format!("Milestone {} does not belong to this project", milestone_id)));
+        }
+        after.milestone_id = Some(milestone_id);
+    }
+    if let Some(idea_id) = patch.idea_id {
+        after.idea_id = idea_id;
+    }
+    if let Some(agents) = &patch.agents {
+        after.agents = if agents.trim().is_empty() {
+            None
+        } else {
+            Some(serde_json::from_str::<Vec<String>>(agents).map_err(|e| {
+                AppError::invalid(format!("agents must be a JSON list of strings: {}", e))
+            })?)
+        };
+    }
+    if serde_json::to_value(&after)? == serde_json::to_value(&before)? {
+        return Ok(before);
+    }
+    after.updated_at = chrono::Utc::now().to_rfc3339();
+    conn.execute(
+        "UPDATE plan_tickets SET title = ?1, description = ?2, priority = ?3, feature_name = ?4, milestone_id = ?5,
+            idea_id = ?6, agents = ?7, updated_at = ?8 WHERE id = ?9 AND project_id = ?10",
+        params![
+            after.title,
+            after.description,
+            after.priority,
+            after.feature_name,
+            after.milestone_id,
+            after.idea_id,
+            after.agents.as_ref().map(serde_json::to_string).transpose()?,
+            after.updated_at,
+            after.id,
+            project_id,
+        ],
+    )?;
+    record_plan_ticket_changes(conn, &before, &after, actor)?;
+    Ok(after)
 }
 
-trait IfEmpty {
-    fn if_empty<'a>(&'a self, default: &'a str) -> &'a str;
-}
-impl IfEmpty for str {
-    fn if_empty<'a>(&'a self, default: &'a str) -> &'a str {
-        if self.trim().is_empty() {
-            default
-        } else {
-            self
+/// Apply the same patch (and optionally a move to `column`) to several tickets, all or nothing.
+pub fn bulk_update_plan_tickets(
+    conn: &Connection,
+    project_id: &str,
+    ticket_ids: &[String],
+    patch: &PlanTicketPatch,
+    column: Option<&str>,
+    actor: &Actor,
+) -> AppResult<Vec<PlanTicket>> {
+    // Name the failing ticket in the error details, keeping any details the error already has.
+    fn for_ticket(ticket_id: &str) -> impl Fn(AppError) -> AppError + '_ {
+        move |mut e: AppError| {
+            let mut details = e.details.take().unwrap_or_else(|| serde_json::json!({}));
+            if let Some(obj) = details.as_object_mut() {
+                obj.insert("ticket_id".into(), ticket_id.into());
+            }
+            e.with_details(details)
+        }
+    }
+    let tx = conn.unchecked_transaction()?;
+    let mut out = Vec::with_capacity(ticket_ids.len());
+    for ticket_id in ticket_ids {
+        let mut ticket =
+            apply_plan_ticket_patch(&tx, project_id, ticket_id, patch, actor).map_err(for_ticket(ticket_id))?;
+        if let Some(column) = column.map(str::trim).filter(|c| *c != ticket.status) {
+            ticket = apply_plan_ticket_move(&tx, project_id, ticket_id, column, None, actor)
+                .map_err(for_ticket(ticket_id))?;
         }
+        out.push(ticket);
     }
+    tx.commit()?;
+    Ok(out)
 }
 
-/// Set in-progress IDs for a project's kanban state (upsert).
-pub fn set_plan_kanban_state_for_project(
+/// Move a plan ticket to the trash (for Worker tab Archive; avoids fetch in Tauri).
+pub fn delete_plan_ticket(
     conn: &Connection,
     project_id: &str,
-    in_progress_ids: &[String],
-) -> Result<(), String> {
-    let project_id = project_id.trim();
-    let now = chrono::Utc::now().to_rfc3339();
-    let ids_json = serde_json::to_string(in_progress_ids).map_err(|e| e.to_string())?;
-    conn.execute(
-        "INSERT INTO plan_kanban_state (project_id, in_progress_ids, updated_at) VALUES (?1, ?2, ?3) ON CONFLICT(project_id) DO UPDATE SET in_progress_ids = excluded.in_progress_ids, updated_at = excluded.updated_at",
-        rusqlite::params![project_id, ids_json, &now],
-    )
-    .map_err(|e| e.to_string())?;
+    ticket_id: &str,
+    actor: &Actor,
+) -> AppResult<()> {
+    let ticket =
+        get_plan_ticket(conn, project_id, ticket_id)?.ok_or_else(|| AppError::not_found("Ticket not found"))?;
+    let tx = conn.unchecked_transaction()?;
+    apply_plan_ticket_delete(&tx, &ticket, actor)?;
+    tx.commit()?;
     Ok(())
 }
 
-const GENERAL_DEVELOPMENT_NAME: &str = "General Development";
-const GENERAL_DEVELOPMENT_SLUG: &str = "general-development";
-
-pub fn get_milestones_for_project(conn: &Connection, project_id: &str) -> Result<Vec<serde_json::Value>, String> {
-    let project_id = project_id.trim();
-    let mut stmt = conn
-        .prepare(
-            "SELECT id, project_id, name, slug, content, created_at, updated_at FROM milestones WHERE project_id = ?1 ORDER BY name ASC",
-        )
-        .map_err(|e| e.to_string())?;
-    let rows = stmt
-        .query_map(rusqlite::params![project_id], |row| {
-            Ok(serde_json::json!({
-                "id": row.get::<_, i64>(0)?,
-                "project_id": row.get::<_, String>(1)?,
-                "name": row.get::<_, String>(2)?,
-                "slug": row.get::<_, String>(3)?,
-                "content": row.get::<_, Option<String>>(4)?,
-                "created_at": row.get::<_, String>(5)?,
-                "updated_at": row.get::<_, String>(6)?,
-            }))
-        })
-        .map_err(|e| e.to_string())?;
-    let mut out = vec![];
-    for row in rows {
-        out.push(row.map_err(|e| e.to_string())?);
-    }
-    let has_general = out.iter().any(|v| v.get("name").and_then(|n| n.as_str()) == Some(GENERAL_DEVELOPMENT_NAME));
-    if !has_general {
-        let now = chrono::Utc::now().to_rfc3339();
-        conn.execute(
-            "INSERT INTO milestones (project_id, name, slug, content, created_at, updated_at) VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
-            rusqlite::params![project_id, GENERAL_DEVELOPMENT_NAME, GENERAL_DEVELOPMENT_SLUG, None::<String>, &now, &now],
-        )
-        .map_err(|e| e.to_string())?;
-        let mut stmt2 = conn
-            .prepare(
-                "SELECT id, project_id, name, slug, content, created_at, updated_at FROM milestones WHERE project_id = ?1 ORDER BY name ASC",
-            )
-            .map_err(|e| e.to_string())?;
-        let rows2 = stmt2
-            .query_map(rusqlite::params![project_id], |row| {
-                Ok(serde_json::json!({
-                    "id": row.get::<_, i64>(0)?,
-                    "project_id": row.get::<_, String>(1)?,
-                    "name": row.get::<_, String>(2)?,
-                    "slug": row.get::<_, String>(3)?,
-                    "content": row.get::<_, Option<String>>(4)?,
-                    "created_at": row.get::<_, String>(5)?,
-                    "updated_at": row.get::<_, String>(6)?,
-                }))
-            })
-            .map_err(|e| e.to_string())?;
-        out = vec![];
-        for row in rows2 {
-            out.push(row.map_err(|e| e.to_string())?);
-        }
-    }
-    Ok(out)
+fn apply_plan_ticket_delete(conn: &Connection, ticket: &PlanTicket, actor: &Actor) -> AppResult<()> {
+    conn.execute(
+        "UPDATE plan_tickets SET deleted_at = ?1 WHERE id = ?2 AND project_id = ?3",
+        rusqlite::params![chrono::Utc::now().to_rfc3339(), ticket.id, ticket.project_id],
+    )?;
+    record_plan_ticket_event(conn, ticket, "deleted", None, Some(serde_json::to_value(ticket)?), None, actor)
 }
 
-/// Create one idea and return the new row. Used by Idea-driven "create from description" flow.
-pub fn create_idea(
+/// Insert or update the plan ticket with this number from its tickets.md form. New tickets go to `milestone_id` and
+/// the workflow's initial column; checking or unchecking moves the ticket to the end of the done or initial column,
+/// with the same transition and WIP checks as move_plan_ticket. A refused move fails before anything is written.
+pub fn upsert_plan_ticket_from_markdown(
     conn: &Connection,
-    project_id: Option<&str>,
-    title: &str,
-    description: &str,
-    category: &str,
-    source: &str,
-) -> Result<serde_json::Value, String> {
+    project_id: &str,
+    t: &crate::planner_md::MdTicket,
+    milestone_id: i64,
+    workflow: &Workflow,
+    actor: &Actor,
+) -> AppResult<()> {
+    let project_id = project_id.trim();
     let now = chrono::Utc::now().to_rfc3339();
+    let agents = if t.agents.is_empty() { None } else { Some(serde_json::to_string(&t.agents)?) };
+    let mut before = get_plan_ticket_by_number(conn, project_id, t.number)?;
+    if let Some(b) = before.as_ref().filter(|b| b.done != t.done) {
+        let column = if t.done { workflow.done_status() } else { workflow.initial_status() };
+        before = Some(apply_plan_ticket_move(conn, project_id, &b.id, column, None, actor)?);
+    }
+    let done = before.as_ref().map_or(t.done, |b| b.done);
+    let (status, position) = match &before {
+        Some(b) => (b.status.clone(), b.position),
+        None => {
+            let status = if t.done { workflow.done_status() } else { workflow.initial_status() };
+            (status.to_string(), next_position(conn, project_id, status)?)
+        }
+    };
     conn.execute(
-        "INSERT INTO ideas (project_id, title, description, category, body, source, created_at, updated_at) VALUES (?1, ?2, ?3, ?4, NULL, ?5, ?6, ?7)",
+        "INSERT INTO plan_tickets (id, project_id, number, title, description, priority, feature_name, done, status, position, milestone_id, idea_id, agents, created_at, updated_at)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, NULL, ?12, ?13, ?13)
+         ON CONFLICT(project_id, number) DO UPDATE SET title = excluded.title, description = excluded.description,
+            priority = excluded.priority, feature_name = excluded.feature_name, done = excluded.done,
+            status = excluded.status, position = excluded.position, agents = excluded.agents, updated_at = excluded.updated_at,
+            deleted_at = NULL",
         rusqlite::params![
+            format!("ticket-{}-{}", project_id, t.number),
             project_id,
-            title.trim(),
-            description.trim(),
-            if category.is_empty() { "other" } else { category },
-            if source.is_empty() { "manual" } else { source },
-            &now,
+            t.number,
+            t.title,
+            t.description.as_deref().unwrap_or(""),
+            t.priority,
+            t.feature_name.as_str().if_empty("General"),
+            done as i64,
+            status,
+            position,
+            milestone_id,
+            agents,
             &now,
         ],
-    )
-    .map_err(|e| e.to_string())?;
-    let id = conn.last_insert_rowid();
-    let (project_id_out, title_out, description_out, category_out, source_out, created_at, updated_at): (
-        Option<String>, String, String, String, String, String, String,
-    ) = conn
-        .query_row(
-            "SELECT project_id, title, description, category, source, created_at, updated_at FROM ideas WHERE id = ?1",
-            rusqlite::params![id],
-            |row| {
-                Ok((
-                    row.get(0)?,
-                    row.get(1)?,
-                    row.get(2)?,
-                    row.get(3)?,
-                    row.get(4)?,
-                    row.get(5)?,
-                    row.get(6)?,
-                ))
-            },
-        )
-        .map_err(|e| e.to_string())?;
-    Ok(serde_json::json!({
-        "id": id,
-        "project_id": project_id_out,
-        "title": title_out,
-        "description": description_out,
-        "category": category_out,
-        "source": source_out,
-        "created_at": created_at,
-        "updated_at": updated_at,
-    }))
+    )?;
+    let after = get_plan_ticket_by_number(conn, project_id, t.number)?
+        .ok_or_else(|| AppError::internal("Ticket missing after upsert"))?;
+    match before {
+        Some(before) => record_plan_ticket_changes(conn, &before, &after, actor),
+        None => record_plan_ticket_event(conn, &after, "created", None, None, Some(serde_json::to_value(&after)?), actor),
+    }
 }
 
-/// Create one milestone for a project and return the new row. Used by Idea-driven "create from description" flow.
-pub fn create_milestone(
+pub fn delete_plan_ticket_by_number(conn: &Connection, project_id: &str, number: i64, actor: &Actor) -> AppResult<()> {
+    if let Some(ticket) = get_plan_ticket_by_number(conn, project_id, number)? {
+        apply_plan_ticket_delete(conn, &ticket, actor)?;
+    }
+    Ok(())
+}
+
+/// Fingerprints of each ticket as of the last tickets.md sync, by ticket number.
+pub fn get_plan_ticket_md_baseline(conn: &Connection, project_id: &str) -> AppResult<HashMap<i64, String>> {
+    let mut stmt = conn.prepare_cached("SELECT number, fingerprint FROM plan_ticket_md_sync WHERE project_id = ?1")?;
+    let rows = stmt.query_map(rusqlite::params![project_id.trim()], |row| Ok((row.get(0)?, row.get(1)?)))?;
+    let mut out = HashMap::new();
+    for row in rows {
+        let (number, fingerprint) = row?;
+        out.insert(number, fingerprint);
+    }
+    Ok(out)
+}
+
+/// Record (or with None, forget) the synced fingerprint of a ticket number.
+pub fn set_plan_ticket_md_baseline(
     conn: &Connection,
     project_id: &str,
-    name: &str,
-    slug: &str,
-    content: Option<&str>,
-) -> Result<serde_json::Value, String> {
-    let now = chrono::Utc::now().to_rfc3339();
-    let slug_use: String = if slug.trim().is_empty() {
-        name.trim()
-            .to_lowercase()
-            .chars()
-            .map(|c| if c.is_ascii_alphanumeric() { c } else { '-' })
-            .collect::<String>()
-            .trim_matches('-')
-            .to_string()
-    } else {
-        slug.trim().to_string()
+    number: i64,
+    fingerprint: Option<&str>,
+) -> AppResult<()> {
+    let project_id = project_id.trim();
+    match fingerprint {
+        Some(fp) => conn.execute(
+            "INSERT INTO plan_ticket_md_sync (project_id, number, fingerprint, synced_at) VALUES (?1, ?2, ?3, ?4)
+             ON CONFLICT(project_id, number) DO UPDATE SET fingerprint = excluded.fingerprint, synced_at = excluded.synced_at",
+            rusqlite::params![project_id, number, fp, chrono::Utc::now().to_rfc3339()],
+        )?,
+        None => conn.execute(
+            "DELETE FROM plan_ticket_md_sync WHERE project_id = ?1 AND number = ?2",
+            rusqlite::params![project_id, number],
+        )?,
     };
-    let slug_use = if slug_use.is_empty() { "milestone".to_string() } else { slug_use };
-    conn.execute(
-        "INSERT INTO milestones (project_id, name, slug, content, created_at, updated_at) VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
-        rusqlite::params![
-            project_id.trim(),
+    Ok(())
+}
+
+/// Summary of the most recent non-declined implementation_log entry for a ticket number.
+pub fn get_latest_implementation_summary(
+    conn: &Connection,
+    project_id: &str,
+    ticket_number: i64,
+) -> AppResult<Option<String>> {
+    match conn.query_row(
+        "SELECT summary FROM implementation_log WHERE project_id = ?1 AND ticket_number = ?2 AND status != 'declined' AND summary != '' ORDER BY completed_at DESC, id DESC LIMIT 1",
+        rusqlite::params![project_id.trim(), ticket_number],
+        |row| row.get(0),
+    ) {
+        Ok(v) => Ok(Some(v)),
+        Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+        Err(e) => Err(e.into()),
+    }
+}
+
+fn commit_template_key(project_id: &str) -> String {
+    format!("{}{}", KV_COMMIT_TEMPLATE_PREFIX, project_id.trim())
+}
+
+/// Per-project commit message template (kv_store "commit_template:<project_id>"). None = use the default.
+pub fn get_commit_template(conn: &Connection, project_id: &str) -> AppResult<Option<String>> {
+    match conn.query_row(
+        "SELECT value FROM kv_store WHERE key = ?1",
+        params![commit_template_key(project_id)],
+        |row| row.get::<_, String>(0),
+    ) {
+        Ok(v) if !v.trim().is_empty() => Ok(Some(v)),
+        Ok(_) | Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// Save or clear (None / empty) the per-project commit message template.
+pub fn set_commit_template(conn: &Connection, project_id: &str, template: Option<&str>) -> AppResult<()> {
+    let key = commit_template_key(project_id);
+    match template.map(str::trim).filter(|t| !t.is_empty()) {
+        Some(t) => conn.execute(
+            "INSERT OR REPLACE INTO kv_store (key, value) VALUES (?1, ?2)",
+            params![key, t],
+        ),
+        None => conn.execute("DELETE FROM kv_store WHERE key = ?1", params![key]),
+    }?;
+    Ok(())
+}
+
+/// Automatic backup settings (kv_store), checked hourly by the background thread started in run().
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
+#[serde(default)]
+#[ts(export)]
+pub struct BackupSchedule {
+    pub enabled: bool,
+    #[serde(alias = "intervalHours")]
+    #[ts(as = "f64")]
+    pub interval_hours: i64,
+    /// Scheduled backups to keep; older ones are deleted after each new one.
+    #[ts(as = "f64")]
+    pub keep: i64,
+}
+
+impl Default for BackupSchedule {
+    fn default() -> Self {
+        Self { enabled: true, interval_hours: 24, keep: 7 }
+    }
+}
+
+pub fn get_backup_schedule(conn: &Connection) -> AppResult<BackupSchedule> {
+    match conn.query_row(
+        "SELECT value FROM kv_store WHERE key = ?1",
+        params![KV_BACKUP_SCHEDULE],
+        |row| row.get::<_, String>(0),
+    ) {
+        Ok(v) => Ok(serde_json::from_str(&v).unwrap_or_default()),
+        Err(rusqlite::Error::QueryReturnedNoRows) => Ok(BackupSchedule::default()),
+        Err(e) => Err(e.into()),
+    }
+}
+
+pub fn set_backup_schedule(conn: &Connection, schedule: &BackupSchedule) -> AppResult<()> {
+    if schedule.interval_hours < 1 {
+        return Err(AppError::invalid("interval_hours must be at least 1"));
+    }
+    if schedule.keep < 1 {
+        return Err(AppError::invalid("keep must be at least 1"));
+    }
+    conn.execute(
+        "INSERT OR REPLACE INTO kv_store (key, value) VALUES (?1, ?2)",
+        params![KV_BACKUP_SCHEDULE, serde_json::to_string(schedule)?],
+    )?;
+    Ok(())
+}
+
+/// A commit that references a plan ticket (from `git log` scanning).
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct TicketCommitLink {
+    pub project_id: String,
+    #[ts(as = "f64")]
+    pub ticket_number: i64,
+    pub commit_hash: String,
+    pub subject: String,
+    /// Referenced with a closing keyword (fixes/closes/resolves #N).
+    pub closes: bool,
+    pub committed_at: String,
+}
+
+/// Record a commit → ticket reference. Returns true when the link is new.
+pub fn record_ticket_commit(conn: &Connection, link: &TicketCommitLink) -> AppResult<bool> {
+    let now = chrono::Utc::now().to_rfc3339();
+    let inserted = conn
+        .execute(
+            "INSERT OR IGNORE INTO plan_ticket_commits (project_id, ticket_number, commit_hash, subject, closes, committed_at, created_at) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+            params![
+                link.project_id.trim(),
+                link.ticket_number,
+                link.commit_hash,
+                link.subject,
+                link.closes as i64,
+                link.committed_at,
+                &now,
+            ],
+        )?;
+    if inserted > 0 {
+        // Attach the hash to the ticket's latest implementation_log row if it has none yet; earlier runs belong to
+        // earlier commits (or none).
+        conn.execute(
+            "UPDATE implementation_log SET commit_hash = ?1
+             WHERE id = (SELECT MAX(id) FROM implementation_log WHERE project_id = ?2 AND ticket_number = ?3)
+               AND commit_hash IS NULL",
+            params![link.commit_hash, link.project_id.trim(), link.ticket_number],
+        )?;
+    }
+    Ok(inserted > 0)
+}
+
+/// Commits linked to one ticket number, newest first.
+pub fn get_ticket_commits(conn: &Connection, project_id: &str, ticket_number: i64) -> AppResult<Vec<TicketCommitLink>> {
+    let mut stmt = conn
+        .prepare_cached(
+            "SELECT project_id, ticket_number, commit_hash, subject, closes, committed_at FROM plan_ticket_commits WHERE project_id = ?1 AND ticket_number = ?2 ORDER BY committed_at DESC",
+        )?;
+    let rows = stmt
+        .query_map(params![project_id.trim(), ticket_number], |row| {
+            Ok(TicketCommitLink {
+                project_id: row.get(0)?,
+                ticket_number: row.get(1)?,
+                commit_hash: row.get(2)?,
+                subject: row.get(3)?,
+                closes: row.get::<_, i64>(4)? != 0,
+                committed_at: row.get(5)?,
+            })
+        })?;
+    let mut out = vec![];
+    for row in rows {
+        out.push(row?);
+    }
+    Ok(out)
+}
+
+/// Move the ticket with this number to the end of the workflow's done column, following move_plan_ticket's rules.
+/// Returns true when a not-yet-done ticket was closed.
+pub fn close_plan_ticket_by_number(conn: &Connection, project_id: &str, number: i64, actor: &Actor) -> AppResult<bool> {
+    let Some(ticket) = get_plan_ticket_by_number(conn, project_id, number)?.filter(|t| !t.done) else {
+        return Ok(false);
+    };
+    let workflow = get_plan_workflow(conn, project_id)?;
+    let done_status = workflow.done_status();
+    let position = next_position(conn, project_id.trim(), done_status)?;
+    apply_plan_ticket_move(conn, project_id, &ticket.id, done_status, Some(position), actor)?;
+    Ok(true)
+}
+
+trait IfEmpty {
+    fn if_empty<'a>(&'a self, default: &'a str) -> &'a str;
+}
+impl IfEmpty for str {
+    fn if_empty<'a>(&'a self, default: &'a str) -> &'a str {
+        if self.trim().is_empty() {
+            default
+        } else {
+            self
+        }
+    }
+}
+
+/// Set the in-progress tickets from the old Kanban UI's list: listed open tickets move to "In progress" (in list
+/// order), others there go back to the initial column. Each move follows move_plan_ticket's rules; if one is refused
+/// nothing changes.
+pub fn set_plan_kanban_state_for_project(
+    conn: &Connection,
+    project_id: &str,
+    in_progress_ids: &[String],
+    actor: &Actor,
+) -> AppResult<()> {
+    let project_id = project_id.trim();
+    let workflow = get_plan_workflow(conn, project_id)?;
+    if workflow.column(IN_PROGRESS_STATUS).is_none() {
+        return Err(AppError::invalid(format!("Workflow has no '{}' column", IN_PROGRESS_STATUS)));
+    }
+    let tx = conn.unchecked_transaction()?;
+    // Free the column first so WIP limits count only the tickets that stay.
+    for id in column_ticket_ids(&tx, project_id, IN_PROGRESS_STATUS)? {
+        if !in_progress_ids.contains(&id) {
+            apply_plan_ticket_move(&tx, project_id, &id, workflow.initial_status(), None, actor)?;
+        }
+    }
+    let mut ordered = vec![];
+    for id in in_progress_ids {
+        let Some(ticket) = get_plan_ticket(&tx, project_id, id)?.filter(|t| !t.done) else {
+            continue;
+        };
+        if ticket.status != IN_PROGRESS_STATUS {
+            apply_plan_ticket_move(&tx, project_id, &ticket.id, IN_PROGRESS_STATUS, None, actor)?;
+        }
+        if !ordered.contains(&ticket.id) {
+            ordered.push(ticket.id);
+        }
+    }
+    write_column_order(&tx, project_id, &ordered)?;
+    tx.commit()?;
+    Ok(())
+}
+
+/// Status of tickets in progress; get/set_plan_kanban_state map the old in-progress list onto it.
+const IN_PROGRESS_STATUS: &str = "In progress";
+
+/// One column of a project's planner workflow. `name` is also the status stored on the tickets in it.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct WorkflowColumn {
+    pub name: String,
+    /// Maximum number of tickets in the column; None = unlimited.
+    #[serde(default)]
+    #[ts(as = "Option<f64>")]
+    pub wip_limit: Option<i64>,
+    /// Tickets in this column count as done.
+    #[serde(default)]
+    pub done: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct WorkflowTransition {
+    pub from: String,
+    pub to: String,
+}
+
+/// Per-project kanban workflow: ordered columns and the moves allowed between them. Reordering within a column is
+/// always allowed.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct Workflow {
+    pub columns: Vec<WorkflowColumn>,
+    pub transitions: Vec<WorkflowTransition>,
+}
+
+impl Workflow {
+    fn column(&self, name: &str) -> Option<&WorkflowColumn> {
+        self.columns.iter().find(|c| c.name == name)
+    }
+
+    /// Status of new and reopened tickets: the first column that is not a done column.
+    pub fn initial_status(&self) -> &str {
+        self.columns
+            .iter()
+            .find(|c| !c.done)
+            .or(self.columns.first())
+            .map(|c| c.name.as_str())
+            .unwrap_or("Todo")
+    }
+
+    /// Status of tickets marked done: the first done column.
+    pub fn done_status(&self) -> &str {
+        self.columns
+            .iter()
+            .find(|c| c.done)
+            .or(self.columns.last())
+            .map(|c| c.name.as_str())
+            .unwrap_or("Done")
+    }
+
+    fn allows(&self, from: &str, to: &str) -> bool {
+        from == to || self.transitions.iter().any(|t| t.from == from && t.to == to)
+    }
+}
+
+/// Workflow used until a project saves its own.
+fn default_workflow() -> Workflow {
+    let column = |name: &str, done: bool| WorkflowColumn {
+        name: name.to_string(),
+        wip_limit: None,
+        done,
+    };
+    let transitions = [
+        ("Todo", &["In progress", "Blocked", "Done"][..]),
+        ("In progress", &["Todo", "Review", "Blocked", "Done"][..]),
+        ("Review", &["In progress", "Blocked", "Done"][..]),
+        ("Blocked", &["Todo", "In progress", "Done"][..]),
+        ("Done", &["Todo", "In progress"][..]),
+    ];
+    Workflow {
+        columns: vec![
+            column("Todo", false),
+            column(IN_PROGRESS_STATUS, false),
+            column("Review", false),
+            column("Blocked", false),
+            column("Done", true),
+        ],
+        transitions: transitions
+            .iter()
+            .flat_map(|(from, tos)| {
+                tos.iter().map(move |to| WorkflowTransition {
+                    from: from.to_string(),
+                    to: to.to_string(),
+                })
+            })
+            .collect(),
+    }
+}
+
+/// A project's workflow, or the default when it has not saved one.
+pub fn get_plan_workflow(conn: &Connection, project_id: &str) -> AppResult<Workflow> {
+    let project_id = project_id.trim();
+    let mut stmt = conn.prepare_cached(
+        "SELECT name, wip_limit, done FROM plan_workflow_columns WHERE project_id = ?1 ORDER BY position",
+    )?;
+    let columns = stmt
+        .query_map(params![project_id], |row| {
+            Ok(WorkflowColumn {
+                name: row.get(0)?,
+                wip_limit: row.get(1)?,
+                done: row.get::<_, i64>(2)? != 0,
+            })
+        })?
+        .collect::<rusqlite::Result<Vec<_>>>()?;
+    if columns.is_empty() {
+        return Ok(default_workflow());
+    }
+    let mut stmt = conn.prepare_cached(
+        "SELECT from_status, to_status FROM plan_workflow_transitions WHERE project_id = ?1 ORDER BY rowid",
+    )?;
+    let transitions = stmt
+        .query_map(params![project_id], |row| Ok(WorkflowTransition { from: row.get(0)?, to: row.get(1)? }))?
+        .collect::<rusqlite::Result<Vec<_>>>()?;
+    Ok(Workflow { columns, transitions })
+}
+
+/// Replace a project's workflow. Columns that still hold tickets cannot be removed.
+pub fn set_plan_workflow(conn: &Connection, project_id: &str, workflow: &Workflow) -> AppResult<Workflow> {
+    let project_id = project_id.trim();
+    let mut names: Vec<&str> = vec![];
+    for c in &workflow.columns {
+        let name = c.name.trim();
+        if name.is_empty() || name != c.name {
+            return Err(AppError::invalid(format!("Invalid column name '{}'", c.name)));
+        }
+        if names.contains(&name) {
+            return Err(AppError::invalid(format!("Duplicate column '{}'", name)));
+        }
+        if c.wip_limit.is_some_and(|l| l < 1) {
+            return Err(AppError::invalid(format!("WIP limit of '{}' must be at least 1", name)));
+        }
+        names.push(name);
+    }
+    if !workflow.columns.iter().any(|c| c.done) {
+        return Err(AppError::invalid("Workflow needs at least one done column"));
+    }
+    for t in &workflow.transitions {
+        if !names.contains(&t.from.as_str()) || !names.contains(&t.to.as_str()) {
+            return Err(AppError::invalid(format!("Transition '{}' → '{}' uses an unknown column", t.from, t.to)));
+        }
+    }
+    let mut stmt = conn.prepare_cached(
+        "SELECT status, COUNT(*) FROM plan_tickets WHERE project_id = ?1 AND deleted_at IS NULL
+         GROUP BY status ORDER BY status",
+    )?;
+    let orphaned: Vec<(String, i64)> = stmt
+        .query_map(params![project_id], |row| Ok((row.get(0)?, row.get(1)?)))?
+        .collect::<rusqlite::Result<Vec<(String, i64)>>>()?
+        .into_iter()
+        .filter(|(status, _)| !names.contains(&status.as_str()))
+        .collect();
+    if !orphaned.is_empty() {
+        let list: Vec<String> = orphaned.iter().map(|(s, n)| format!("'{}' ({})", s, n)).collect();
+        return Err(AppError::conflict(format!(
+            "Move tickets out of {} before removing the column",
+            list.join(", ")
+        ))
+        .with_details(serde_json::json!(orphaned.into_iter().collect::<HashMap<_, _>>())));
+    }
+
+    let tx = conn.unchecked_transaction()?;
+    tx.execute("DELETE FROM plan_workflow_transitions WHERE project_id = ?1", params![project_id])?;
+    tx.execute("DELETE FROM plan_workflow_columns WHERE project_id = ?1", params![project_id])?;
+    for (pos, c) in workflow.columns.iter().enumerate() {
+        tx.execute(
+            "INSERT INTO plan_workflow_columns (project_id, name, position, wip_limit, done) VALUES (?1, ?2, ?3, ?4, ?5)",
+            params![project_id, c.name, pos as i64, c.wip_limit, c.done as i64],
+        )?;
+    }
+    for t in &workflow.transitions {
+        tx.execute(
+            "INSERT OR IGNORE INTO plan_workflow_transitions (project_id, from_status, to_status) VALUES (?1, ?2, ?3)",
+            params![project_id, t.from, t.to],
+        )?;
+    }
+    // Done follows the column, which may have changed.
+    for c in &workflow.columns {
+        tx.execute(
+            "UPDATE plan_tickets SET done = ?1 WHERE project_id = ?2 AND status = ?3 AND done != ?1",
+            params![c.done as i64, project_id, c.name],
+        )?;
+    }
+    tx.commit()?;
+    get_plan_workflow(conn, project_id)
+}
+
+/// Ticket ids in a column, in board order.
+fn column_ticket_ids(conn: &Connection, project_id: &str, status: &str) -> AppResult<Vec<String>> {
+    let mut stmt = conn.prepare_cached(
+        "SELECT id FROM plan_tickets WHERE project_id = ?1 AND status = ?2 AND deleted_at IS NULL
+         ORDER BY position, number",
+    )?;
+    let ids = stmt
+        .query_map(params![project_id, status], |row| row.get(0))?
+        .collect::<rusqlite::Result<Vec<String>>>()?;
+    Ok(ids)
+}
+
+fn write_column_order(conn: &Connection, project_id: &str, ids: &[String]) -> AppResult<()> {
+    for (pos, id) in ids.iter().enumerate() {
+        conn.execute(
+            "UPDATE plan_tickets SET position = ?1 WHERE id = ?2 AND project_id = ?3 AND position != ?1",
+            params![pos as i64, id, project_id],
+        )?;
+    }
+    Ok(())
+}
+
+/// Position after the last ticket in a column.
+fn next_position(conn: &Connection, project_id: &str, status: &str) -> AppResult<i64> {
+    Ok(conn.query_row(
+        "SELECT COALESCE(MAX(position) + 1, 0) FROM plan_tickets WHERE project_id = ?1 AND status = ?2",
+        params![project_id, status],
+        |row| row.get(0),
+    )?)
+}
+
+/// Move a ticket to `column` at `position` (0-based; None or past the end = last). Moves between columns must be an
+/// allowed transition and respect the target's WIP limit. Both columns are renumbered.
+pub fn move_plan_ticket(
+    conn: &Connection,
+    project_id: &str,
+    ticket_id: &str,
+    column: &str,
+    position: Option<i64>,
+    actor: &Actor,
+) -> AppResult<PlanTicket> {
+    let tx = conn.unchecked_transaction()?;
+    let moved = apply_plan_ticket_move(&tx, project_id, ticket_id, column, position, actor)?;
+    tx.commit()?;
+    Ok(moved)
+}
+
+/// move_plan_ticket without its own transaction, for callers that batch several changes.
+fn apply_plan_ticket_move(
+    conn: &Connection,
+    project_id: &str,
+    ticket_id: &str,
+    column: &str,
+    position: Option<i64>,
+    actor: &Actor,
+) -> AppResult<PlanTicket> {
+    let project_id = project_id.trim();
+    let ticket =
+        get_plan_ticket(conn, project_id, ticket_id)?.ok_or_else(|| AppError::not_found("Ticket not found"))?;
+    let workflow = get_plan_workflow(conn, project_id)?;
+    let target = workflow
+        .column(column.trim())
+        .ok_or_else(|| AppError::invalid(format!("Unknown column '{}'", column.trim())))?;
+    if !workflow.allows(&ticket.status, &target.name) {
+        let allowed: Vec<&str> = workflow
+            .transitions
+            .iter()
+            .filter(|t| t.from == ticket.status)
+            .map(|t| t.to.as_str())
+            .collect();
+        return Err(AppError::invalid(format!(
+            "Moving a ticket from '{}' to '{}' is not allowed",
+            ticket.status, target.name
+        ))
+        .with_details(serde_json::json!({ "from": ticket.status, "to": target.name, "allowed": allowed })));
+    }
+
+    let mut ids = column_ticket_ids(conn, project_id, &target.name)?;
+    ids.retain(|id| *id != ticket.id);
+    if let Some(limit) = target.wip_limit.filter(|_| ticket.status != target.name) {
+        if ids.len() as i64 >= limit {
+            return Err(AppError::conflict(format!(
+                "Column '{}' is at its WIP limit of {}",
+                target.name, limit
+            ))
+            .with_details(serde_json::json!({ "column": target.name, "wip_limit": limit, "count": ids.len() })));
+        }
+    }
+    let index = position.map(|p| p.clamp(0, ids.len() as i64) as usize).unwrap_or(ids.len());
+    ids.insert(index, ticket.id.clone());
+    conn.execute(
+        "UPDATE plan_tickets SET status = ?1, done = ?2, updated_at = ?3 WHERE id = ?4 AND project_id = ?5",
+        params![target.name, target.done as i64, chrono::Utc::now().to_rfc3339(), ticket.id, project_id],
+    )?;
+    write_column_order(conn, project_id, &ids)?;
+    if ticket.status != target.name {
+        write_column_order(conn, project_id, &column_ticket_ids(conn, project_id, &ticket.status)?)?;
+    }
+    let moved =
+        get_plan_ticket(conn, project_id, &ticket.id)?.ok_or_else(|| AppError::not_found("Ticket not found"))?;
+    record_plan_ticket_changes(conn, &ticket, &moved, actor)?;
+    Ok(moved)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "snake_case")]
+#[ts(export)]
+pub enum ActorKind {
+    User,
+    Agent,
+    MarkdownSync,
+    System,
+}
+
+impl ActorKind {
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::User => "user",
+            Self::Agent => "agent",
+            Self::MarkdownSync => "markdown_sync",
+            Self::System => "system",
+        }
+    }
+
+    fn parse(s: &str) -> Self {
+        match s {
+            "agent" => Self::Agent,
+            "markdown_sync" => Self::MarkdownSync,
+            "system" => Self::System,
+            _ => Self::User,
+        }
+    }
+}
+
+/// Who made a change. `id` is the run id for agents and the commit hash for tickets closed by a commit.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct Actor {
+    pub kind: ActorKind,
+    #[serde(default)]
+    pub id: Option<String>,
+}
+
+impl Actor {
+    pub fn user() -> Self {
+        Self { kind: ActorKind::User, id: None }
+    }
+
+    pub fn agent(run_id: &str) -> Self {
+        Self { kind: ActorKind::Agent, id: Some(run_id.to_string()) }
+    }
+
+    pub fn markdown_sync() -> Self {
+        Self { kind: ActorKind::MarkdownSync, id: None }
+    }
+
+    pub fn commit(hash: &str) -> Self {
+        Self { kind: ActorKind::System, id: Some(hash.to_string()) }
+    }
+}
+
+impl Default for Actor {
+    fn default() -> Self {
+        Self::user()
+    }
+}
+
+/// One entry of a plan ticket's audit trail (plan_ticket_events row). Kept after the ticket is deleted.
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct PlanTicketEvent {
+    #[ts(as = "f64")]
+    pub id: i64,
+    pub project_id: String,
+    pub ticket_id: String,
+    #[ts(as = "f64")]
+    pub ticket_number: i64,
+    /// "created", "updated" (one field), "moved" (status/position), "claimed" (by an agent run) or "deleted".
+    pub kind: String,
+    pub field: Option<String>,
+    pub old_value: Option<serde_json::Value>,
+    pub new_value: Option<serde_json::Value>,
+    pub actor: Actor,
+    pub created_at: String,
+}
+
+const PLAN_TICKET_EVENT_COLUMNS: &str =
+    "id, project_id, ticket_id, ticket_number, kind, field, old_value, new_value, actor_kind, actor_id, created_at";
+
+fn plan_ticket_event_from_row(row: &rusqlite::Row) -> rusqlite::Result<PlanTicketEvent> {
+    let json = |v: Option<String>| v.and_then(|s| serde_json::from_str(&s).ok());
+    Ok(PlanTicketEvent {
+        id: row.get(0)?,
+        project_id: row.get(1)?,
+        ticket_id: row.get(2)?,
+        ticket_number: row.get(3)?,
+        kind: row.get(4)?,
+        field: row.get(5)?,
+        old_value: json(row.get(6)?),
+        new_value: json(row.get(7)?),
+        actor: Actor {
+            kind: ActorKind::parse(&row.get::<_, String>(8)?),
+            id: row.get(9)?,
+        },
+        created_at: row.get(10)?,
+    })
+}
+
+fn record_plan_ticket_event(
+    conn: &Connection,
+    ticket: &PlanTicket,
+    kind: &str,
+    field: Option<&str>,
+    old_value: Option<serde_json::Value>,
+    new_value: Option<serde_json::Value>,
+    actor: &Actor,
+) -> AppResult<()> {
+    conn.execute(
+        "INSERT INTO plan_ticket_events (project_id, ticket_id, ticket_number, kind, field, old_value, new_value, actor_kind, actor_id, created_at)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)",
+        params![
+            ticket.project_id,
+            ticket.id,
+            ticket.number,
+            kind,
+            field,
+            old_value.map(|v| v.to_string()),
+            new_value.map(|v| v.to_string()),
+            actor.kind.as_str(),
+            actor.id,
+            chrono::Utc::now().to_rfc3339(),
+        ],
+    )?;
+    Ok(())
+}
+
+/// Record what changed between two versions of a ticket: one "moved" event for status/position, one "updated" event
+/// per other edited field.
+fn record_plan_ticket_changes(conn: &Connection, before: &PlanTicket, after: &PlanTicket, actor: &Actor) -> AppResult<()> {
+    if before.status != after.status || before.position != after.position {
+        record_plan_ticket_event(
+            conn,
+            after,
+            "moved",
+            None,
+            Some(serde_json::json!({ "status": before.status, "position": before.position })),
+            Some(serde_json::json!({ "status": after.status, "position": after.position })),
+            actor,
+        )?;
+    }
+    let (old, new) = (serde_json::to_value(before)?, serde_json::to_value(after)?);
+    for field in ["title", "description", "priority", "feature_name", "milestone_id", "idea_id", "agents"] {
+        if old[field] != new[field] {
+            record_plan_ticket_event(
+                conn,
+                after,
+                "updated",
+                Some(field),
+                Some(old[field].clone()),
+                Some(new[field].clone()),
+                actor,
+            )?;
+        }
+    }
+    Ok(())
+}
+
+/// Record that an agent run picked up a ticket.
+pub fn claim_plan_ticket(conn: &Connection, project_id: &str, ticket_id: &str, run_id: &str) -> AppResult<()> {
+    let ticket =
+        get_plan_ticket(conn, project_id, ticket_id)?.ok_or_else(|| AppError::not_found("Ticket not found"))?;
+    record_plan_ticket_event(conn, &ticket, "claimed", None, None, None, &Actor::agent(run_id.trim()))
+}
+
+/// A ticket's events, oldest first. Works for deleted tickets too.
+pub fn get_plan_ticket_history(conn: &Connection, project_id: &str, ticket_id: &str) -> AppResult<Vec<PlanTicketEvent>> {
+    let mut stmt = conn.prepare_cached(&format!(
+        "SELECT {} FROM plan_ticket_events WHERE project_id = ?1 AND ticket_id = ?2 ORDER BY id",
+        PLAN_TICKET_EVENT_COLUMNS
+    ))?;
+    let events = stmt
+        .query_map(params![project_id.trim(), ticket_id.trim()], plan_ticket_event_from_row)?
+        .collect::<rusqlite::Result<Vec<_>>>()?;
+    Ok(events)
+}
+
+/// Project activity feed, newest first. Pass the last id of a page as `before_id` for the next one.
+pub fn get_plan_ticket_activity(
+    conn: &Connection,
+    project_id: &str,
+    limit: i64,
+    before_id: Option<i64>,
+) -> AppResult<Vec<PlanTicketEvent>> {
+    let mut stmt = conn.prepare_cached(&format!(
+        "SELECT {} FROM plan_ticket_events WHERE project_id = ?1 AND (?2 IS NULL OR id < ?2) ORDER BY id DESC LIMIT ?3",
+        PLAN_TICKET_EVENT_COLUMNS
+    ))?;
+    let events = stmt
+        .query_map(params![project_id.trim(), before_id, limit.clamp(1, 500)], plan_ticket_event_from_row)?
+        .collect::<rusqlite::Result<Vec<_>>>()?;
+    Ok(events)
+}
+
+/// `ticket_id` is blocked by `depends_on_id` until that ticket is done.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct PlanTicketDependency {
+    pub ticket_id: String,
+    pub depends_on_id: String,
+}
+
+/// All dependency edges between a project's tickets (edges to tickets in the trash are left out).
+pub fn get_plan_ticket_dependencies(conn: &Connection, project_id: &str) -> AppResult<Vec<PlanTicketDependency>> {
+    let mut stmt = conn.prepare_cached(
+        "SELECT d.ticket_id, d.depends_on_id FROM plan_ticket_dependencies d
+         JOIN plan_tickets t ON t.id = d.ticket_id
+         JOIN plan_tickets dep ON dep.id = d.depends_on_id
+         WHERE t.project_id = ?1 AND t.deleted_at IS NULL AND dep.deleted_at IS NULL
+         ORDER BY t.number, d.depends_on_id",
+    )?;
+    let deps = stmt
+        .query_map(params![project_id.trim()], |row| {
+            Ok(PlanTicketDependency { ticket_id: row.get(0)?, depends_on_id: row.get(1)? })
+        })?
+        .collect::<rusqlite::Result<Vec<_>>>()?;
+    Ok(deps)
+}
+
+/// Chain of ids from `from` to `to` following "depends on" edges, if there is one.
+fn dependency_path(deps: &[PlanTicketDependency], from: &str, to: &str) -> Option<Vec<String>> {
+    let mut parent: HashMap<&str, &str> = HashMap::new();
+    let mut queue = std::collections::VecDeque::from([from]);
+    while let Some(id) = queue.pop_front() {
+        if id == to {
+            let mut path = vec![to.to_string()];
+            let mut cur = to;
+            while let Some(p) = parent.get(cur) {
+                path.push(p.to_string());
+                cur = p;
+            }
+            path.reverse();
+            return Some(path);
+        }
+        for d in deps.iter().filter(|d| d.ticket_id == id) {
+            if d.depends_on_id != from && !parent.contains_key(d.depends_on_id.as_str()) {
+                parent.insert(&d.depends_on_id, id);
+                queue.push_back(&d.depends_on_id);
+            }
+        }
+    }
+    None
+}
+
+/// Record that `ticket_id` depends on `depends_on_id`. Both must belong to the project; an edge that would close a
+/// cycle is rejected with the cycle (ticket ids) in the error details.
+pub fn add_plan_ticket_dependency(
+    conn: &Connection,
+    project_id: &str,
+    ticket_id: &str,
+    depends_on_id: &str,
+) -> AppResult<()> {
+    let project_id = project_id.trim();
+    let (ticket_id, depends_on_id) = (ticket_id.trim(), depends_on_id.trim());
+    for id in [ticket_id, depends_on_id] {
+        if get_plan_ticket(conn, project_id, id)?.is_none() {
+            return Err(AppError::not_found(format!("Ticket not found: {}", id)));
+        }
+    }
+    if ticket_id == depends_on_id {
+        return Err(AppError::invalid("A ticket cannot depend on itself"));
+    }
+    let deps = get_plan_ticket_dependencies(conn, project_id)?;
+    if let Some(mut cycle) = dependency_path(&deps, depends_on_id, ticket_id) {
+        cycle.insert(0, ticket_id.to_string());
+        let numbers: HashMap<String, i64> =
+            get_plan_tickets_for_project(conn, project_id)?.into_iter().map(|t| (t.id, t.number)).collect();
+        let labels: Vec<String> = cycle
+            .iter()
+            .map(|id| numbers.get(id).map(|n| format!("#{}", n)).unwrap_or_else(|| id.clone()))
+            .collect();
+        return Err(AppError::conflict(format!("Dependency would create a cycle: {}", labels.join(" → ")))
+            .with_details(serde_json::json!({ "cycle": cycle })));
+    }
+    conn.execute(
+        "INSERT OR IGNORE INTO plan_ticket_dependencies (ticket_id, depends_on_id, created_at) VALUES (?1, ?2, ?3)",
+        params![ticket_id, depends_on_id, chrono::Utc::now().to_rfc3339()],
+    )?;
+    Ok(())
+}
+
+/// Remove a dependency edge. Returns false when it did not exist.
+pub fn remove_plan_ticket_dependency(
+    conn: &Connection,
+    project_id: &str,
+    ticket_id: &str,
+    depends_on_id: &str,
+) -> AppResult<bool> {
+    let removed = conn.execute(
+        "DELETE FROM plan_ticket_dependencies WHERE ticket_id = ?1 AND depends_on_id = ?2
+            AND ticket_id IN (SELECT id FROM plan_tickets WHERE project_id = ?3)",
+        params![ticket_id.trim(), depends_on_id.trim(), project_id.trim()],
+    )?;
+    Ok(removed > 0)
+}
+
+/// Open tickets whose dependencies are all done, in topological-then-priority order: tickets with shorter dependency
+/// chains first (foundations before what builds on them), then by priority, board position and number.
+pub fn get_ready_plan_tickets(conn: &Connection, project_id: &str) -> AppResult<Vec<PlanTicket>> {
+    let tickets = get_plan_tickets_for_project(conn, project_id)?;
+    let deps = get_plan_ticket_dependencies(conn, project_id)?;
+    let done: HashMap<&str, bool> = tickets.iter().map(|t| (t.id.as_str(), t.done)).collect();
+    let mut depth: HashMap<&str, usize> = HashMap::new();
+    // The graph is acyclic (add_plan_ticket_dependency refuses cycles), so the recursion terminates.
+    fn chain_depth<'a>(id: &'a str, deps: &'a [PlanTicketDependency], memo: &mut HashMap<&'a str, usize>) -> usize {
+        if let Some(d) = memo.get(id) {
+            return *d;
+        }
+        let d = deps
+            .iter()
+            .filter(|e| e.ticket_id == id)
+            .map(|e| chain_depth(&e.depends_on_id, deps, memo) + 1)
+            .max()
+            .unwrap_or(0);
+        memo.insert(id, d);
+        d
+    }
+    let mut ready: Vec<(usize, PlanTicket)> = vec![];
+    for t in &tickets {
+        let blocked = deps
+            .iter()
+            .any(|d| d.ticket_id == t.id && !done.get(d.depends_on_id.as_str()).copied().unwrap_or(true));
+        if !t.done && !blocked {
+            ready.push((chain_depth(&t.id, &deps, &mut depth), t.clone()));
+        }
+    }
+    ready.sort_by(|(da, a), (db, b)| {
+        da.cmp(db)
+            .then_with(|| a.priority.cmp(&b.priority))
+            .then_with(|| a.position.cmp(&b.position))
+            .then_with(|| a.number.cmp(&b.number))
+    });
+    Ok(ready.into_iter().map(|(_, t)| t).collect())
+}
+
+const GENERAL_DEVELOPMENT_NAME: &str = "General Development";
+const GENERAL_DEVELOPMENT_SLUG: &str = "general-development";
+
+const MILESTONE_COLUMNS: &str =
+    "id, project_id, name, slug, content, start_date, due_date, position, status, created_at, updated_at";
+
+const MILESTONE_STATUSES: [&str; 3] = ["planned", "active", "shipped"];
+
+fn milestone_from_row(row: &rusqlite::Row) -> rusqlite::Result<Milestone> {
+    Ok(Milestone {
+        id: row.get(0)?,
+        project_id: row.get(1)?,
+        name: row.get(2)?,
+        slug: row.get(3)?,
+        content: row.get(4)?,
+        start_date: row.get(5)?,
+        due_date: row.get(6)?,
+        position: row.get(7)?,
+        status: row.get(8)?,
+        created_at: row.get(9)?,
+        updated_at: row.get(10)?,
+    })
+}
+
+fn query_milestones(conn: &Connection, project_id: &str) -> AppResult<Vec<Milestone>> {
+    let mut stmt = conn
+        .prepare_cached(&format!(
+            "SELECT {} FROM milestones WHERE project_id = ?1 ORDER BY position ASC, name ASC",
+            MILESTONE_COLUMNS
+        ))?;
+    let rows = stmt
+        .query_map(rusqlite::params![project_id], milestone_from_row)?;
+    let mut out = vec![];
+    for row in rows {
+        out.push(row?);
+    }
+    Ok(out)
+}
+
+/// Milestones of a project, creating the default "General Development" milestone when it is missing.
+pub fn get_milestones_for_project(conn: &Connection, project_id: &str) -> AppResult<Vec<Milestone>> {
+    let project_id = project_id.trim();
+    let out = query_milestones(conn, project_id)?;
+    if out.iter().any(|m| m.name == GENERAL_DEVELOPMENT_NAME) {
+        return Ok(out);
+    }
+    let now = chrono::Utc::now().to_rfc3339();
+    conn.execute(
+        "INSERT INTO milestones (project_id, name, slug, content, position, created_at, updated_at)
+         VALUES (?1, ?2, ?3, ?4, (SELECT COALESCE(MAX(position) + 1, 0) FROM milestones WHERE project_id = ?1), ?5, ?6)",
+        rusqlite::params![project_id, GENERAL_DEVELOPMENT_NAME, GENERAL_DEVELOPMENT_SLUG, None::<String>, &now, &now],
+    )?;
+    query_milestones(conn, project_id)
+}
+
+/// Id of the project's "General Development" milestone (created if missing); the default for new tickets.
+pub fn general_development_milestone_id(conn: &Connection, project_id: &str) -> AppResult<i64> {
+    get_milestones_for_project(conn, project_id)?
+        .into_iter()
+        .find(|m| m.name == GENERAL_DEVELOPMENT_NAME)
+        .map(|m| m.id)
+        .ok_or_else(|| AppError::internal("General Development milestone missing"))
+}
+
+/// Create one idea and return the new row. Used by Idea-driven "create from description" flow.
+pub fn create_idea(
+    conn: &Connection,
+    project_id: Option<&str>,
+    title: &str,
+    description: &str,
+    category: &str,
+    source: &str,
+) -> AppResult<Idea> {
+    let now = chrono::Utc::now().to_rfc3339();
+    conn.execute(
+        "INSERT INTO ideas (project_id, title, description, category, body, source, created_at, updated_at) VALUES (?1, ?2, ?3, ?4, NULL, ?5, ?6, ?7)",
+        rusqlite::params![
+            project_id,
+            title.trim(),
+            description.trim(),
+            if category.is_empty() { "other" } else { category },
+            if source.is_empty() { "manual" } else { source },
+            &now,
+            &now,
+        ],
+    )?;
+    let id = conn.last_insert_rowid();
+    conn.query_row(
+        &format!("SELECT {} FROM ideas WHERE id = ?1", IDEA_COLUMNS),
+        rusqlite::params![id],
+        idea_from_row,
+    )
+    .map_err(AppError::from)
+}
+
+/// Create one milestone for a project and return the new row. Used by Idea-driven "create from description" flow.
+pub fn create_milestone(
+    conn: &Connection,
+    project_id: &str,
+    name: &str,
+    slug: &str,
+    content: Option<&str>,
+) -> AppResult<Milestone> {
+    let now = chrono::Utc::now().to_rfc3339();
+    let slug_use = if slug.trim().is_empty() { milestone_slug(name) } else { slug.trim().to_string() };
+    conn.execute(
+        "INSERT INTO milestones (project_id, name, slug, content, position, created_at, updated_at)
+         VALUES (?1, ?2, ?3, ?4, (SELECT COALESCE(MAX(position) + 1, 0) FROM milestones WHERE project_id = ?1), ?5, ?6)",
+        rusqlite::params![
+            project_id.trim(),
             name.trim(),
             slug_use,
             content.unwrap_or("").trim(),
             &now,
             &now,
         ],
-    )
-    .map_err(|e| e.to_string())?;
-    let id = conn.last_insert_rowid();
-    let (project_id_out, name_out, slug_out, content_out, created_at, updated_at): (
-        String, String, String, Option<String>, String, String,
-    ) = conn
-        .query_row(
-            "SELECT project_id, name, slug, content, created_at, updated_at FROM milestones WHERE id = ?1",
-            rusqlite::params![id],
-            |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?, row.get(4)?, row.get(5)?)),
-        )
-        .map_err(|e| e.to_string())?;
-    Ok(serde_json::json!({
-        "id": id,
-        "project_id": project_id_out,
-        "name": name_out,
-        "slug": slug_out,
-        "content": content_out,
-        "created_at": created_at,
-        "updated_at": updated_at,
-    }))
-}
-
-pub fn get_ideas_list(conn: &Connection, project_id: Option<&str>) -> Result<Vec<serde_json::Value>, String> {
-    let mut out = vec![];
-    let map_row = |row: &rusqlite::Row| {
-        Ok(serde_json::json!({
-            "id": row.get::<_, i64>(0)?,
-            "project_id": row.get::<_, Option<String>>(1)?,
-            "title": row.get::<_, String>(2)?,
-            "description": row.get::<_, String>(3)?,
-            "category": row.get::<_, String>(4)?,
-            "source": row.get::<_, String>(5)?,
-            "created_at": row.get::<_, String>(6)?,
-            "updated_at": row.get::<_, String>(7)?,
-        }))
+    )?;
+    let id = conn.last_insert_rowid();
+    conn.query_row(
+        &format!("SELECT {} FROM milestones WHERE id = ?1", MILESTONE_COLUMNS),
+        rusqlite::params![id],
+        milestone_from_row,
+    )
+    .map_err(AppError::from)
+}
+
+/// Partial update of a milestone. `content` can be cleared with null.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct MilestonePatch {
+    #[serde(default)]
+    #[ts(optional)]
+    pub name: Option<String>,
+    /// Empty = derive from the name.
+    #[serde(default)]
+    #[ts(optional)]
+    pub slug: Option<String>,
+    #[serde(default, deserialize_with = "double_option")]
+    #[ts(optional = nullable, as = "Option<String>")]
+    pub content: Option<Option<String>>,
+    /// YYYY-MM-DD; null clears.
+    #[serde(default, alias = "startDate", deserialize_with = "double_option")]
+    #[ts(optional = nullable, as = "Option<String>")]
+    pub start_date: Option<Option<String>>,
+    /// YYYY-MM-DD; null clears.
+    #[serde(default, alias = "dueDate", deserialize_with = "double_option")]
+    #[ts(optional = nullable, as = "Option<String>")]
+    pub due_date: Option<Option<String>>,
+    /// "planned", "active" or "shipped".
+    #[serde(default)]
+    #[ts(optional)]
+    pub status: Option<String>,
+}
+
+/// What happens to a deleted milestone's tickets.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[serde(tag = "mode", rename_all = "snake_case")]
+#[ts(export)]
+pub enum MilestoneTicketPolicy {
+    /// Move them to "General Development".
+    #[default]
+    General,
+    /// Move them to another milestone of the project.
+    Milestone {
+        #[serde(alias = "milestoneId")]
+        #[ts(as = "f64")]
+        milestone_id: i64,
+    },
+    /// Delete them with the milestone.
+    DeleteTickets,
+}
+
+fn milestone_slug(name: &str) -> String {
+    let slug = name
+        .trim()
+        .to_lowercase()
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '-' })
+        .collect::<String>()
+        .trim_matches('-')
+        .to_string();
+    if slug.is_empty() { "milestone".to_string() } else { slug }
+}
+
+fn get_milestone(conn: &Connection, project_id: &str, milestone_id: i64) -> AppResult<Milestone> {
+    conn.query_row(
+        &format!("SELECT {} FROM milestones WHERE id = ?1 AND project_id = ?2", MILESTONE_COLUMNS),
+        params![milestone_id, project_id.trim()],
+        milestone_from_row,
+    )
+    .map_err(|e| match e {
+        rusqlite::Error::QueryReturnedNoRows => AppError::not_found(format!("Milestone {} not found", milestone_id)),
+        e => e.into(),
+    })
+}
+
+/// "General Development" is recreated by name whenever it is missing, so it cannot be renamed, deleted or merged away.
+fn ensure_not_general(milestone: &Milestone, action: &str) -> AppResult<()> {
+    if milestone.name == GENERAL_DEVELOPMENT_NAME {
+        return Err(AppError::invalid(format!("The {} milestone cannot be {}", GENERAL_DEVELOPMENT_NAME, action)));
+    }
+    Ok(())
+}
+
+/// Rename a milestone or edit its slug/content. Names stay unique within the project.
+pub fn update_milestone(
+    conn: &Connection,
+    project_id: &str,
+    milestone_id: i64,
+    patch: &MilestonePatch,
+) -> AppResult<Milestone> {
+    let mut milestone = get_milestone(conn, project_id, milestone_id)?;
+    if let Some(name) = patch.name.as_deref().map(str::trim).filter(|n| *n != milestone.name) {
+        if name.is_empty() {
+            return Err(AppError::invalid("name is required"));
+        }
+        ensure_not_general(&milestone, "renamed")?;
+        let taken: i64 = conn.query_row(
+            "SELECT COUNT(*) FROM milestones WHERE project_id = ?1 AND name = ?2 AND id != ?3",
+            params![milestone.project_id, name, milestone_id],
+            |row| row.get(0),
+        )?;
+        if taken > 0 || name == GENERAL_DEVELOPMENT_NAME {
+            return Err(AppError::conflict(format!("A milestone named '{}' already exists", name)));
+        }
+        milestone.name = name.to_string();
+    }
+    if let Some(slug) = &patch.slug {
+        milestone.slug = if slug.trim().is_empty() { milestone_slug(&milestone.name) } else { slug.trim().to_string() };
+    }
+    if let Some(content) = &patch.content {
+        milestone.content = content.as_deref().map(str::trim).map(str::to_string);
+    }
+    if let Some(start_date) = &patch.start_date {
+        milestone.start_date = start_date.as_deref().map(|d| parse_milestone_date(d, "start_date")).transpose()?;
+    }
+    if let Some(due_date) = &patch.due_date {
+        milestone.due_date = due_date.as_deref().map(|d| parse_milestone_date(d, "due_date")).transpose()?;
+    }
+    if let (Some(start), Some(due)) = (&milestone.start_date, &milestone.due_date) {
+        if start > due {
+            return Err(AppError::invalid(format!("start_date {} is after due_date {}", start, due)));
+        }
+    }
+    if let Some(status) = &patch.status {
+        if !MILESTONE_STATUSES.contains(&status.as_str()) {
+            return Err(AppError::invalid(format!(
+                "Invalid milestone status '{}' (expected {})",
+                status,
+                MILESTONE_STATUSES.join(", ")
+            )));
+        }
+        milestone.status = status.clone();
+    }
+    milestone.updated_at = chrono::Utc::now().to_rfc3339();
+    conn.execute(
+        "UPDATE milestones SET name = ?1, slug = ?2, content = ?3, start_date = ?4, due_date = ?5, status = ?6,
+            updated_at = ?7 WHERE id = ?8",
+        params![
+            milestone.name,
+            milestone.slug,
+            milestone.content,
+            milestone.start_date,
+            milestone.due_date,
+            milestone.status,
+            milestone.updated_at,
+            milestone_id
+        ],
+    )?;
+    Ok(milestone)
+}
+
+/// Normalize a YYYY-MM-DD date.
+fn parse_milestone_date(value: &str, field: &str) -> AppResult<String> {
+    chrono::NaiveDate::parse_from_str(value.trim(), "%Y-%m-%d")
+        .map(|d| d.format("%Y-%m-%d").to_string())
+        .map_err(|_| AppError::invalid(format!("{} must be a date in YYYY-MM-DD form, got '{}'", field, value)))
+}
+
+/// Put the listed milestones first, in that order; the others keep their relative order after them.
+pub fn reorder_milestones(conn: &Connection, project_id: &str, milestone_ids: &[i64]) -> AppResult<Vec<Milestone>> {
+    let project_id = project_id.trim();
+    let current = get_milestones_for_project(conn, project_id)?;
+    if let Some(unknown) = milestone_ids.iter().find(|id| !current.iter().any(|m| m.id == **id)) {
+        return Err(AppError::not_found(format!("Milestone {} not found", unknown)));
+    }
+    let mut order: Vec<i64> = vec![];
+    for id in milestone_ids.iter().chain(current.iter().map(|m| &m.id)) {
+        if !order.contains(id) {
+            order.push(*id);
+        }
+    }
+    let tx = conn.unchecked_transaction()?;
+    {
+        let mut stmt = tx.prepare_cached("UPDATE milestones SET position = ?1 WHERE id = ?2 AND project_id = ?3")?;
+        for (position, id) in order.iter().enumerate() {
+            stmt.execute(params![position as i64, id, project_id])?;
+        }
+    }
+    tx.commit()?;
+    query_milestones(conn, project_id)
+}
+
+/// Move every ticket of `from` to `to` (milestone ids of the same project), recording each change.
+fn reassign_milestone_tickets(conn: &Connection, project_id: &str, from: i64, to: i64, actor: &Actor) -> AppResult<usize> {
+    let ids = milestone_ticket_ids(conn, project_id, from)?;
+    let patch = PlanTicketPatch { milestone_id: Some(to), ..Default::default() };
+    for id in &ids {
+        apply_plan_ticket_patch(conn, project_id, id, &patch, actor)?;
+    }
+    Ok(ids.len())
+}
+
+fn milestone_ticket_ids(conn: &Connection, project_id: &str, milestone_id: i64) -> AppResult<Vec<String>> {
+    let mut stmt =
+        conn.prepare_cached(
+            "SELECT id FROM plan_tickets WHERE project_id = ?1 AND milestone_id = ?2 AND deleted_at IS NULL
+             ORDER BY number",
+        )?;
+    let ids = stmt
+        .query_map(params![project_id.trim(), milestone_id], |row| row.get(0))?
+        .collect::<rusqlite::Result<Vec<String>>>()?;
+    Ok(ids)
+}
+
+/// Delete a milestone, handling its tickets per `policy`. Returns how many tickets were moved or deleted.
+pub fn delete_milestone(
+    conn: &Connection,
+    project_id: &str,
+    milestone_id: i64,
+    policy: &MilestoneTicketPolicy,
+    actor: &Actor,
+) -> AppResult<usize> {
+    let project_id = project_id.trim();
+    let milestone = get_milestone(conn, project_id, milestone_id)?;
+    ensure_not_general(&milestone, "deleted")?;
+    let tx = conn.unchecked_transaction()?;
+    let affected = match policy {
+        MilestoneTicketPolicy::General => {
+            let general = general_development_milestone_id(&tx, project_id)?;
+            reassign_milestone_tickets(&tx, project_id, milestone_id, general, actor)?
+        }
+        MilestoneTicketPolicy::Milestone { milestone_id: target } => {
+            if *target == milestone_id {
+                return Err(AppError::invalid("Tickets cannot be moved to the milestone being deleted"));
+            }
+            get_milestone(&tx, project_id, *target)?;
+            reassign_milestone_tickets(&tx, project_id, milestone_id, *target, actor)?
+        }
+        MilestoneTicketPolicy::DeleteTickets => {
+            let ids = milestone_ticket_ids(&tx, project_id, milestone_id)?;
+            for id in &ids {
+                if let Some(ticket) = get_plan_ticket(&tx, project_id, id)? {
+                    apply_plan_ticket_delete(&tx, &ticket, actor)?;
+                }
+            }
+            ids.len()
+        }
+    };
+    tx.execute("DELETE FROM milestones WHERE id = ?1 AND project_id = ?2", params![milestone_id, project_id])?;
+    tx.commit()?;
+    Ok(affected)
+}
+
+/// Fold `source_ids` into `target_id`: their tickets move to the target, their content is appended to the target's,
+/// and the sources are deleted. Returns the updated target.
+pub fn merge_milestones(
+    conn: &Connection,
+    project_id: &str,
+    source_ids: &[i64],
+    target_id: i64,
+    actor: &Actor,
+) -> AppResult<Milestone> {
+    let project_id = project_id.trim();
+    let mut target = get_milestone(conn, project_id, target_id)?;
+    let mut sources = vec![];
+    for id in source_ids.iter().filter(|id| **id != target_id) {
+        let source = get_milestone(conn, project_id, *id)?;
+        ensure_not_general(&source, "merged into another milestone")?;
+        if !sources.iter().any(|s: &Milestone| s.id == source.id) {
+            sources.push(source);
+        }
+    }
+    if sources.is_empty() {
+        return Err(AppError::invalid("Pick at least one milestone other than the target to merge"));
+    }
+    let tx = conn.unchecked_transaction()?;
+    let mut content: Vec<String> = target.content.iter().filter(|c| !c.trim().is_empty()).cloned().collect();
+    for source in &sources {
+        reassign_milestone_tickets(&tx, project_id, source.id, target_id, actor)?;
+        if let Some(c) = source.content.as_deref().map(str::trim).filter(|c| !c.is_empty()) {
+            content.push(format!("## {}\n\n{}", source.name, c));
+        }
+        tx.execute("DELETE FROM milestones WHERE id = ?1", params![source.id])?;
+    }
+    target.content = if content.is_empty() { target.content } else { Some(content.join("\n\n")) };
+    target.updated_at = chrono::Utc::now().to_rfc3339();
+    tx.execute(
+        "UPDATE milestones SET content = ?1, updated_at = ?2 WHERE id = ?3",
+        params![target.content, target.updated_at, target_id],
+    )?;
+    tx.commit()?;
+    Ok(target)
+}
+
+/// Open tickets of a milestone by priority.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct PriorityCounts {
+    #[ts(as = "f64")]
+    pub p0: i64,
+    #[ts(as = "f64")]
+    pub p1: i64,
+    #[ts(as = "f64")]
+    pub p2: i64,
+    #[ts(as = "f64")]
+    pub p3: i64,
+}
+
+/// Ticket counts for one milestone (get_milestone_progress).
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct MilestoneProgress {
+    #[ts(as = "f64")]
+    pub milestone_id: i64,
+    pub name: String,
+    #[ts(as = "f64")]
+    pub total: i64,
+    #[ts(as = "f64")]
+    pub done: i64,
+    /// Open tickets in the "In progress" column.
+    #[ts(as = "f64")]
+    pub in_progress: i64,
+    pub open_by_priority: PriorityCounts,
+    /// Done tickets completed within the past 7 / 30 days (see get_milestone_progress).
+    #[ts(as = "f64")]
+    pub done_last_7_days: i64,
+    #[ts(as = "f64")]
+    pub done_last_30_days: i64,
+}
+
+/// Progress of every milestone of a project, in milestone list order (empty milestones included). A done ticket was
+/// completed when it last moved into its done column, or, without such an event, when its latest implementation log
+/// entry was completed; later edits do not count.
+pub fn get_milestone_progress(conn: &Connection, project_id: &str) -> AppResult<Vec<MilestoneProgress>> {
+    let project_id = project_id.trim();
+    get_milestones_for_project(conn, project_id)?;
+    let now = chrono::Utc::now();
+    let since = |days: i64| (now - chrono::Duration::days(days)).to_rfc3339();
+    let mut stmt = conn.prepare_cached(
+        "WITH completed AS (
+            SELECT t.id, COALESCE(
+                (SELECT MAX(e.created_at) FROM plan_ticket_events e
+                 WHERE e.ticket_id = t.id AND e.kind = 'moved'
+                   AND json_extract(e.new_value, '$.status') = t.status
+                   AND json_extract(e.old_value, '$.status') != t.status),
+                (SELECT MAX(l.completed_at) FROM implementation_log l
+                 WHERE l.project_id = t.project_id AND l.ticket_number = t.number AND l.status != 'declined')
+            ) AS completed_at
+            FROM plan_tickets t
+            WHERE t.project_id = ?1 AND t.done = 1 AND t.deleted_at IS NULL
+         )
+         SELECT m.id, m.name, COUNT(t.id),
+            COALESCE(SUM(t.done = 1), 0),
+            COALESCE(SUM(t.done = 0 AND t.status = ?2), 0),
+            COALESCE(SUM(t.done = 0 AND t.priority = 'P0'), 0),
+            COALESCE(SUM(t.done = 0 AND t.priority = 'P1'), 0),
+            COALESCE(SUM(t.done = 0 AND t.priority = 'P2'), 0),
+            COALESCE(SUM(t.done = 0 AND t.priority = 'P3'), 0),
+            COALESCE(SUM(c.completed_at >= ?3), 0),
+            COALESCE(SUM(c.completed_at >= ?4), 0)
+         FROM milestones m
+         LEFT JOIN plan_tickets t ON t.milestone_id = m.id AND t.project_id = m.project_id AND t.deleted_at IS NULL
+         LEFT JOIN completed c ON c.id = t.id
+         WHERE m.project_id = ?1
+         GROUP BY m.id
+         ORDER BY m.position ASC, m.name ASC",
+    )?;
+    let progress = stmt
+        .query_map(params![project_id, IN_PROGRESS_STATUS, since(7), since(30)], |row| {
+            Ok(MilestoneProgress {
+                milestone_id: row.get(0)?,
+                name: row.get(1)?,
+                total: row.get(2)?,
+                done: row.get(3)?,
+                in_progress: row.get(4)?,
+                open_by_priority: PriorityCounts { p0: row.get(5)?, p1: row.get(6)?, p2: row.get(7)?, p3: row.get(8)? },
+                done_last_7_days: row.get(9)?,
+                done_last_30_days: row.get(10)?,
+            })
+        })?
+        .collect::<rusqlite::Result<Vec<_>>>()?;
+    Ok(progress)
+}
+
+/// A milestone on the roadmap with its projected completion.
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct RoadmapMilestone {
+    pub milestone: Milestone,
+    #[ts(as = "f64")]
+    pub total: i64,
+    #[ts(as = "f64")]
+    pub done: i64,
+    /// YYYY-MM-DD the open tickets of this and all earlier milestones should be finished at the current throughput.
+    /// None when shipped or when there is no throughput to project from.
+    pub projected_completion: Option<String>,
+    /// projected_completion is on or before due_date (None without both).
+    pub on_track: Option<bool>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct Roadmap {
+    pub project_id: String,
+    /// Tickets completed per day over the throughput window.
+    pub throughput_per_day: f64,
+    #[ts(as = "f64")]
+    pub throughput_window_days: i64,
+    pub milestones: Vec<RoadmapMilestone>,
+}
+
+const ROADMAP_THROUGHPUT_DAYS: i64 = 28;
+
+/// Milestones in order with a completion date projected from implementation_log throughput: open tickets are worked
+/// off milestone by milestone (shipped ones skipped) at the rate tickets were completed over the last four weeks.
+pub fn get_roadmap(conn: &Connection, project_id: &str) -> AppResult<Roadmap> {
+    let project_id = project_id.trim();
+    let progress = get_milestone_progress(conn, project_id)?;
+    let milestones = query_milestones(conn, project_id)?;
+    let today = chrono::Utc::now().date_naive();
+    let since = (chrono::Utc::now() - chrono::Duration::days(ROADMAP_THROUGHPUT_DAYS)).to_rfc3339();
+    let completed: i64 = conn.query_row(
+        "SELECT COUNT(DISTINCT ticket_number) FROM implementation_log
+         WHERE project_id = ?1 AND status != 'declined' AND completed_at >= ?2",
+        params![project_id, since],
+        |row| row.get(0),
+    )?;
+    let per_day = completed as f64 / ROADMAP_THROUGHPUT_DAYS as f64;
+    let mut backlog = 0i64;
+    let mut out = vec![];
+    for milestone in milestones {
+        let (total, done) = progress
+            .iter()
+            .find(|p| p.milestone_id == milestone.id)
+            .map(|p| (p.total, p.done))
+            .unwrap_or((0, 0));
+        let projected_completion = if milestone.status == "shipped" {
+            None
+        } else {
+            backlog += total - done;
+            if backlog == 0 {
+                Some(today)
+            } else if per_day > 0.0 {
+                Some(today + chrono::Duration::days((backlog as f64 / per_day).ceil() as i64))
+            } else {
+                None
+            }
+        }
+        .map(|d| d.format("%Y-%m-%d").to_string());
+        let on_track = match (&projected_completion, &milestone.due_date) {
+            (Some(projected), Some(due)) => Some(projected <= due),
+            _ => None,
+        };
+        out.push(RoadmapMilestone { milestone, total, done, projected_completion, on_track });
+    }
+    Ok(Roadmap {
+        project_id: project_id.to_string(),
+        throughput_per_day: per_day,
+        throughput_window_days: ROADMAP_THROUGHPUT_DAYS,
+        milestones: out,
+    })
+}
+
+const IDEA_COLUMNS: &str = "id, project_id, title, description, category, source, status, created_at, updated_at, \
+     reach, impact, confidence, effort, ease";
+
+fn idea_from_row(row: &rusqlite::Row) -> rusqlite::Result<Idea> {
+    Ok(Idea {
+        id: row.get(0)?,
+        project_id: row.get(1)?,
+        title: row.get(2)?,
+        description: row.get(3)?,
+        category: row.get(4)?,
+        source: row.get(5)?,
+        status: row.get(6)?,
+        reach: row.get(9)?,
+        impact: row.get(10)?,
+        confidence: row.get(11)?,
+        effort: row.get(12)?,
+        ease: row.get(13)?,
+        rice_score: None,
+        ice_score: None,
+        created_at: row.get(7)?,
+        updated_at: row.get(8)?,
+    }
+    .scored())
+}
+
+impl Idea {
+    /// Fill in rice_score and ice_score from the factors.
+    fn scored(mut self) -> Self {
+        self.rice_score = match (self.reach, self.impact, self.confidence, self.effort) {
+            (Some(r), Some(i), Some(c), Some(e)) if e > 0.0 => Some(r * i * c / 100.0 / e),
+            _ => None,
+        };
+        self.ice_score = match (self.impact, self.confidence, self.ease) {
+            (Some(i), Some(c), Some(e)) => Some(i * c / 10.0 * e),
+            _ => None,
+        };
+        self
+    }
+}
+
+/// How an idea's priority score is computed.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "snake_case")]
+#[ts(export)]
+pub enum IdeaScoreMethod {
+    /// reach × impact × confidence% ÷ effort.
+    #[default]
+    Rice,
+    /// impact × confidence (as 0–10) × ease.
+    Ice,
+}
+
+impl IdeaScoreMethod {
+    /// The idea's score by this method (see Idea::scored); None when unscored.
+    fn score(self, idea: &Idea) -> Option<f64> {
+        match self {
+            Self::Rice => idea.rice_score,
+            Self::Ice => idea.ice_score,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "snake_case")]
+#[ts(export)]
+pub enum IdeaSort {
+    #[default]
+    Id,
+    /// Unscored ideas last.
+    Score,
+    Title,
+    CreatedAt,
+    UpdatedAt,
+}
+
+/// Filters and order for get_ideas_list. All fields are optional.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, TS)]
+#[serde(default)]
+#[ts(export)]
+pub struct IdeaListQuery {
+    #[ts(optional)]
+    pub category: Option<String>,
+    #[ts(optional)]
+    pub source: Option<String>,
+    #[ts(optional)]
+    pub status: Option<String>,
+    /// Score method used by `sort: "score"` and `min_score`.
+    #[serde(alias = "scoreMethod")]
+    #[ts(optional)]
+    pub score_method: Option<IdeaScoreMethod>,
+    /// Only ideas scoring at least this much (unscored ideas are dropped).
+    #[serde(alias = "minScore")]
+    #[ts(optional)]
+    pub min_score: Option<f64>,
+    #[ts(optional)]
+    pub sort: Option<IdeaSort>,
+    #[ts(optional)]
+    pub descending: Option<bool>,
+}
+
+/// Ideas of a project plus the ones not tied to any project (all ideas when project_id is None), filtered and
+/// sorted per `query`. Score filters and sorting run on the scores from Idea::scored, so they match what is shown.
+pub fn get_ideas_list(conn: &Connection, project_id: Option<&str>, query: &IdeaListQuery) -> AppResult<Vec<Idea>> {
+    let method = query.score_method.unwrap_or_default();
+    let mut filters: Vec<String> = vec![];
+    let mut args: Vec<Box<dyn rusqlite::ToSql>> = vec![];
+    let mut bind = |filters: &mut Vec<String>, clause: &str, value: Box<dyn rusqlite::ToSql>| {
+        args.push(value);
+        filters.push(clause.replace('?', &format!("?{}", args.len())));
+    };
+    if let Some(pid) = project_id {
+        bind(&mut filters, "(project_id = ? OR project_id IS NULL)", Box::new(pid.trim().to_string()));
+    }
+    for (column, value) in [("category", &query.category), ("source", &query.source), ("status", &query.status)] {
+        if let Some(value) = value.as_deref().map(str::trim).filter(|v| !v.is_empty()) {
+            bind(&mut filters, &format!("{} = ?", column), Box::new(value.to_string()));
+        }
+    }
+    let direction = if query.descending.unwrap_or(false) { "DESC" } else { "ASC" };
+    let order = match query.sort.unwrap_or_default() {
+        IdeaSort::Id => format!("id {}", direction),
+        // Sorted below by score; ties keep this order.
+        IdeaSort::Score => "id ASC".to_string(),
+        IdeaSort::Title => format!("title COLLATE NOCASE {}, id ASC", direction),
+        IdeaSort::CreatedAt => format!("created_at {}, id ASC", direction),
+        IdeaSort::UpdatedAt => format!("updated_at {}, id ASC", direction),
+    };
+    let filter = if filters.is_empty() { String::new() } else { format!("WHERE {}", filters.join(" AND ")) };
+    let mut stmt =
+        conn.prepare_cached(&format!("SELECT {} FROM ideas {} ORDER BY {}", IDEA_COLUMNS, filter, order))?;
+    let args: Vec<&dyn rusqlite::ToSql> = args.iter().map(|a| a.as_ref()).collect();
+    let mut ideas = stmt.query_map(args.as_slice(), idea_from_row)?.collect::<rusqlite::Result<Vec<_>>>()?;
+    if let Some(min_score) = query.min_score {
+        ideas.retain(|idea| method.score(idea).is_some_and(|score| score >= min_score));
+    }
+    if query.sort == Some(IdeaSort::Score) {
+        let descending = query.descending.unwrap_or(false);
+        ideas.sort_by(|a, b| match (method.score(a), method.score(b)) {
+            (Some(x), Some(y)) if descending => y.total_cmp(&x),
+            (Some(x), Some(y)) => x.total_cmp(&y),
+            (x, y) => y.is_some().cmp(&x.is_some()),
+        });
+    }
+    Ok(ideas)
+}
+
+/// One idea in rank_ideas with the factors behind its score.
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct RankedIdea {
+    #[ts(as = "f64")]
+    pub rank: i64,
+    pub score: f64,
+    pub method: IdeaScoreMethod,
+    /// Each factor and how it enters the score, e.g. "reach 500 × impact 2 × confidence 80% ÷ effort 4".
+    pub breakdown: String,
+    pub idea: Idea,
+}
+
+/// Top `limit` scored ideas that are still open (new or accepted), best first.
+pub fn rank_ideas(
+    conn: &Connection,
+    project_id: Option<&str>,
+    method: IdeaScoreMethod,
+    limit: usize,
+) -> AppResult<Vec<RankedIdea>> {
+    let query = IdeaListQuery {
+        score_method: Some(method),
+        // Any score passes; unscored ideas (NULL) do not.
+        min_score: Some(f64::MIN),
+        sort: Some(IdeaSort::Score),
+        descending: Some(true),
+        ..Default::default()
+    };
+    let ranked = get_ideas_list(conn, project_id, &query)?
+        .into_iter()
+        .filter(|idea| idea.status == "new" || idea.status == "accepted")
+        .take(limit)
+        .enumerate()
+        .filter_map(|(i, idea)| {
+            let (score, breakdown) = match method {
+                IdeaScoreMethod::Rice => (
+                    idea.rice_score?,
+                    format!(
+                        "reach {} × impact {} × confidence {}% ÷ effort {}",
+                        idea.reach?, idea.impact?, idea.confidence?, idea.effort?
+                    ),
+                ),
+                IdeaScoreMethod::Ice => (
+                    idea.ice_score?,
+                    format!(
+                        "impact {} × confidence {}/10 × ease {}",
+                        idea.impact?,
+                        idea.confidence? / 10.0,
+                        idea.ease?
+                    ),
+                ),
+            };
+            Some(RankedIdea { rank: i as i64 + 1, score, method, breakdown, idea })
+        })
+        .collect();
+    Ok(ranked)
+}
+
+const IDEA_CATEGORIES: [&str; 7] = ["saas", "iaas", "paas", "website", "webapp", "webshop", "other"];
+const IDEA_STATUSES: [&str; 4] = ["new", "accepted", "rejected", "converted"];
+
+/// Partial update of an idea. `project_id` can be cleared with null to untie the idea from its project.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct IdeaPatch {
+    #[serde(default)]
+    #[ts(optional)]
+    pub title: Option<String>,
+    #[serde(default)]
+    #[ts(optional)]
+    pub description: Option<String>,
+    #[serde(default)]
+    #[ts(optional)]
+    pub category: Option<String>,
+    #[serde(default, alias = "projectId", deserialize_with = "double_option")]
+    #[ts(optional = nullable, as = "Option<String>")]
+    pub project_id: Option<Option<String>>,
+    /// "new", "accepted", "rejected" or "converted".
+    #[serde(default)]
+    #[ts(optional)]
+    pub status: Option<String>,
+    /// Scoring factors (see Idea); null clears.
+    #[serde(default, deserialize_with = "double_option")]
+    #[ts(optional = nullable, as = "Option<f64>")]
+    pub reach: Option<Option<f64>>,
+    #[serde(default, deserialize_with = "double_option")]
+    #[ts(optional = nullable, as = "Option<f64>")]
+    pub impact: Option<Option<f64>>,
+    #[serde(default, deserialize_with = "double_option")]
+    #[ts(optional = nullable, as = "Option<f64>")]
+    pub confidence: Option<Option<f64>>,
+    #[serde(default, deserialize_with = "double_option")]
+    #[ts(optional = nullable, as = "Option<f64>")]
+    pub effort: Option<Option<f64>>,
+    #[serde(default, deserialize_with = "double_option")]
+    #[ts(optional = nullable, as = "Option<f64>")]
+    pub ease: Option<Option<f64>>,
+}
+
+fn get_idea(conn: &Connection, idea_id: i64) -> AppResult<Idea> {
+    conn.query_row(&format!("SELECT {} FROM ideas WHERE id = ?1", IDEA_COLUMNS), params![idea_id], idea_from_row)
+        .map_err(|e| match e {
+            rusqlite::Error::QueryReturnedNoRows => AppError::not_found(format!("Idea {} not found", idea_id)),
+            e => e.into(),
+        })
+}
+
+/// Edit an idea's fields; only the fields present in `patch` change.
+pub fn update_idea(conn: &Connection, idea_id: i64, patch: &IdeaPatch) -> AppResult<Idea> {
+    let mut idea = get_idea(conn, idea_id)?;
+    if let Some(title) = &patch.title {
+        if title.trim().is_empty() {
+            return Err(AppError::invalid("title is required"));
+        }
+        idea.title = title.trim().to_string();
+    }
+    if let Some(description) = &patch.description {
+        idea.description = description.trim().to_string();
+    }
+    if let Some(category) = &patch.category {
+        if !IDEA_CATEGORIES.contains(&category.as_str()) {
+            return Err(AppError::invalid(format!(
+                "Invalid idea category '{}' (expected {})",
+                category,
+                IDEA_CATEGORIES.join(", ")
+            )));
+        }
+        idea.category = category.clone();
+    }
+    if let Some(project_id) = &patch.project_id {
+        idea.project_id = project_id.as_deref().map(str::trim).filter(|p| !p.is_empty()).map(str::to_string);
+    }
+    if let Some(status) = &patch.status {
+        if !IDEA_STATUSES.contains(&status.as_str()) {
+            return Err(AppError::invalid(format!(
+                "Invalid idea status '{}' (expected {})",
+                status,
+                IDEA_STATUSES.join(", ")
+            )));
+        }
+        idea.status = status.clone();
+    }
+    let factors = [
+        (&patch.reach, &mut idea.reach, "reach", f64::INFINITY),
+        (&patch.impact, &mut idea.impact, "impact", f64::INFINITY),
+        (&patch.confidence, &mut idea.confidence, "confidence", 100.0),
+        (&patch.effort, &mut idea.effort, "effort", f64::INFINITY),
+        (&patch.ease, &mut idea.ease, "ease", 10.0),
+    ];
+    for (value, field, name, max) in factors {
+        if let Some(value) = *value {
+            if let Some(v) = value.filter(|v| !v.is_finite() || *v < 0.0 || *v > max) {
+                return Err(AppError::invalid(if max.is_finite() {
+                    format!("{} must be between 0 and {}, got {}", name, max, v)
+                } else {
+                    format!("{} must be 0 or more, got {}", name, v)
+                }));
+            }
+            *field = value;
+        }
+    }
+    idea.updated_at = chrono::Utc::now().to_rfc3339();
+    conn.execute(
+        "UPDATE ideas SET title = ?1, description = ?2, category = ?3, project_id = ?4, status = ?5, reach = ?6,
+            impact = ?7, confidence = ?8, effort = ?9, ease = ?10, updated_at = ?11
+         WHERE id = ?12",
+        params![
+            idea.title,
+            idea.description,
+            idea.category,
+            idea.project_id,
+            idea.status,
+            idea.reach,
+            idea.impact,
+            idea.confidence,
+            idea.effort,
+            idea.ease,
+            idea.updated_at,
+            idea_id
+        ],
+    )?;
+    Ok(idea.scored())
+}
+
+/// Delete an idea. Tickets created from it keep existing with idea_id cleared.
+pub fn delete_idea(conn: &Connection, idea_id: i64, actor: &Actor) -> AppResult<()> {
+    get_idea(conn, idea_id)?;
+    let tx = conn.unchecked_transaction()?;
+    let tickets: Vec<(String, String)> = {
+        let mut stmt =
+            tx.prepare_cached("SELECT project_id, id FROM plan_tickets WHERE idea_id = ?1 AND deleted_at IS NULL")?;
+        let rows = stmt.query_map(params![idea_id], |row| Ok((row.get(0)?, row.get(1)?)))?;
+        rows.collect::<rusqlite::Result<Vec<_>>>()?
     };
-    if let Some(pid) = project_id {
-        let mut stmt = conn
-            .prepare("SELECT id, project_id, title, description, category, source, created_at, updated_at FROM ideas WHERE project_id = ?1 OR project_id IS NULL ORDER BY id ASC")
-            .map_err(|e| e.to_string())?;
-        let rows = stmt.query_map(rusqlite::params![pid.trim()], map_row).map_err(|e| e.to_string())?;
-        for row in rows {
-            out.push(row.map_err(|e| e.to_string())?);
+    let unlink = PlanTicketPatch { idea_id: Some(None), ..Default::default() };
+    for (project_id, ticket_id) in &tickets {
+        apply_plan_ticket_patch(&tx, project_id, ticket_id, &unlink, actor)?;
+    }
+    tx.execute("UPDATE plan_tickets SET idea_id = NULL WHERE idea_id = ?1", params![idea_id])?;
+    tx.execute("DELETE FROM ideas WHERE id = ?1", params![idea_id])?;
+    tx.commit()?;
+    Ok(())
+}
+
+/// Result of convert_idea_to_plan.
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct IdeaConversion {
+    pub idea: Idea,
+    pub milestone: Milestone,
+    pub tickets: Vec<PlanTicket>,
+}
+
+/// Turn an idea into a milestone (reusing one with the same name, else created with the idea's description as
+/// content) and one ticket per title, all linked through idea_id. The idea is tied to the project and marked
+/// converted. All or nothing.
+pub fn convert_idea_to_plan(
+    conn: &Connection,
+    idea_id: i64,
+    project_id: &str,
+    milestone_name: &str,
+    ticket_titles: &[String],
+    actor: &Actor,
+) -> AppResult<IdeaConversion> {
+    let project_id = project_id.trim();
+    let idea = get_idea(conn, idea_id)?;
+    if idea.status == "converted" {
+        return Err(AppError::conflict(format!("Idea {} has already been converted", idea_id)));
+    }
+    if idea.project_id.as_deref().is_some_and(|p| p != project_id) {
+        return Err(AppError::invalid(format!("Idea {} belongs to another project", idea_id)));
+    }
+    if get_project(conn, project_id)?.is_none() {
+        return Err(AppError::not_found("Project not found"));
+    }
+    let milestone_name = milestone_name.trim().if_empty(&idea.title).trim().to_string();
+    let tx = conn.unchecked_transaction()?;
+    let milestone = match query_milestones(&tx, project_id)?.into_iter().find(|m| m.name == milestone_name) {
+        Some(m) => m,
+        None => create_milestone(&tx, project_id, &milestone_name, "", Some(&idea.description))?,
+    };
+    let mut tickets = vec![];
+    for title in ticket_titles.iter().map(|t| t.trim()).filter(|t| !t.is_empty()) {
+        tickets.push(create_plan_ticket(
+            &tx,
+            project_id,
+            title,
+            None,
+            "P1",
+            &idea.title,
+            milestone.id,
+            Some(idea_id),
+            None,
+            actor,
+        )?);
+    }
+    let now = chrono::Utc::now().to_rfc3339();
+    tx.execute(
+        "UPDATE ideas SET project_id = ?1, status = 'converted', updated_at = ?2 WHERE id = ?3",
+        params![project_id, &now, idea_id],
+    )?;
+    let idea = get_idea(&tx, idea_id)?;
+    tx.commit()?;
+    Ok(IdeaConversion { idea, milestone, tickets })
+}
+
+/// Source of a search hit (one per indexed table).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "snake_case")]
+#[ts(export)]
+pub enum SearchKind {
+    PlanTicket,
+    Ticket,
+    Idea,
+    Milestone,
+    Prompt,
+    /// implementation_log entry written by an agent run.
+    RunLog,
+}
+
+impl SearchKind {
+    fn sql(self) -> &'static str {
+        match self {
+            Self::PlanTicket => "plan_ticket",
+            Self::Ticket => "ticket",
+            Self::Idea => "idea",
+            Self::Milestone => "milestone",
+            Self::Prompt => "prompt",
+            Self::RunLog => "run_log",
+        }
+    }
+
+    fn from_sql(kind: &str) -> Option<Self> {
+        [Self::PlanTicket, Self::Ticket, Self::Idea, Self::Milestone, Self::Prompt, Self::RunLog]
+            .into_iter()
+            .find(|k| k.sql() == kind)
+    }
+}
+
+/// One ranked search result. `title` and `snippet` are HTML-escaped with matches wrapped in `<mark>`.
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct SearchHit {
+    pub kind: SearchKind,
+    /// Row id in the source table (plan ticket / ticket / prompt uuid, or the integer id as text).
+    pub id: String,
+    pub project_id: Option<String>,
+    pub title: String,
+    pub snippet: String,
+    /// Higher is better (negated bm25).
+    pub score: f64,
+}
+
+const MATCH_START: &str = "\u{1}";
+const MATCH_END: &str = "\u{2}";
+
+/// Turn free text into an FTS5 query: every word must match, the last one as a prefix (search as you type).
+fn fts_query(query: &str) -> Option<String> {
+    let terms: Vec<String> = query
+        .split_whitespace()
+        .map(|t| t.replace('"', ""))
+        .filter(|t| t.chars().any(char::is_alphanumeric))
+        .map(|t| format!("\"{}\"", t))
+        .collect();
+    let (last, rest) = terms.split_last()?;
+    Some(rest.iter().chain([&format!("{}*", last)]).cloned().collect::<Vec<_>>().join(" "))
+}
+
+fn highlighted_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace(MATCH_START, "<mark>")
+        .replace(MATCH_END, "</mark>")
+}
+
+/// Full-text search over the search_index, best matches first. Title matches weigh 10× body matches. With a project,
+/// hits are limited to its rows plus the global tickets, prompts and ideas linked to it (and unassigned ideas, as in
+/// get_ideas_list).
+pub fn search(
+    conn: &Connection,
+    query: &str,
+    kinds: &[SearchKind],
+    project_id: Option<&str>,
+    limit: usize,
+) -> AppResult<Vec<SearchHit>> {
+    let Some(fts) = fts_query(query) else {
+        return Ok(vec![]);
+    };
+    let mut filters = vec![
+        "search_index MATCH ?1".to_string(),
+        "(s.project_id IS NULL OR s.project_id IN (SELECT id FROM projects WHERE deleted_at IS NULL))".to_string(),
+    ];
+    if !kinds.is_empty() {
+        let kinds: Vec<String> = kinds.iter().map(|k| format!("'{}'", k.sql())).collect();
+        filters.push(format!("s.kind IN ({})", kinds.join(", ")));
+    }
+    if project_id.is_some() {
+        filters.push(
+            "(s.project_id = ?2
+                OR (s.kind = 'ticket' AND s.ref_id IN (SELECT ticket_id FROM project_tickets WHERE project_id = ?2))
+                OR (s.kind = 'prompt' AND s.ref_id IN (SELECT prompt_id FROM project_prompts WHERE project_id = ?2))
+                OR (s.kind = 'idea' AND (s.project_id IS NULL
+                    OR s.ref_id IN (SELECT idea_id FROM project_ideas WHERE project_id = ?2))))"
+                .to_string(),
+        );
+    }
+    let sql = format!(
+        "SELECT s.kind, s.ref_id, s.project_id,
+            highlight(search_index, 3, '{start}', '{end}'),
+            snippet(search_index, 4, '{start}', '{end}', '…', 16),
+            bm25(search_index, 0.0, 0.0, 0.0, 10.0, 1.0) AS rank
+        FROM search_index s
+        WHERE {}
+        ORDER BY rank
+        LIMIT {}",
+        filters.join(" AND "),
+        limit,
+        start = MATCH_START,
+        end = MATCH_END,
+    );
+    let mut stmt = conn.prepare(&sql)?;
+    let pid = project_id.map(str::trim).unwrap_or_default();
+    let args: Vec<&dyn rusqlite::ToSql> = if project_id.is_some() { vec![&fts, &pid] } else { vec![&fts] };
+    let rows = stmt.query_map(args.as_slice(), |row| {
+        let id: rusqlite::types::Value = row.get(1)?;
+        Ok((
+            row.get::<_, String>(0)?,
+            match id {
+                rusqlite::types::Value::Integer(n) => n.to_string(),
+                rusqlite::types::Value::Text(s) => s,
+                _ => String::new(),
+            },
+            row.get::<_, Option<String>>(2)?,
+            row.get::<_, Option<String>>(3)?.unwrap_or_default(),
+            row.get::<_, Option<String>>(4)?.unwrap_or_default(),
+            row.get::<_, f64>(5)?,
+        ))
+    })?;
+    let mut hits = vec![];
+    for row in rows {
+        let (kind, id, project_id, title, snippet, rank) = row?;
+        let Some(kind) = SearchKind::from_sql(&kind) else {
+            continue;
+        };
+        hits.push(SearchHit {
+            kind,
+            id,
+            project_id,
+            title: highlighted_html(&title),
+            snippet: highlighted_html(&snippet),
+            score: -rank,
+        });
+    }
+    Ok(hits)
+}
+
+/// Kind of row in the trash (the tables with soft deletion).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "snake_case")]
+#[ts(export)]
+pub enum TrashKind {
+    Project,
+    PlanTicket,
+    Ticket,
+    Feature,
+    Prompt,
+    Design,
+}
+
+impl TrashKind {
+    const ALL: [Self; 6] = [Self::Project, Self::PlanTicket, Self::Ticket, Self::Feature, Self::Prompt, Self::Design];
+
+    fn table(self) -> &'static str {
+        match self {
+            Self::Project => "projects",
+            Self::PlanTicket => "plan_tickets",
+            Self::Ticket => "tickets",
+            Self::Feature => "features",
+            Self::Prompt => "prompts",
+            Self::Design => "designs",
+        }
+    }
+
+    /// Columns for TrashItem: project id and display title.
+    fn columns(self) -> (&'static str, &'static str) {
+        match self {
+            Self::Project => ("id", "name"),
+            Self::PlanTicket => ("project_id", "'#' || number || ' ' || title"),
+            Self::Ticket | Self::Feature | Self::Prompt => ("NULL", "title"),
+            Self::Design => ("NULL", "name"),
+        }
+    }
+}
+
+/// A deleted row waiting in the trash.
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct TrashItem {
+    pub kind: TrashKind,
+    pub id: String,
+    /// The project itself for projects, the owning project for plan tickets; None for global rows.
+    pub project_id: Option<String>,
+    pub title: String,
+    pub deleted_at: String,
+}
+
+fn query_trash(
+    conn: &Connection,
+    kind: TrashKind,
+    filter: &str,
+    args: &[&dyn rusqlite::ToSql],
+) -> AppResult<Vec<TrashItem>> {
+    let (project_id, title) = kind.columns();
+    let mut stmt = conn.prepare(&format!(
+        "SELECT id, {}, {}, deleted_at FROM {} WHERE deleted_at IS NOT NULL {}",
+        project_id,
+        title,
+        kind.table(),
+        filter
+    ))?;
+    let items = stmt
+        .query_map(args, |row| {
+            Ok(TrashItem {
+                kind,
+                id: row.get(0)?,
+                project_id: row.get(1)?,
+                title: row.get(2)?,
+                deleted_at: row.get(3)?,
+            })
+        })?
+        .collect::<rusqlite::Result<Vec<_>>>()?;
+    Ok(items)
+}
+
+/// Everything in the trash, most recently deleted first. With a project: the project and its plan tickets only.
+pub fn list_trash(conn: &Connection, project_id: Option<&str>) -> AppResult<Vec<TrashItem>> {
+    let mut items = vec![];
+    for kind in TrashKind::ALL {
+        match project_id.map(str::trim) {
+            None => items.extend(query_trash(conn, kind, "", &[])?),
+            Some(pid) if kind.columns().0 != "NULL" => {
+                items.extend(query_trash(conn, kind, &format!("AND {} = ?1", kind.columns().0), &[&pid])?)
+            }
+            Some(_) => {}
+        }
+    }
+    items.sort_by(|a, b| b.deleted_at.cmp(&a.deleted_at));
+    Ok(items)
+}
+
+/// Take a row out of the trash. A plan ticket needs its project outside the trash; it goes back to the end of its
+/// column (or the workflow's initial/done column if that column is gone) and to General Development if its milestone
+/// was deleted meanwhile.
+pub fn restore_from_trash(conn: &Connection, kind: TrashKind, id: &str, actor: &Actor) -> AppResult<TrashItem> {
+    let id = id.trim();
+    let item = query_trash(conn, kind, "AND id = ?1", &[&id])?
+        .into_iter()
+        .next()
+        .ok_or_else(|| AppError::not_found(format!("No {} {} in the trash", kind.table().trim_end_matches('s'), id)))?;
+    let tx = conn.unchecked_transaction()?;
+    if kind == TrashKind::PlanTicket {
+        let project_id = item.project_id.as_deref().unwrap_or_default();
+        if get_project(&tx, project_id)?.is_none() {
+            return Err(AppError::invalid(format!("Project {} is in the trash; restore it first", project_id)));
+        }
+        let (status, done, milestone_exists): (String, bool, bool) = tx.query_row(
+            "SELECT status, done,
+                EXISTS (SELECT 1 FROM milestones m WHERE m.id = t.milestone_id AND m.project_id = t.project_id)
+             FROM plan_tickets t WHERE id = ?1",
+            params![id],
+            |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)),
+        )?;
+        let workflow = get_plan_workflow(&tx, project_id)?;
+        let (status, done) = match workflow.column(&status) {
+            Some(column) => (column.name.clone(), column.done),
+            None if done => (workflow.done_status().to_string(), true),
+            None => (workflow.initial_status().to_string(), false),
+        };
+        let milestone_id =
+            if milestone_exists { None } else { Some(general_development_milestone_id(&tx, project_id)?) };
+        tx.execute(
+            "UPDATE plan_tickets SET deleted_at = NULL, status = ?1, done = ?2, position = ?3,
+                milestone_id = COALESCE(?4, milestone_id), updated_at = ?5
+             WHERE id = ?6",
+            params![
+                &status,
+                done,
+                next_position(&tx, project_id, &status)?,
+                milestone_id,
+                chrono::Utc::now().to_rfc3339(),
+                id
+            ],
+        )?;
+        let ticket = get_plan_ticket(&tx, project_id, id)?
+            .ok_or_else(|| AppError::internal("Ticket missing after restore"))?;
+        record_plan_ticket_event(&tx, &ticket, "restored", None, None, Some(serde_json::to_value(&ticket)?), actor)?;
+    } else {
+        tx.execute(&format!("UPDATE {} SET deleted_at = NULL WHERE id = ?1", kind.table()), params![id])?;
+    }
+    tx.commit()?;
+    Ok(item)
+}
+
+/// Permanently delete rows in the trash: all kinds or one, optionally only those deleted more than `older_than_days`
+/// ago. Returns how many rows were deleted (a project counts once, with everything it owns).
+pub fn empty_trash(conn: &Connection, kind: Option<TrashKind>, older_than_days: Option<i64>) -> AppResult<usize> {
+    let cutoff = older_than_days.map(|days| (chrono::Utc::now() - chrono::Duration::days(days.max(0))).to_rfc3339());
+    let filter = "AND (?1 IS NULL OR deleted_at < ?1)";
+    let tx = conn.unchecked_transaction()?;
+    let mut removed = 0;
+    for kind in TrashKind::ALL.into_iter().filter(|k| kind.is_none_or(|only| only == *k)) {
+        if kind == TrashKind::Project {
+            for item in query_trash(&tx, kind, filter, &[&cutoff])? {
+                purge_project(&tx, &item.id)?;
+                removed += 1;
+            }
+        } else {
+            removed += tx.execute(
+                &format!("DELETE FROM {} WHERE deleted_at IS NOT NULL {}", kind.table(), filter),
+                params![cutoff],
+            )?;
+        }
+    }
+    tx.commit()?;
+    Ok(removed)
+}
+
+/// Category of a problem found by check_db_health.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "snake_case")]
+#[ts(export)]
+pub enum HealthIssueKind {
+    /// PRAGMA integrity_check reported corruption.
+    Integrity,
+    /// The schema is newer than this build or a table is missing.
+    Schema,
+    /// PRAGMA foreign_key_check reported a row pointing at a missing parent.
+    ForeignKey,
+    /// A row that belongs to a project that does not exist.
+    Orphan,
+    /// A link row (project → ticket/feature/prompt/idea/design, ticket dependency) to a missing row.
+    DanglingLink,
+    /// A plan ticket whose milestone is missing or belongs to another project.
+    MissingMilestone,
+}
+
+/// What repair_db does about an issue.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "snake_case")]
+#[ts(export)]
+pub enum HealthRepair {
+    /// Fixed in place: derived or link rows are deleted, orphan ideas become global, tickets go to General
+    /// Development.
+    Fix,
+    /// The row is moved to the quarantine table.
+    Quarantine,
+    /// Not repairable automatically (corruption, schema): restore a backup.
+    Manual,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct HealthIssue {
+    pub kind: HealthIssueKind,
+    pub table: Option<String>,
+    /// SQLite rowid of the offending row.
+    #[ts(as = "Option<f64>")]
+    pub row_id: Option<i64>,
+    pub message: String,
+    pub repair: HealthRepair,
+}
+
+/// Result of check_db_health (run on startup and by get_db_health).
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct DbHealth {
+    pub checked_at: String,
+    /// No issues found.
+    pub ok: bool,
+    #[ts(as = "f64")]
+    pub schema_version: i64,
+    #[ts(as = "f64")]
+    pub latest_version: i64,
+    pub issues: Vec<HealthIssue>,
+    /// Rows currently in the quarantine table.
+    #[ts(as = "f64")]
+    pub quarantined: i64,
+}
+
+/// Tables every migrated database has.
+const REQUIRED_TABLES: &[&str] = &[
+    "kv_store",
+    "projects",
+    "plan_tickets",
+    "milestones",
+    "ideas",
+    "tickets",
+    "features",
+    "prompts",
+    "designs",
+    "implementation_log",
+    "plan_ticket_events",
+    "search_index",
+    "quarantine",
+];
+
+/// Rows that can be deleted outright when they point nowhere: links, sync state and other data derived from the rows
+/// they reference.
+const DERIVED_TABLES: &[&str] = &[
+    "project_tickets",
+    "project_features",
+    "project_prompts",
+    "project_ideas",
+    "project_designs",
+    "plan_ticket_dependencies",
+    "plan_ticket_md_sync",
+    "plan_workflow_columns",
+    "plan_workflow_transitions",
+    "plan_kanban_state",
+    "plan_ticket_commits",
+];
+
+/// An orphan query: rows of `table` matching `filter`, named in the issue message by the SQL expression `label`.
+struct OrphanCheck {
+    kind: HealthIssueKind,
+    table: &'static str,
+    filter: String,
+    label: String,
+    message: String,
+    repair: HealthRepair,
+}
+
+const UNKNOWN_PROJECT: &str = "project_id NOT IN (SELECT id FROM projects)";
+
+fn orphan_checks() -> Vec<OrphanCheck> {
+    let check = |kind, table, filter: &str, label: &str, message: &str, repair| OrphanCheck {
+        kind,
+        table,
+        filter: filter.to_string(),
+        label: label.to_string(),
+        message: message.to_string(),
+        repair,
+    };
+    let ticket_label = "'#' || number || ' ' || title";
+    let mut checks = vec![
+        check(
+            HealthIssueKind::Orphan,
+            "plan_tickets",
+            UNKNOWN_PROJECT,
+            ticket_label,
+            "Plan ticket of unknown project",
+            HealthRepair::Quarantine,
+        ),
+        check(
+            HealthIssueKind::Orphan,
+            "milestones",
+            UNKNOWN_PROJECT,
+            "name",
+            "Milestone of unknown project",
+            HealthRepair::Quarantine,
+        ),
+        check(
+            HealthIssueKind::Orphan,
+            "implementation_log",
+            UNKNOWN_PROJECT,
+            "'#' || ticket_number || ' ' || ticket_title",
+            "Implementation log entry of unknown project",
+            HealthRepair::Quarantine,
+        ),
+        check(
+            HealthIssueKind::Orphan,
+            "ideas",
+            "project_id IS NOT NULL AND project_id NOT IN (SELECT id FROM projects)",
+            "title",
+            "Idea of unknown project",
+            HealthRepair::Fix,
+        ),
+        check(
+            HealthIssueKind::Orphan,
+            "plan_kanban_state",
+            UNKNOWN_PROJECT,
+            "project_id",
+            "Kanban state of unknown project",
+            HealthRepair::Fix,
+        ),
+        check(
+            HealthIssueKind::Orphan,
+            "plan_ticket_commits",
+            UNKNOWN_PROJECT,
+            "'#' || ticket_number || ' ' || commit_hash",
+            "Commit link of unknown project",
+            HealthRepair::Fix,
+        ),
+        check(
+            HealthIssueKind::DanglingLink,
+            "plan_ticket_dependencies",
+            "ticket_id NOT IN (SELECT id FROM plan_tickets) OR depends_on_id NOT IN (SELECT id FROM plan_tickets)",
+            "ticket_id || ' → ' || depends_on_id",
+            "Dependency on a missing plan ticket",
+            HealthRepair::Fix,
+        ),
+        check(
+            HealthIssueKind::MissingMilestone,
+            "plan_tickets",
+            "project_id IN (SELECT id FROM projects) AND milestone_id IS NOT NULL AND NOT EXISTS (
+                SELECT 1 FROM milestones m WHERE m.id = plan_tickets.milestone_id AND m.project_id = plan_tickets.project_id)",
+            ticket_label,
+            "Plan ticket's milestone is missing",
+            HealthRepair::Fix,
+        ),
+    ];
+    for (table, column, target) in PROJECT_LINK_TABLES {
+        checks.push(check(
+            HealthIssueKind::DanglingLink,
+            table,
+            &format!("{} OR {} NOT IN (SELECT id FROM {})", UNKNOWN_PROJECT, column, target),
+            &format!("project_id || ' → ' || {}", column),
+            &format!("Project link to a missing project or {}", target.trim_end_matches('s')),
+            HealthRepair::Fix,
+        ));
+    }
+    checks
+}
+
+fn table_exists(conn: &Connection, table: &str) -> AppResult<bool> {
+    let count: i64 = conn.query_row(
+        "SELECT COUNT(*) FROM sqlite_master WHERE type = 'table' AND name = ?1",
+        [table],
+        |row| row.get(0),
+    )?;
+    Ok(count > 0)
+}
+
+/// Integrity, schema, orphan and foreign key checks. Read-only; repair_db acts on the issues.
+pub fn check_db_health(conn: &Connection) -> AppResult<DbHealth> {
+    let mut issues = vec![];
+
+    let mut stmt = conn.prepare("PRAGMA integrity_check")?;
+    let messages = stmt.query_map([], |row| row.get::<_, String>(0))?.collect::<Result<Vec<_>, _>>()?;
+    for message in messages.into_iter().filter(|m| m != "ok") {
+        issues.push(HealthIssue {
+            kind: HealthIssueKind::Integrity,
+            table: None,
+            row_id: None,
+            message,
+            repair: HealthRepair::Manual,
+        });
+    }
+
+    let schema_version = schema_version(conn)?;
+    let latest_version = latest_schema_version();
+    if schema_version > latest_version {
+        issues.push(HealthIssue {
+            kind: HealthIssueKind::Schema,
+            table: None,
+            row_id: None,
+            message: format!(
+                "Schema version {} is newer than this build supports ({})",
+                schema_version, latest_version
+            ),
+            repair: HealthRepair::Manual,
+        });
+    }
+    let mut missing_tables = vec![];
+    for table in REQUIRED_TABLES {
+        if !table_exists(conn, table)? {
+            missing_tables.push(*table);
+            issues.push(HealthIssue {
+                kind: HealthIssueKind::Schema,
+                table: Some(table.to_string()),
+                row_id: None,
+                message: format!("Table {} is missing", table),
+                repair: HealthRepair::Manual,
+            });
         }
+    }
+
+    // Orphan checks need the relational schema; skip them on a database that is missing tables.
+    if missing_tables.is_empty() {
+        for check in orphan_checks() {
+            let sql = format!("SELECT rowid, {} FROM {} WHERE {}", check.label, check.table, check.filter);
+            let mut stmt = conn.prepare(&sql)?;
+            let rows = stmt
+                .query_map([], |row| Ok((row.get::<_, i64>(0)?, row.get::<_, Option<String>>(1)?)))?
+                .collect::<Result<Vec<_>, _>>()?;
+            for (row_id, label) in rows {
+                issues.push(HealthIssue {
+                    kind: check.kind,
+                    table: Some(check.table.to_string()),
+                    row_id: Some(row_id),
+                    message: format!("{}: {}", check.message, label.unwrap_or_default()),
+                    repair: check.repair,
+                });
+            }
+        }
+    }
+
+    // Foreign key violations not already reported by the orphan checks (the same row can fail both).
+    let mut stmt = conn.prepare("PRAGMA foreign_key_check")?;
+    let violations = stmt
+        .query_map([], |row| {
+            Ok((row.get::<_, String>(0)?, row.get::<_, Option<i64>>(1)?, row.get::<_, String>(2)?))
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+    for (table, row_id, parent) in violations {
+        let Some(row_id) = row_id else { continue };
+        if issues.iter().any(|i| i.table.as_deref() == Some(table.as_str()) && i.row_id == Some(row_id)) {
+            continue;
+        }
+        let repair = if DERIVED_TABLES.contains(&table.as_str()) {
+            HealthRepair::Fix
+        } else {
+            HealthRepair::Quarantine
+        };
+        issues.push(HealthIssue {
+            kind: HealthIssueKind::ForeignKey,
+            message: format!("{} row {} references a missing {} row", table, row_id, parent),
+            table: Some(table),
+            row_id: Some(row_id),
+            repair,
+        });
+    }
+
+    let quarantined = if missing_tables.contains(&"quarantine") {
+        0
     } else {
-        let mut stmt = conn
-            .prepare("SELECT id, project_id, title, description, category, source, created_at, updated_at FROM ideas ORDER BY id ASC")
-            .map_err(|e| e.to_string())?;
-        let rows = stmt.query_map([], map_row).map_err(|e| e.to_string())?;
-        for row in rows {
-            out.push(row.map_err(|e| e.to_string())?);
+        conn.query_row("SELECT COUNT(*) FROM quarantine", [], |row| row.get(0))?
+    };
+    Ok(DbHealth {
+        checked_at: chrono::Utc::now().to_rfc3339(),
+        ok: issues.is_empty(),
+        schema_version,
+        latest_version,
+        issues,
+        quarantined,
+    })
+}
+
+/// What repair_db did, and the health check that followed.
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct RepairReport {
+    /// Copy of the database taken before the repair (set by the repair_db command).
+    pub backup_path: Option<String>,
+    /// Issues fixed in place.
+    #[ts(as = "f64")]
+    pub fixed: i64,
+    /// Rows moved to the quarantine table.
+    #[ts(as = "f64")]
+    pub quarantined: i64,
+    /// Issues left for a manual fix or a restore.
+    #[ts(as = "f64")]
+    pub manual: i64,
+    pub health: DbHealth,
+}
+
+/// Move a row to the quarantine table as a JSON object of its columns, together with the rows that reference it
+/// through an ON DELETE CASCADE foreign key (which deleting it would otherwise remove unrecorded). Returns the number
+/// of rows moved.
+fn quarantine_row(conn: &Connection, table: &str, row_id: i64, reason: &str) -> AppResult<i64> {
+    let mut stmt = conn.prepare(&format!("SELECT * FROM {} WHERE rowid = ?1", table))?;
+    let names: Vec<String> = stmt.column_names().into_iter().map(String::from).collect();
+    let data = match stmt.query_row([row_id], |row| {
+            let mut object = serde_json::Map::new();
+            for (i, name) in names.iter().enumerate() {
+                let value = match row.get_ref(i)? {
+                    rusqlite::types::ValueRef::Null => serde_json::Value::Null,
+                    rusqlite::types::ValueRef::Integer(n) => n.into(),
+                    rusqlite::types::ValueRef::Real(f) => f.into(),
+                    rusqlite::types::ValueRef::Text(t) => String::from_utf8_lossy(t).into(),
+                    rusqlite::types::ValueRef::Blob(b) => String::from_utf8_lossy(b).into(),
+                };
+                object.insert(name.clone(), value);
+            }
+            Ok(serde_json::Value::Object(object))
+        }) {
+        Ok(data) => data,
+        Err(rusqlite::Error::QueryReturnedNoRows) => return Ok(0),
+        Err(e) => return Err(e.into()),
+    };
+    let mut moved = 0;
+    let child_reason = format!("{} (references quarantined {} row {})", reason, table, row_id);
+    for (child_table, child_id) in cascade_children(conn, table, row_id)? {
+        moved += quarantine_row(conn, &child_table, child_id, &child_reason)?;
+    }
+    conn.execute(
+        "INSERT INTO quarantine (source_table, row_id, reason, data, quarantined_at) VALUES (?1, ?2, ?3, ?4, ?5)",
+        params![table, row_id, reason, data.to_string(), chrono::Utc::now().to_rfc3339()],
+    )?;
+    conn.execute(&format!("DELETE FROM {} WHERE rowid = ?1", table), [row_id])?;
+    Ok(moved + 1)
+}
+
+/// Rows (table, rowid) that an ON DELETE CASCADE foreign key would delete along with row `row_id` of `table`.
+fn cascade_children(conn: &Connection, table: &str, row_id: i64) -> AppResult<Vec<(String, i64)>> {
+    let mut stmt = conn.prepare(
+        "SELECT m.name, f.id, f.\"from\", f.\"to\" FROM sqlite_master m, pragma_foreign_key_list(m.name) f
+         WHERE m.type = 'table' AND f.\"table\" = ?1 AND f.on_delete = 'CASCADE' ORDER BY m.name, f.id, f.seq",
+    )?;
+    let columns = stmt
+        .query_map([table], |row| {
+            let to: Option<String> = row.get(3)?;
+            Ok((row.get::<_, String>(0)?, row.get::<_, i64>(1)?, row.get::<_, String>(2)?, to))
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+    // One join condition per foreign key; composite keys have a row per column.
+    let mut keys: Vec<(String, i64, Vec<String>)> = vec![];
+    for (child, id, from, to) in columns {
+        let condition = format!("c.\"{}\" = p.\"{}\"", from, to.as_deref().unwrap_or("rowid"));
+        match keys.last_mut() {
+            Some((t, i, conditions)) if *t == child && *i == id => conditions.push(condition),
+            _ => keys.push((child, id, vec![condition])),
+        }
+    }
+    let mut out = vec![];
+    for (child, _, conditions) in keys {
+        let sql = format!(
+            "SELECT c.rowid FROM \"{}\" c JOIN \"{}\" p ON {} WHERE p.rowid = ?1",
+            child,
+            table,
+            conditions.join(" AND ")
+        );
+        let mut stmt = conn.prepare(&sql)?;
+        for id in stmt.query_map([row_id], |row| row.get::<_, i64>(0))? {
+            out.push((child.clone(), id?));
         }
     }
     Ok(out)
 }
+
+/// Apply one Fix: orphan ideas become global, tickets with a missing milestone move to General Development, and
+/// anything else (links, derived state) is deleted.
+fn fix_issue(conn: &Connection, issue: &HealthIssue, table: &str, row_id: i64) -> AppResult<bool> {
+    let changed = match (issue.kind, table) {
+        (HealthIssueKind::Orphan, "ideas") => {
+            conn.execute("UPDATE ideas SET project_id = NULL WHERE rowid = ?1", [row_id])?
+        }
+        (HealthIssueKind::MissingMilestone, _) => {
+            let project_id: String =
+                conn.query_row("SELECT project_id FROM plan_tickets WHERE rowid = ?1", [row_id], |row| row.get(0))?;
+            let milestone_id = general_development_milestone_id(conn, &project_id)?;
+            conn.execute(
+                "UPDATE plan_tickets SET milestone_id = ?1, updated_at = ?2 WHERE rowid = ?3",
+                params![milestone_id, chrono::Utc::now().to_rfc3339(), row_id],
+            )?
+        }
+        _ => conn.execute(&format!("DELETE FROM {} WHERE rowid = ?1", table), [row_id])?,
+    };
+    Ok(changed > 0)
+}
+
+/// Fix what can be fixed safely, quarantine the rest, then check again. Integrity problems get a REINDEX (which
+/// clears index-only corruption); anything still reported needs a restore. Take a backup first.
+pub fn repair_db(conn: &Connection) -> AppResult<RepairReport> {
+    let before = check_db_health(conn)?;
+    if before.issues.iter().any(|i| i.kind == HealthIssueKind::Integrity) {
+        conn.execute_batch("REINDEX")?;
+    }
+
+    let mut fixed = 0;
+    let mut quarantined = 0;
+    let tx = conn.unchecked_transaction()?;
+    for issue in &before.issues {
+        let (Some(table), Some(row_id)) = (issue.table.as_deref(), issue.row_id) else { continue };
+        match issue.repair {
+            HealthRepair::Fix => {
+                if fix_issue(&tx, issue, table, row_id)? {
+                    fixed += 1;
+                }
+            }
+            HealthRepair::Quarantine => {
+                quarantined += quarantine_row(&tx, table, row_id, &issue.message)?;
+            }
+            HealthRepair::Manual => {}
+        }
+    }
+    tx.commit()?;
+
+    let health = check_db_health(conn)?;
+    Ok(RepairReport {
+        backup_path: None,
+        fixed,
+        quarantined,
+        manual: health.issues.len() as i64,
+        health,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::ErrorCode;
+
+    /// A database at schema version 3 (before relational projects), for migration tests.
+    fn v3_database(dir: &Path) -> (Connection, PathBuf) {
+        let path = dir.join("app.db");
+        let conn = Connection::open(&path).unwrap();
+        m001_initial_schema(&conn).unwrap();
+        m002_implementation_log_status(&conn).unwrap();
+        m003_plan_ticket_commits(&conn).unwrap();
+        conn.execute_batch("PRAGMA user_version = 3").unwrap();
+        (conn, path)
+    }
+
+    #[test]
+    fn migrations_take_one_backup_and_prune_old_ones() {
+        let dir = tempfile::tempdir().unwrap();
+        let (conn, path) = v3_database(dir.path());
+        drop(conn);
+        let backups = dir.path().join("backups");
+        std::fs::create_dir_all(&backups).unwrap();
+        let stale = std::time::SystemTime::now() - std::time::Duration::from_secs(3600);
+        for n in 1..=4 {
+            let old = backups.join(format!("app-v{}-to-v{}-2020010{}T000000.000Z.db", n - 1, n, n));
+            std::fs::write(&old, "old").unwrap();
+            std::fs::File::options().write(true).open(&old).unwrap().set_modified(stale).unwrap();
+        }
+        std::fs::write(backups.join("scheduled-20200101T000000.000Z.zip"), "kept").unwrap();
+
+        let conn = open_db(&path).unwrap();
+        let latest = latest_schema_version();
+        let mut stmt = conn.prepare("SELECT DISTINCT backup_path FROM schema_migrations WHERE version > 3").unwrap();
+        let paths: Vec<String> = stmt.query_map([], |row| row.get(0)).unwrap().collect::<Result<_, _>>().unwrap();
+        assert_eq!(paths.len(), 1);
+        let name = Path::new(&paths[0]).file_name().unwrap().to_string_lossy().to_string();
+        assert!(name.starts_with(&format!("app-v3-to-v{}-", latest)), "{}", name);
+
+        let mut left: Vec<String> = std::fs::read_dir(&backups)
+            .unwrap()
+            .map(|e| e.unwrap().file_name().to_string_lossy().to_string())
+            .collect();
+        left.sort();
+        assert_eq!(left.len(), MIGRATION_BACKUPS_KEEP + 1, "{:?}", left);
+        assert!(left.contains(&name));
+        assert!(left.contains(&"scheduled-20200101T000000.000Z.zip".to_string()));
+        assert!(!left.contains(&"app-v0-to-v1-20200101T000000.000Z.db".to_string()));
+    }
+
+    #[test]
+    fn m004_keeps_plan_tickets_and_milestones_of_missing_projects() {
+        let dir = tempfile::tempdir().unwrap();
+        let (conn, path) = v3_database(dir.path());
+        conn.execute_batch(
+            r#"
+            INSERT INTO kv_store VALUES ('projects', '[{"id":"p1","name":"One"}]');
+            INSERT INTO plan_tickets (id, project_id, number, title, created_at, updated_at)
+                VALUES ('a', 'p1', 1, 'Kept', 't', 't'), ('b', 'gone', 1, 'Orphan', 't', 't');
+            INSERT INTO milestones (project_id, name, slug, created_at, updated_at)
+                VALUES ('gone', 'Old', 'old', 't', 't'), ('alsogone', 'Older', 'older', 't', 't');
+            INSERT INTO ideas (project_id, title, description, created_at, updated_at)
+                VALUES ('nowhere', 'Idea', 'd', 't', 't');
+            "#,
+        )
+        .unwrap();
+
+        run_migrations(&conn, &path).unwrap();
+
+        let names: Vec<(String, String)> = conn
+            .prepare("SELECT id, name FROM projects ORDER BY id")
+            .unwrap()
+            .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))
+            .unwrap()
+            .collect::<Result<_, _>>()
+            .unwrap();
+        assert_eq!(
+            names,
+            vec![
+                ("alsogone".to_string(), "Recovered project (alsogone)".to_string()),
+                ("gone".to_string(), "Recovered project (gone)".to_string()),
+                ("p1".to_string(), "One".to_string()),
+            ]
+        );
+        let count = |sql: &str| conn.query_row(sql, [], |row| row.get::<_, i64>(0)).unwrap();
+        assert_eq!(count("SELECT COUNT(*) FROM plan_tickets"), 2);
+        assert_eq!(count("SELECT COUNT(*) FROM plan_tickets WHERE project_id = 'gone' AND title = 'Orphan'"), 1);
+        assert_eq!(count("SELECT COUNT(*) FROM milestones WHERE project_id IN ('gone', 'alsogone')"), 2);
+        assert_eq!(count("SELECT COUNT(*) FROM ideas WHERE title = 'Idea' AND project_id IS NULL"), 1);
+        assert!(check_db_health(&conn).unwrap().ok);
+    }
+
+    #[test]
+    fn saves_trash_left_out_rows_atomically_and_refuse_to_trash_everything() {
+        let dir = tempfile::tempdir().unwrap();
+        let conn = open_db(&dir.path().join("app.db")).unwrap();
+        let prompt = |id: &str| crate::Prompt {
+            id: id.to_string(),
+            title: format!("Prompt {}", id),
+            content: "text".to_string(),
+            created_at: "t".to_string(),
+            updated_at: "t".to_string(),
+        };
+        let ids = |conn: &Connection| get_prompts(conn).unwrap().into_iter().map(|p| p.id).collect::<Vec<_>>();
+        save_prompts(&conn, &[prompt("1"), prompt("2"), prompt("3")], false).unwrap();
+
+        save_prompts(&conn, &[prompt("1")], false).unwrap();
+        assert_eq!(ids(&conn), ["1"]);
+        let trashed: Vec<String> = list_trash(&conn, None).unwrap().into_iter().map(|t| t.id).collect();
+        assert_eq!(trashed.len(), 2);
+        for id in ["2", "3"] {
+            restore_from_trash(&conn, TrashKind::Prompt, id, &Actor::user()).unwrap();
+        }
+        let mut restored = ids(&conn);
+        restored.sort();
+        assert_eq!(restored, ["1", "2", "3"]);
+
+        // An empty or entirely unrelated list is refused unless confirmed.
+        assert_eq!(save_prompts(&conn, &[], false).unwrap_err().code, ErrorCode::Conflict);
+        assert_eq!(save_prompts(&conn, &[prompt("9")], false).unwrap_err().code, ErrorCode::Conflict);
+        assert_eq!(ids(&conn).len(), 3);
+
+        // A failing row rolls back the whole save, trash step included.
+        conn.execute_batch(
+            "CREATE TEMP TRIGGER reject_bad BEFORE INSERT ON prompts WHEN new.id = 'bad'
+             BEGIN SELECT RAISE(ABORT, 'bad row'); END;",
+        )
+        .unwrap();
+        assert!(save_prompts(&conn, &[prompt("1"), prompt("bad")], false).is_err());
+        assert_eq!(ids(&conn).len(), 3);
+
+        save_prompts(&conn, &[], true).unwrap();
+        assert!(ids(&conn).is_empty());
+    }
+
+    /// A migrated database with project "p" and milestone 1, plus `count` tickets in it.
+    fn planner_database(dir: &Path, count: usize) -> (Connection, Vec<PlanTicket>) {
+        let conn = open_db(&dir.join("app.db")).unwrap();
+        conn.execute_batch(
+            "INSERT INTO projects (id, name) VALUES ('p', 'P');
+             INSERT INTO milestones (project_id, name, slug, created_at, updated_at) VALUES ('p', 'M', 'm', 't', 't');",
+        )
+        .unwrap();
+        let tickets = (1..=count)
+            .map(|n| {
+                let title = format!("Ticket {}", n);
+                create_plan_ticket(&conn, "p", &title, None, "P1", "General", 1, None, None, &Actor::user()).unwrap()
+            })
+            .collect();
+        (conn, tickets)
+    }
+
+    fn status_of(conn: &Connection, ticket: &PlanTicket) -> (String, bool) {
+        let t = get_plan_ticket(conn, "p", &ticket.id).unwrap().unwrap();
+        (t.status, t.done)
+    }
+
+    #[test]
+    fn kanban_state_respects_wip_limits_and_transitions() {
+        let dir = tempfile::tempdir().unwrap();
+        let (conn, tickets) = planner_database(dir.path(), 3);
+        let ids: Vec<String> = tickets.iter().map(|t| t.id.clone()).collect();
+        let mut workflow = default_workflow();
+        workflow.columns[1].wip_limit = Some(2);
+        set_plan_workflow(&conn, "p", &workflow).unwrap();
+
+        let err = set_plan_kanban_state_for_project(&conn, "p", &ids, &Actor::user()).unwrap_err();
+        assert_eq!(err.code, ErrorCode::Conflict);
+        assert!(get_plan_kanban_state_for_project(&conn, "p").unwrap().in_progress_ids.is_empty());
+
+        set_plan_kanban_state_for_project(&conn, "p", &[ids[1].clone(), ids[0].clone()], &Actor::user()).unwrap();
+        let in_progress = |conn: &Connection| get_plan_kanban_state_for_project(conn, "p").unwrap().in_progress_ids;
+        assert_eq!(in_progress(&conn), vec![ids[1].clone(), ids[0].clone()]);
+        // Swapping a ticket in stays within the limit because the one leaving moves out first.
+        set_plan_kanban_state_for_project(&conn, "p", &[ids[2].clone(), ids[0].clone()], &Actor::user()).unwrap();
+        assert_eq!(in_progress(&conn), vec![ids[2].clone(), ids[0].clone()]);
+        assert_eq!(status_of(&conn, &tickets[1]).0, "Todo");
+
+        // In progress may not go back to Todo in this workflow, so dropping a ticket from the list is refused.
+        workflow.transitions.retain(|t| !(t.from == "In progress" && t.to == "Todo"));
+        set_plan_workflow(&conn, "p", &workflow).unwrap();
+        let err = set_plan_kanban_state_for_project(&conn, "p", &[ids[0].clone()], &Actor::user()).unwrap_err();
+        assert_eq!(err.code, ErrorCode::InvalidArgument);
+        assert_eq!(status_of(&conn, &tickets[2]).0, "In progress");
+    }
+
+    #[test]
+    fn closing_by_number_uses_the_workflow_done_column() {
+        let dir = tempfile::tempdir().unwrap();
+        let (conn, tickets) = planner_database(dir.path(), 3);
+        let mut workflow = default_workflow();
+        workflow.columns[4].name = "Shipped".to_string();
+        workflow.transitions.iter_mut().filter(|t| t.to == "Done").for_each(|t| t.to = "Shipped".to_string());
+        workflow.transitions.retain(|t| t.from != "Done");
+        set_plan_workflow(&conn, "p", &workflow).unwrap();
+
+        assert!(close_plan_ticket_by_number(&conn, "p", 1, &Actor::commit("abc")).unwrap());
+        assert!(close_plan_ticket_by_number(&conn, "p", 2, &Actor::commit("abc")).unwrap());
+        assert!(!close_plan_ticket_by_number(&conn, "p", 2, &Actor::commit("abc")).unwrap());
+        assert_eq!(status_of(&conn, &tickets[0]), ("Shipped".to_string(), true));
+        let shipped = column_ticket_ids(&conn, "p", "Shipped").unwrap();
+        assert_eq!(shipped, vec![tickets[0].id.clone(), tickets[1].id.clone()]);
+    }
+
+    #[test]
+    fn repair_quarantines_orphans_with_their_cascaded_rows() {
+        let dir = tempfile::tempdir().unwrap();
+        let (conn, tickets) = planner_database(dir.path(), 1);
+        let now = chrono::Utc::now().to_rfc3339();
+        conn.execute_batch("PRAGMA foreign_keys = OFF").unwrap();
+        conn.execute(
+            "INSERT INTO plan_tickets (id, project_id, number, title, created_at, updated_at)
+             VALUES ('orphan', 'gone', 1, 'Orphan', ?1, ?1)",
+            [&now],
+        )
+        .unwrap();
+        conn.execute(
+            "INSERT INTO plan_ticket_dependencies (ticket_id, depends_on_id, created_at) VALUES (?1, 'orphan', ?2)",
+            params![tickets[0].id, now],
+        )
+        .unwrap();
+        conn.execute(
+            "INSERT INTO ideas (project_id, title, description, created_at, updated_at)
+             VALUES ('gone', 'I', '', ?1, ?1)",
+            [&now],
+        )
+        .unwrap();
+        conn.execute_batch("PRAGMA foreign_keys = ON").unwrap();
+
+        let health = check_db_health(&conn).unwrap();
+        assert_eq!(health.issues.len(), 2, "{:?}", health.issues);
+        let report = repair_db(&conn).unwrap();
+        assert_eq!((report.fixed, report.quarantined, report.manual), (1, 2, 0));
+        assert!(report.health.ok);
+        assert_eq!(report.health.quarantined, 2);
+
+        // The dependency row the ticket's delete would have cascaded to is kept in quarantine, not lost.
+        let mut stmt = conn.prepare("SELECT source_table, data FROM quarantine ORDER BY id").unwrap();
+        let rows: Vec<(String, String)> =
+            stmt.query_map([], |row| Ok((row.get(0)?, row.get(1)?))).unwrap().collect::<Result<_, _>>().unwrap();
+        assert_eq!(rows[0].0, "plan_ticket_dependencies");
+        assert!(rows[0].1.contains(&tickets[0].id));
+        assert_eq!(rows[1].0, "plan_tickets");
+        assert!(get_plan_ticket(&conn, "p", &tickets[0].id).unwrap().is_some());
+        let global: Option<String> =
+            conn.query_row("SELECT project_id FROM ideas WHERE title = 'I'", [], |row| row.get(0)).unwrap();
+        assert_eq!(global, None);
+    }
+
+    #[test]
+    fn fts_query_quotes_words_and_prefixes_the_last() {
+        assert_eq!(fts_query("login redir").as_deref(), Some("\"login\" \"redir\"*"));
+        assert_eq!(fts_query("  say \"hi\"  ").as_deref(), Some("\"say\" \"hi\"*"));
+        assert_eq!(fts_query("café - OR").as_deref(), Some("\"café\" \"OR\"*"));
+        assert_eq!(fts_query(" -- \"\" "), None);
+        assert_eq!(fts_query(""), None);
+    }
+
+    #[test]
+    fn search_skips_trashed_rows_and_moves_do_not_reindex() {
+        let dir = tempfile::tempdir().unwrap();
+        let (conn, tickets) = planner_database(dir.path(), 2);
+        let hits = |conn: &Connection| -> Vec<String> {
+            search(conn, "tick", &[SearchKind::PlanTicket], Some("p"), 10).unwrap().into_iter().map(|h| h.id).collect()
+        };
+        assert_eq!(hits(&conn).len(), 2);
+        let index_rowid = |conn: &Connection, id: &str| -> i64 {
+            conn.query_row("SELECT rowid FROM search_index WHERE ref_id = ?1", [id], |row| row.get(0)).unwrap()
+        };
+        let indexed = index_rowid(&conn, &tickets[0].id);
+
+        // A board move changes no indexed column: the index row is left alone.
+        move_plan_ticket(&conn, "p", &tickets[0].id, IN_PROGRESS_STATUS, None, &Actor::user()).unwrap();
+        assert_eq!(index_rowid(&conn, &tickets[0].id), indexed);
+
+        delete_plan_ticket(&conn, "p", &tickets[0].id, &Actor::user()).unwrap();
+        assert_eq!(hits(&conn), vec![tickets[1].id.clone()]);
+        restore_from_trash(&conn, TrashKind::PlanTicket, &tickets[0].id, &Actor::user()).unwrap();
+        assert_eq!(hits(&conn).len(), 2);
+
+        // A trashed project hides its rows too.
+        conn.execute("UPDATE projects SET deleted_at = 't' WHERE id = 'p'", []).unwrap();
+        assert!(hits(&conn).is_empty());
+    }
+
+    #[test]
+    fn dependency_cycles_are_refused_and_readiness_follows_done_blockers() {
+        let dir = tempfile::tempdir().unwrap();
+        let (conn, tickets) = planner_database(dir.path(), 4);
+        let id = |n: usize| tickets[n - 1].id.clone();
+        let depend = |t: usize, on: usize| add_plan_ticket_dependency(&conn, "p", &id(t), &id(on));
+        assert_eq!(depend(1, 1).unwrap_err().code, ErrorCode::InvalidArgument);
+
+        // #1 is blocked by #2, #2 by #3.
+        depend(1, 2).unwrap();
+        let err = depend(2, 1).unwrap_err();
+        assert_eq!(err.code, ErrorCode::Conflict);
+        assert_eq!(err.details.unwrap()["cycle"], serde_json::json!([id(2), id(1), id(2)]));
+        depend(2, 3).unwrap();
+        let err = depend(3, 1).unwrap_err();
+        assert_eq!(err.code, ErrorCode::Conflict);
+        assert_eq!(err.message, "Dependency would create a cycle: #3 → #1 → #2 → #3");
+        assert_eq!(err.details.unwrap()["cycle"], serde_json::json!([id(3), id(1), id(2), id(3)]));
+        assert_eq!(get_plan_ticket_dependencies(&conn, "p").unwrap().len(), 2);
+
+        let ready = |conn: &Connection| -> Vec<i64> {
+            get_ready_plan_tickets(conn, "p").unwrap().into_iter().map(|t| t.number).collect()
+        };
+        assert_eq!(ready(&conn), vec![3, 4]);
+        // Moving the blocker to the done column frees #2, which sorts after #4 because it has a (done) dependency.
+        move_plan_ticket(&conn, "p", &id(3), "Done", None, &Actor::user()).unwrap();
+        assert_eq!(ready(&conn), vec![4, 2]);
+        move_plan_ticket(&conn, "p", &id(2), "Done", None, &Actor::user()).unwrap();
+        assert_eq!(ready(&conn), vec![4, 1]);
+    }
+
+    #[test]
+    fn roadmap_projection_skips_shipped_milestones() {
+        let dir = tempfile::tempdir().unwrap();
+        let (conn, _) = planner_database(dir.path(), 4);
+        for (name, status) in [("Shipped", "shipped"), ("Next", "planned")] {
+            let m = create_milestone(&conn, "p", name, "", None).unwrap();
+            let patch = MilestonePatch { status: Some(status.to_string()), ..Default::default() };
+            update_milestone(&conn, "p", m.id, &patch).unwrap();
+            for n in 1..=2 {
+                let title = format!("{} {}", name, n);
+                create_plan_ticket(&conn, "p", &title, None, "P1", "General", m.id, None, None, &Actor::user())
+                    .unwrap();
+            }
+        }
+        // 14 tickets completed over the four-week window: half a ticket per day.
+        let yesterday = (chrono::Utc::now() - chrono::Duration::days(1)).to_rfc3339();
+        for n in 101..=114 {
+            conn.execute(
+                "INSERT INTO implementation_log
+                    (project_id, run_id, ticket_number, ticket_title, completed_at, created_at, status)
+                 VALUES ('p', 'r', ?1, 't', ?2, ?2, 'accepted')",
+                params![n, yesterday],
+            )
+            .unwrap();
+        }
+
+        let roadmap = get_roadmap(&conn, "p").unwrap();
+        assert_eq!(roadmap.throughput_per_day, 0.5);
+        let today = chrono::Utc::now().date_naive();
+        let in_days = |days: i64| Some((today + chrono::Duration::days(days)).format("%Y-%m-%d").to_string());
+        let projected = |name: &str| {
+            let m = roadmap.milestones.iter().find(|m| m.milestone.name == name).unwrap();
+            (m.total - m.done, m.projected_completion.clone())
+        };
+        assert_eq!(projected("M"), (4, in_days(8)));
+        assert_eq!(projected("Shipped"), (2, None));
+        // The shipped milestone's open tickets are not part of the backlog ahead of "Next".
+        assert_eq!(projected("Next"), (2, in_days(12)));
+    }
+
+    #[test]
+    fn ranking_and_score_filters_use_the_displayed_scores() {
+        let dir = tempfile::tempdir().unwrap();
+        let (conn, _) = planner_database(dir.path(), 0);
+        // (title, reach, impact, confidence, effort): zero or negative effort leaves an idea unscored.
+        let factors = [
+            ("small", 100.0, 1.0, 50.0, 2.0),
+            ("big", 1000.0, 2.0, 80.0, 4.0),
+            ("free", 500.0, 3.0, 100.0, 0.0),
+            ("negative", 500.0, 3.0, 100.0, -1.0),
+            ("medium", 400.0, 1.0, 100.0, 0.5),
+        ];
+        for (title, reach, impact, confidence, effort) in factors {
+            let idea = create_idea(&conn, Some("p"), title, "", "", "").unwrap();
+            conn.execute(
+                "UPDATE ideas SET reach = ?1, impact = ?2, confidence = ?3, effort = ?4 WHERE id = ?5",
+                params![reach, impact, confidence, effort, idea.id],
+            )
+            .unwrap();
+        }
+        create_idea(&conn, Some("p"), "unscored", "", "", "").unwrap();
+
+        let ranked = rank_ideas(&conn, Some("p"), IdeaScoreMethod::Rice, 10).unwrap();
+        let titles: Vec<&str> = ranked.iter().map(|r| r.idea.title.as_str()).collect();
+        assert_eq!(titles, ["medium", "big", "small"]);
+        for r in &ranked {
+            assert_eq!(Some(r.score), r.idea.rice_score);
+        }
+        assert!(ranked.windows(2).all(|w| w[0].score >= w[1].score));
+
+        let query = IdeaListQuery {
+            min_score: Some(25.0),
+            sort: Some(IdeaSort::Score),
+            ..Default::default()
+        };
+        let ideas = get_ideas_list(&conn, Some("p"), &query).unwrap();
+        let titles: Vec<&str> = ideas.iter().map(|i| i.title.as_str()).collect();
+        assert_eq!(titles, ["small", "big", "medium"]);
+        let query = IdeaListQuery { sort: Some(IdeaSort::Score), descending: Some(true), ..Default::default() };
+        let ideas = get_ideas_list(&conn, Some("p"), &query).unwrap();
+        let titles: Vec<&str> = ideas.iter().map(|i| i.title.as_str()).collect();
+        assert_eq!(titles, ["medium", "big", "small", "free", "negative", "unscored"]);
+    }
+
+    #[test]
+    fn failed_idea_conversion_rolls_back_everything() {
+        let dir = tempfile::tempdir().unwrap();
+        let (conn, _) = planner_database(dir.path(), 0);
+        let idea = create_idea(&conn, None, "Dark mode", "Theme support", "", "").unwrap();
+        conn.execute_batch(
+            "CREATE TEMP TRIGGER reject_ticket BEFORE INSERT ON plan_tickets WHEN new.title = 'Boom'
+             BEGIN SELECT RAISE(ABORT, 'ticket rejected'); END;",
+        )
+        .unwrap();
+        let titles = ["Add tokens".to_string(), "Boom".to_string()];
+        assert!(convert_idea_to_plan(&conn, idea.id, "p", "", &titles, &Actor::user()).is_err());
+
+        let count = |sql: &str| -> i64 { conn.query_row(sql, [], |row| row.get(0)).unwrap() };
+        assert_eq!(count("SELECT COUNT(*) FROM milestones WHERE name = 'Dark mode'"), 0);
+        assert_eq!(count("SELECT COUNT(*) FROM plan_tickets"), 0);
+        assert_eq!(count("SELECT COUNT(*) FROM plan_ticket_events"), 0);
+        let unchanged = get_idea(&conn, idea.id).unwrap();
+        assert_eq!((unchanged.status.as_str(), unchanged.project_id), ("new", None));
+
+        conn.execute_batch("DROP TRIGGER reject_ticket").unwrap();
+        let converted = convert_idea_to_plan(&conn, idea.id, "p", "", &titles, &Actor::user()).unwrap();
+        assert_eq!(converted.idea.status, "converted");
+        assert_eq!(converted.milestone.name, "Dark mode");
+        assert_eq!(converted.tickets.len(), 2);
+        assert!(converted.tickets.iter().all(|t| t.idea_id == Some(idea.id)));
+    }
+
+    #[test]
+    fn markdown_checkboxes_move_tickets_through_the_workflow() {
+        let dir = tempfile::tempdir().unwrap();
+        let (conn, tickets) = planner_database(dir.path(), 3);
+        let mut workflow = default_workflow();
+        workflow.transitions.retain(|t| !(t.from == "In progress" && t.to == "Done"));
+        set_plan_workflow(&conn, "p", &workflow).unwrap();
+        update_plan_ticket(&conn, "p", &tickets[1].id, false, IN_PROGRESS_STATUS, &Actor::user()).unwrap();
+        let md = |ticket: &PlanTicket, title: &str| crate::planner_md::MdTicket {
+            title: title.to_string(),
+            done: true,
+            ..crate::planner_md::MdTicket::from(ticket)
+        };
+        let sync = Actor::markdown_sync();
+
+        upsert_plan_ticket_from_markdown(&conn, "p", &md(&tickets[0], "Checked"), 1, &workflow, &sync).unwrap();
+        assert_eq!(status_of(&conn, &tickets[0]), ("Done".to_string(), true));
+        assert_eq!(column_ticket_ids(&conn, "p", "Done").unwrap(), vec![tickets[0].id.clone()]);
+
+        // In progress -> Done is not allowed here, so the checkbox is refused and nothing else is applied either.
+        let err =
+            upsert_plan_ticket_from_markdown(&conn, "p", &md(&tickets[1], "Renamed"), 1, &workflow, &sync).unwrap_err();
+        assert_eq!(err.code, ErrorCode::InvalidArgument);
+        let unchanged = get_plan_ticket(&conn, "p", &tickets[1].id).unwrap().unwrap();
+        assert_eq!((unchanged.status.as_str(), unchanged.done), (IN_PROGRESS_STATUS, false));
+        assert_eq!(unchanged.title, "Ticket 2");
+
+        // Unchecking goes back to the initial column, which is subject to its WIP limit (#3 fills it).
+        workflow.columns[0].wip_limit = Some(1);
+        set_plan_workflow(&conn, "p", &workflow).unwrap();
+        let unchecked = crate::planner_md::MdTicket { done: false, ..md(&tickets[0], "Checked") };
+        let err = upsert_plan_ticket_from_markdown(&conn, "p", &unchecked, 1, &workflow, &sync).unwrap_err();
+        assert_eq!(err.code, ErrorCode::Conflict);
+        assert_eq!(status_of(&conn, &tickets[0]), ("Done".to_string(), true));
+    }
+
+    #[test]
+    fn blocked_tickets_can_be_marked_done_in_the_default_workflow() {
+        let dir = tempfile::tempdir().unwrap();
+        let (conn, tickets) = planner_database(dir.path(), 1);
+        update_plan_ticket(&conn, "p", &tickets[0].id, false, "Blocked", &Actor::user()).unwrap();
+        update_plan_ticket(&conn, "p", &tickets[0].id, true, "Done", &Actor::user()).unwrap();
+        assert_eq!(status_of(&conn, &tickets[0]), ("Done".to_string(), true));
+    }
+
+    #[test]
+    fn milestone_progress_counts_completion_time_not_later_edits() {
+        let dir = tempfile::tempdir().unwrap();
+        let (conn, tickets) = planner_database(dir.path(), 4);
+        for t in &tickets[..3] {
+            update_plan_ticket(&conn, "p", &t.id, true, "Done", &Actor::user()).unwrap();
+        }
+        let days_ago = |days: i64| (chrono::Utc::now() - chrono::Duration::days(days)).to_rfc3339();
+        // #1 was closed 40 days ago, #2 12 days ago; #3 just now.
+        for (ticket, days) in [(&tickets[0], 40), (&tickets[1], 12)] {
+            conn.execute(
+                "UPDATE plan_ticket_events SET created_at = ?1 WHERE ticket_id = ?2",
+                params![days_ago(days), ticket.id],
+            )
+            .unwrap();
+        }
+        // #4 was marked done before ticket events existed; its implementation log entry is 5 days old.
+        conn.execute("UPDATE plan_tickets SET done = 1, status = 'Done' WHERE id = ?1", params![tickets[3].id]).unwrap();
+        conn.execute(
+            "INSERT INTO implementation_log (project_id, run_id, ticket_number, ticket_title, completed_at, created_at, status)
+             VALUES ('p', 'r', 4, 'Ticket 4', ?1, ?1, 'accepted')",
+            params![days_ago(5)],
+        )
+        .unwrap();
+        let counts = |conn: &Connection| {
+            let progress = &get_milestone_progress(conn, "p").unwrap()[0];
+            (progress.done, progress.done_last_7_days, progress.done_last_30_days)
+        };
+        assert_eq!(counts(&conn), (4, 2, 3));
+
+        // Editing old done tickets, or reordering them within Done, does not make them recently completed.
+        let patch = PlanTicketPatch { title: Some("Renamed".to_string()), ..Default::default() };
+        patch_plan_ticket(&conn, "p", &tickets[0].id, &patch, &Actor::user()).unwrap();
+        move_plan_ticket(&conn, "p", &tickets[1].id, "Done", Some(0), &Actor::user()).unwrap();
+        assert_eq!(counts(&conn), (4, 2, 3));
+    }
+}
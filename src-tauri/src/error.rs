@@ -0,0 +1,169 @@
+//! Error type returned by every command. Serialises to `{ code, message, details }` so the frontend can branch on
+//! `code` instead of matching English messages.
+
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use ts_rs::TS;
+
+/// Stable, machine-readable error category.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "snake_case")]
+#[ts(export)]
+pub enum ErrorCode {
+    NotFound,
+    InvalidArgument,
+    /// The path is not inside a git work tree.
+    NotAGitRepo,
+    /// A path escapes the directory it must stay under (project root, repository).
+    PathOutsideRoot,
+    ProcessSpawnFailed,
+    /// A git command ran and failed; `message` is its stderr.
+    Git,
+    /// The operation conflicts with the current state (duplicate id, dependents still present, already running).
+    Conflict,
+    Db,
+    /// The database is not open: it could not be opened or migrated, or it is closed for a restore.
+    DbUnavailable,
+    Io,
+    Serialization,
+    Internal,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct AppError {
+    pub code: ErrorCode,
+    pub message: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[ts(optional)]
+    pub details: Option<serde_json::Value>,
+}
+
+pub type AppResult<T> = Result<T, AppError>;
+
+impl AppError {
+    pub fn new(code: ErrorCode, message: impl Into<String>) -> Self {
+        Self {
+            code,
+            message: message.into(),
+            details: None,
+        }
+    }
+
+    pub fn with_details(mut self, details: serde_json::Value) -> Self {
+        self.details = Some(details);
+        self
+    }
+
+    pub fn not_found(message: impl Into<String>) -> Self {
+        Self::new(ErrorCode::NotFound, message)
+    }
+
+    pub fn invalid(message: impl Into<String>) -> Self {
+        Self::new(ErrorCode::InvalidArgument, message)
+    }
+
+    pub fn not_a_git_repo(message: impl Into<String>) -> Self {
+        Self::new(ErrorCode::NotAGitRepo, message)
+    }
+
+    pub fn path_outside_root(message: impl Into<String>) -> Self {
+        Self::new(ErrorCode::PathOutsideRoot, message)
+    }
+
+    pub fn spawn_failed(message: impl Into<String>) -> Self {
+        Self::new(ErrorCode::ProcessSpawnFailed, message)
+    }
+
+    pub fn git(message: impl Into<String>) -> Self {
+        Self::new(ErrorCode::Git, message)
+    }
+
+    pub fn conflict(message: impl Into<String>) -> Self {
+        Self::new(ErrorCode::Conflict, message)
+    }
+
+    pub fn db_unavailable(message: impl Into<String>) -> Self {
+        Self::new(ErrorCode::DbUnavailable, message)
+    }
+
+    pub fn internal(message: impl Into<String>) -> Self {
+        Self::new(ErrorCode::Internal, message)
+    }
+}
+
+impl fmt::Display for AppError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+impl std::error::Error for AppError {}
+
+impl From<rusqlite::Error> for AppError {
+    fn from(e: rusqlite::Error) -> Self {
+        match e {
+            rusqlite::Error::QueryReturnedNoRows => Self::not_found("Not found"),
+            e => Self::new(ErrorCode::Db, e.to_string()),
+        }
+    }
+}
+
+impl From<std::io::Error> for AppError {
+    fn from(e: std::io::Error) -> Self {
+        let code = match e.kind() {
+            std::io::ErrorKind::NotFound => ErrorCode::NotFound,
+            _ => ErrorCode::Io,
+        };
+        Self::new(code, e.to_string()).with_details(serde_json::json!({ "kind": format!("{:?}", e.kind()) }))
+    }
+}
+
+impl From<serde_json::Error> for AppError {
+    fn from(e: serde_json::Error) -> Self {
+        Self::new(ErrorCode::Serialization, e.to_string())
+    }
+}
+
+impl From<zip::result::ZipError> for AppError {
+    fn from(e: zip::result::ZipError) -> Self {
+        Self::new(ErrorCode::Io, e.to_string())
+    }
+}
+
+impl From<notify::Error> for AppError {
+    fn from(e: notify::Error) -> Self {
+        Self::new(ErrorCode::Io, e.to_string())
+    }
+}
+
+impl From<tauri::Error> for AppError {
+    fn from(e: tauri::Error) -> Self {
+        Self::internal(e.to_string())
+    }
+}
+
+impl<T> From<std::sync::PoisonError<T>> for AppError {
+    fn from(e: std::sync::PoisonError<T>) -> Self {
+        Self::internal(e.to_string())
+    }
+}
+
+impl From<std::path::StripPrefixError> for AppError {
+    fn from(e: std::path::StripPrefixError) -> Self {
+        Self::path_outside_root(e.to_string())
+    }
+}
+
+/// Messages from helpers and callbacks that still produce plain strings.
+impl From<String> for AppError {
+    fn from(message: String) -> Self {
+        Self::internal(message)
+    }
+}
+
+impl From<&str> for AppError {
+    fn from(message: &str) -> Self {
+        Self::internal(message)
+    }
+}
@@ -1,8 +1,13 @@
 //! Tauri app entry and commands: run scripts, SQLite, project/ticket CRUD. Invoked from the frontend via invoke().
 
+mod backup;
 mod db;
+mod error;
+mod planner_md;
+mod roadmap;
 
 use base64::Engine;
+use error::{AppError, AppResult};
 use chrono::prelude::*;
 use serde::{Deserialize, Serialize};
 use std::collections::{HashMap, HashSet};
@@ -14,6 +19,7 @@ use std::thread;
 use std::time::{SystemTime, UNIX_EPOCH};
 use tauri::path::BaseDirectory;
 use tauri::{AppHandle, Emitter, Manager, State};
+use ts_rs::TS;
 use url::Url;
 
 #[cfg(unix)]
@@ -26,28 +32,34 @@ struct RunEntry {
 
 struct RunningState {
     runs: Arc<Mutex<HashMap<String, RunEntry>>>,
+    /// Private askpass directory of each running git network run, for answer_git_prompt.
+    askpass_dirs: Arc<Mutex<HashMap<String, PathBuf>>>,
 }
 
 impl Default for RunningState {
     fn default() -> Self {
         Self {
             runs: Arc::new(Mutex::new(HashMap::new())),
+            askpass_dirs: Arc::new(Mutex::new(HashMap::new())),
         }
     }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export)]
 pub struct RunIdResponse {
     pub run_id: String,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export)]
 pub struct ScriptLogPayload {
     pub run_id: String,
     pub line: String,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export)]
 pub struct ScriptExitedPayload {
     pub run_id: String,
     pub label: String,
@@ -56,7 +68,8 @@ pub struct ScriptExitedPayload {
     pub exit_code: Option<i32>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export)]
 pub struct RunningRunInfo {
     pub run_id: String,
     pub label: String,
@@ -219,17 +232,17 @@ fn get_seed_designs() -> Vec<Design> {
     ]
 }
 
-fn seed_initial_data(conn: &rusqlite::Connection) -> Result<(), String> {
+fn seed_initial_data(conn: &rusqlite::Connection) -> AppResult<()> {
     // Seed Prompts
     let prompts_count: i64 = conn.query_row(
         "SELECT COUNT(*) FROM prompts",
         [],
         |row| row.get(0),
-    ).map_err(|e| e.to_string())?;
+    )?;
 
     if prompts_count == 0 {
         println!("Seeding initial prompts...");
-        db::save_prompts(conn, &get_seed_prompts())?;
+        db::save_prompts(conn, &get_seed_prompts(), false)?;
     }
 
     // Seed Designs
@@ -237,17 +250,18 @@ fn seed_initial_data(conn: &rusqlite::Connection) -> Result<(), String> {
         "SELECT COUNT(*) FROM designs",
         [],
         |row| row.get(0),
-    ).map_err(|e| e.to_string())?;
+    )?;
 
     if designs_count == 0 {
         println!("Seeding initial designs...");
-        db::save_designs(conn, &get_seed_designs())?;
+        db::save_designs(conn, &get_seed_designs(), false)?;
     }
 
     Ok(())
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export)]
 pub struct Ticket {
     pub id: String,
     pub title: String,
@@ -265,7 +279,8 @@ pub struct Ticket {
 }
 
 /// A milestone that has to be done in an application. One feature has many tickets and must have at least one.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export)]
 pub struct Feature {
     pub id: String,
     pub title: String, // label for run / display
@@ -280,7 +295,8 @@ pub struct Feature {
     pub updated_at: String,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export)]
 pub struct Prompt {
     pub id: String,
     pub title: String,
@@ -289,7 +305,8 @@ pub struct Prompt {
     pub updated_at: String,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export)]
 pub struct Design {
     pub id: String,
     pub name: String,
@@ -299,8 +316,9 @@ pub struct Design {
     pub updated_at: String,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
 #[serde(rename_all = "camelCase")]
+#[ts(export)]
 pub struct RunScriptArgs {
     #[serde(default)]
     pub prompt_ids: Vec<u32>,
@@ -312,7 +330,8 @@ pub struct RunScriptArgs {
     pub run_label: Option<String>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export)]
 pub struct TimingParams {
     pub sleep_after_open_project: f64,
     pub sleep_after_window_focus: f64,
@@ -327,7 +346,8 @@ pub struct TimingParams {
 }
 
 /// Git repository info for the project details Git tab.
-#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, Serialize, Deserialize, Default, TS)]
+#[ts(export)]
 pub struct GitInfo {
     pub current_branch: String,
     pub branches: Vec<String>,
@@ -338,39 +358,145 @@ pub struct GitInfo {
     pub config_preview: String,
 }
 
-fn run_git(project_path: &PathBuf, args: &[&str]) -> Result<String, String> {
+fn run_git(project_path: &PathBuf, args: &[&str]) -> AppResult<String> {
     let out = Command::new("git")
         .args(args)
         .current_dir(project_path)
-        .output()
-        .map_err(|e| e.to_string())?;
+        .output()?;
     let stdout = String::from_utf8_lossy(&out.stdout).trim().to_string();
     let stderr = String::from_utf8_lossy(&out.stderr).trim().to_string();
     if out.status.success() {
         Ok(stdout)
     } else {
-        Err(if stderr.is_empty() { stdout } else { stderr })
+        Err(AppError::git(if stderr.is_empty() { stdout } else { stderr }))
+    }
+}
+
+/// run_git without trimming or UTF-8 conversion, for file contents and `-z` output.
+fn run_git_raw(project_path: &Path, args: &[&str]) -> AppResult<Vec<u8>> {
+    let out = Command::new("git").args(args).current_dir(project_path).output()?;
+    if out.status.success() {
+        Ok(out.stdout)
+    } else {
+        Err(AppError::git(String::from_utf8_lossy(&out.stderr).trim()))
+    }
+}
+
+/// Where a repository lives. `git_dir` is per-worktree (HEAD, MERGE_HEAD); `common_dir` holds the shared config and refs.
+/// For a plain clone both are `<toplevel>/.git`; for linked worktrees and submodules `.git` is a file pointing elsewhere.
+#[derive(Debug, Clone)]
+struct GitRepoLocation {
+    toplevel: PathBuf,
+    git_dir: PathBuf,
+    common_dir: PathBuf,
+}
+
+/// Ask git (not the filesystem) whether `path` is inside a work tree. None when it is not.
+fn discover_git_repo(path: &Path) -> Option<GitRepoLocation> {
+    let out = Command::new("git")
+        .args(["rev-parse", "--git-dir", "--git-common-dir", "--show-toplevel"])
+        .current_dir(path)
+        .output()
+        .ok()?;
+    if !out.status.success() {
+        return None;
+    }
+    let stdout = String::from_utf8_lossy(&out.stdout).to_string();
+    let mut lines = stdout.lines().map(str::trim);
+    let absolute = |p: &str| {
+        let p = PathBuf::from(p);
+        if p.is_absolute() { p } else { path.join(p) }
+    };
+    let git_dir = absolute(lines.next()?);
+    let common_dir = absolute(lines.next()?);
+    // --show-toplevel prints nothing inside a bare repo or a git dir.
+    let toplevel = PathBuf::from(lines.next().filter(|l| !l.is_empty())?);
+    Some(GitRepoLocation {
+        toplevel,
+        git_dir,
+        common_dir,
+    })
+}
+
+/// Directories that are never searched for nested repositories.
+const NESTED_REPO_SKIP_DIRS: &[&str] = &["node_modules", "target", "dist", "build", ".next", "vendor"];
+
+/// Work trees below `dir` (up to `max_depth` levels), for project folders that hold one or more repositories.
+fn find_nested_git_repos(dir: &Path, max_depth: usize) -> Vec<PathBuf> {
+    let mut found = vec![];
+    let entries = match std::fs::read_dir(dir) {
+        Ok(e) => e,
+        Err(_) => return found,
+    };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        let name = entry.file_name().to_string_lossy().to_string();
+        if !path.is_dir() || name.starts_with('.') || NESTED_REPO_SKIP_DIRS.contains(&name.as_str()) {
+            continue;
+        }
+        // `.git` may be a directory (clone) or a file (worktree / submodule).
+        if path.join(".git").exists() {
+            found.push(path);
+        } else if max_depth > 1 {
+            found.extend(find_nested_git_repos(&path, max_depth - 1));
+        }
+    }
+    found.sort();
+    found
+}
+
+/// Resolve the repository for a project path: the path itself (or an enclosing repo), else a single nested repository.
+fn resolve_git_repo(path_buf: &Path) -> AppResult<(PathBuf, GitRepoLocation)> {
+    if let Some(loc) = discover_git_repo(path_buf) {
+        return Ok((path_buf.to_path_buf(), loc));
+    }
+    let nested = find_nested_git_repos(path_buf, 2);
+    match nested.len() {
+        0 => Err(AppError::not_a_git_repo("Not a git repository")),
+        1 => {
+            let repo = nested.into_iter().next().unwrap_or_default();
+            let loc = discover_git_repo(&repo).ok_or_else(|| AppError::not_a_git_repo("Not a git repository"))?;
+            Ok((repo, loc))
+        }
+        n => Err(AppError::invalid(format!(
+            "Project folder contains {} git repositories; choose one: {}",
+            n,
+            nested.iter().map(|p| p.to_string_lossy().to_string()).collect::<Vec<_>>().join(", ")
+        ))),
+    }
+}
+
+/// Git repositories inside (or enclosing) a project folder, for projects that group several repos.
+#[tauri::command]
+fn list_git_repos(project_path: String) -> AppResult<Vec<String>> {
+    let path_buf = PathBuf::from(project_path.trim());
+    if !path_buf.is_dir() {
+        return Err(AppError::not_found("Project path does not exist or is not a directory"));
     }
+    if let Some(loc) = discover_git_repo(&path_buf) {
+        return Ok(vec![loc.toplevel.to_string_lossy().to_string()]);
+    }
+    Ok(find_nested_git_repos(&path_buf, 3)
+        .into_iter()
+        .map(|p| p.to_string_lossy().to_string())
+        .collect())
 }
 
 #[tauri::command]
-fn get_git_info(project_path: String) -> Result<GitInfo, String> {
+fn get_git_info(project_path: String) -> AppResult<GitInfo> {
     let path_buf = PathBuf::from(project_path.trim());
     if path_buf.as_os_str().is_empty() {
-        return Err("Project path is empty".to_string());
+        return Err(AppError::invalid("Project path is empty"));
     }
     if !path_buf.exists() || !path_buf.is_dir() {
-        return Err("Project path does not exist or is not a directory".to_string());
-    }
-    let git_dir = path_buf.join(".git");
-    if !git_dir.exists() {
-        return Err("Not a git repository (no .git)".to_string());
+        return Err(AppError::not_found("Project path does not exist or is not a directory"));
     }
+    let (path_buf, loc) = resolve_git_repo(&path_buf)?;
 
     let mut info = GitInfo::default();
 
-    // HEAD ref (e.g. ref: refs/heads/main)
-    let head_path = git_dir.join("HEAD");
+    // HEAD ref (e.g. ref: refs/heads/main); per worktree, so read from git_dir.
+    let head_path = loc.git_dir.join("HEAD");
     if head_path.is_file() {
         info.head_ref = std::fs::read_to_string(&head_path).unwrap_or_default().trim().to_string();
         if info.head_ref.starts_with("ref: ") {
@@ -398,8 +524,8 @@ fn get_git_info(project_path: String) -> Result<GitInfo, String> {
         info.last_commits = s.lines().map(|l| l.to_string()).filter(|l| !l.is_empty()).collect();
     }
 
-    // .git/config preview (first 4KB, sanitized)
-    let config_path = git_dir.join("config");
+    // config preview (first 4KB, sanitized); shared by worktrees, so read from common_dir.
+    let config_path = loc.common_dir.join("config");
     if config_path.is_file() {
         if let Ok(content) = std::fs::read_to_string(&config_path) {
             let max_len = 4096;
@@ -415,33 +541,33 @@ fn get_git_info(project_path: String) -> Result<GitInfo, String> {
 }
 
 #[tauri::command]
-fn get_git_head(project_path: String) -> Result<String, String> {
+fn get_git_head(project_path: String) -> AppResult<String> {
     let path_buf = PathBuf::from(project_path.trim());
     if path_buf.as_os_str().is_empty() || !path_buf.exists() || !path_buf.is_dir() {
         return Ok(String::new());
     }
-    let git_dir = path_buf.join(".git");
-    if !git_dir.exists() {
+    let Ok((path_buf, _)) = resolve_git_repo(&path_buf) else {
         return Ok(String::new());
-    }
+    };
     Ok(run_git(&path_buf, &["rev-parse", "HEAD"]).unwrap_or_default())
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export)]
 pub struct GitDiffNameStatusEntry {
     pub path: String,
     pub status: String,
 }
 
 #[tauri::command]
-fn get_git_diff_name_status(project_path: String, from_ref: String) -> Result<Vec<GitDiffNameStatusEntry>, String> {
+fn get_git_diff_name_status(project_path: String, from_ref: String) -> AppResult<Vec<GitDiffNameStatusEntry>> {
     let path_buf = PathBuf::from(project_path.trim());
     if !path_buf.exists() || !path_buf.is_dir() {
-        return Err("Project path does not exist".to_string());
+        return Err(AppError::not_found("Project path does not exist"));
     }
-    if !path_buf.join(".git").exists() {
+    let Ok((path_buf, _)) = resolve_git_repo(&path_buf) else {
         return Ok(Vec::new());
-    }
+    };
     let ref_arg = if from_ref.trim().is_empty() { "HEAD" } else { from_ref.trim() };
     let out = run_git(&path_buf, &["diff", "--name-status", ref_arg, "--", "."]);
     let stdout = match out {
@@ -464,62 +590,72 @@ fn get_git_diff_name_status(project_path: String, from_ref: String) -> Result<Ve
     Ok(entries)
 }
 
-#[derive(serde::Serialize)]
+#[derive(serde::Serialize, TS)]
+#[ts(export)]
 pub struct ImplementationLogEntry {
+    #[ts(as = "f64")]
     pub id: i64,
     pub project_id: String,
     pub run_id: String,
+    #[ts(as = "f64")]
     pub ticket_number: i64,
     pub ticket_title: String,
+    #[ts(as = "Option<f64>")]
     pub milestone_id: Option<i64>,
+    #[ts(as = "Option<f64>")]
     pub idea_id: Option<i64>,
     pub completed_at: String,
     pub files_changed: String,
     pub summary: String,
     pub created_at: String,
     pub status: String,
+    /// Commit that referenced this ticket, recorded by scan_commit_ticket_refs.
+    pub commit_hash: Option<String>,
 }
 
-#[tauri::command]
-fn get_implementation_log_entries(ProjectIdArg { project_id }: ProjectIdArg) -> Result<Vec<ImplementationLogEntry>, String> {
-    with_db(|conn| {
-        let mut stmt = conn.prepare(
-            "SELECT id, project_id, run_id, ticket_number, ticket_title, milestone_id, idea_id, completed_at, files_changed, summary, created_at, status FROM implementation_log WHERE project_id = ?1 ORDER BY completed_at DESC, id DESC",
-        )
-        .map_err(|e| e.to_string())?;
-        let rows = stmt
-            .query_map(rusqlite::params![project_id.trim()], |row| {
-                Ok(ImplementationLogEntry {
-                    id: row.get(0)?,
-                    project_id: row.get(1)?,
-                    run_id: row.get(2)?,
-                    ticket_number: row.get(3)?,
-                    ticket_title: row.get(4)?,
-                    milestone_id: row.get(5)?,
-                    idea_id: row.get(6)?,
-                    completed_at: row.get(7)?,
-                    files_changed: row.get(8)?,
-                    summary: row.get(9)?,
-                    created_at: row.get(10)?,
-                    status: row.get::<_, String>(11).unwrap_or_else(|_| "pending".to_string()),
-                })
+fn query_implementation_log(conn: &rusqlite::Connection, project_id: &str) -> AppResult<Vec<ImplementationLogEntry>> {
+    let mut stmt = conn.prepare(
+        "SELECT id, project_id, run_id, ticket_number, ticket_title, milestone_id, idea_id, completed_at, files_changed, summary, created_at, status, commit_hash FROM implementation_log WHERE project_id = ?1 ORDER BY completed_at DESC, id DESC",
+    )?;
+    let rows = stmt
+        .query_map(rusqlite::params![project_id.trim()], |row| {
+            Ok(ImplementationLogEntry {
+                id: row.get(0)?,
+                project_id: row.get(1)?,
+                run_id: row.get(2)?,
+                ticket_number: row.get(3)?,
+                ticket_title: row.get(4)?,
+                milestone_id: row.get(5)?,
+                idea_id: row.get(6)?,
+                completed_at: row.get(7)?,
+                files_changed: row.get(8)?,
+                summary: row.get(9)?,
+                created_at: row.get(10)?,
+                status: row.get::<_, String>(11).unwrap_or_else(|_| "pending".to_string()),
+                commit_hash: row.get(12)?,
             })
-            .map_err(|e| e.to_string())?;
-        let entries: Vec<ImplementationLogEntry> = rows
-            .filter_map(|r| r.ok())
-            .collect();
-        Ok(entries)
-    })
+        })?;
+    let entries: Vec<ImplementationLogEntry> = rows
+        .filter_map(|r| r.ok())
+        .collect();
+    Ok(entries)
+}
+
+#[tauri::command]
+fn get_implementation_log_entries(ProjectIdArg { project_id }: ProjectIdArg) -> AppResult<Vec<ImplementationLogEntry>> {
+    with_db(|conn| query_implementation_log(conn, &project_id))
 }
 
 /// Args for project-scoped commands; accept camelCase projectId from frontend.
-#[derive(serde::Deserialize)]
+#[derive(serde::Deserialize, TS)]
+#[ts(export)]
 struct ProjectIdArg {
     #[serde(alias = "projectId")]
     project_id: String,
 }
 
-#[derive(serde::Deserialize)]
+#[derive(serde::Deserialize, TS)]
+#[ts(export)]
 struct ProjectIdArgOptional {
     #[serde(alias = "projectId", default)]
     project_id: Option<String>,
@@ -527,24 +663,25 @@ struct ProjectIdArgOptional {
 
 /// Project-scoped tickets (plan_tickets). Used when isTauri to avoid fetch to /api which triggers URL parse error.
 #[tauri::command]
-fn get_project_tickets(ProjectIdArg { project_id }: ProjectIdArg) -> Result<Vec<serde_json::Value>, String> {
+fn get_project_tickets(ProjectIdArg { project_id }: ProjectIdArg) -> AppResult<Vec<db::PlanTicket>> {
     with_db(|conn| db::get_plan_tickets_for_project(conn, &project_id))
 }
 
 /// Project kanban state (inProgressIds). Used when isTauri to avoid fetch to /api.
 #[tauri::command]
-fn get_project_kanban_state(ProjectIdArg { project_id }: ProjectIdArg) -> Result<serde_json::Value, String> {
+fn get_project_kanban_state(ProjectIdArg { project_id }: ProjectIdArg) -> AppResult<db::KanbanState> {
     with_db(|conn| db::get_plan_kanban_state_for_project(conn, &project_id))
 }
 
 /// Project milestones. Used when isTauri to avoid fetch to /api.
 #[tauri::command]
-fn get_project_milestones(ProjectIdArg { project_id }: ProjectIdArg) -> Result<Vec<serde_json::Value>, String> {
+fn get_project_milestones(ProjectIdArg { project_id }: ProjectIdArg) -> AppResult<Vec<db::Milestone>> {
     with_db(|conn| db::get_milestones_for_project(conn, &project_id))
 }
 
 /// Args for create_plan_ticket; accept camelCase from frontend. In built app, IPC expects payload key `args`.
-#[derive(serde::Deserialize)]
+#[derive(serde::Deserialize, TS)]
+#[ts(export)]
 struct CreatePlanTicketArgs {
     #[serde(alias = "projectId")]
     project_id: String,
@@ -554,15 +691,19 @@ struct CreatePlanTicketArgs {
     #[serde(alias = "featureName")]
     feature_name: Option<String>,
     #[serde(alias = "milestoneId")]
+    #[ts(as = "f64")]
     milestone_id: i64,
     #[serde(alias = "ideaId")]
+    #[ts(as = "Option<f64>")]
     idea_id: Option<i64>,
     agents: Option<String>,
+    /// Who is creating the ticket; defaults to the user.
+    actor: Option<db::Actor>,
 }
 
 /// Create a plan ticket (for Fast development in Tauri mode; avoids fetch to /api).
 #[tauri::command]
-fn create_plan_ticket(args: CreatePlanTicketArgs) -> Result<serde_json::Value, String> {
+fn create_plan_ticket(args: CreatePlanTicketArgs) -> AppResult<db::PlanTicket> {
     with_db(|conn| {
         db::create_plan_ticket(
             conn,
@@ -574,23 +715,33 @@ fn create_plan_ticket(args: CreatePlanTicketArgs) -> Result<serde_json::Value, S
             args.milestone_id,
             args.idea_id,
             args.agents.as_deref(),
+            &args.actor.clone().unwrap_or_default(),
         )
     })
 }
 
 /// Args for set_plan_kanban_state; accept camelCase from frontend. In built app, IPC expects payload key `args`.
-#[derive(serde::Deserialize)]
+#[derive(serde::Deserialize, TS)]
+#[ts(export)]
 struct SetPlanKanbanStateArgs {
     #[serde(alias = "projectId")]
     project_id: String,
     #[serde(alias = "inProgressIds")]
     in_progress_ids: Vec<String>,
+    actor: Option<db::Actor>,
 }
 
 /// Set plan kanban in-progress IDs (for Fast development in Tauri mode; avoids fetch to /api).
 #[tauri::command]
-fn set_plan_kanban_state(args: SetPlanKanbanStateArgs) -> Result<(), String> {
-    with_db(|conn| db::set_plan_kanban_state_for_project(conn, &args.project_id, &args.in_progress_ids))
+fn set_plan_kanban_state(args: SetPlanKanbanStateArgs) -> AppResult<()> {
+    with_db(|conn| {
+        db::set_plan_kanban_state_for_project(
+            conn,
+            &args.project_id,
+            &args.in_progress_ids,
+            &args.actor.clone().unwrap_or_default(),
+        )
+    })
 }
 
 /// Update a plan ticket's done and status (for Worker tab Mark done/Redo; avoids fetch in Tauri).
@@ -600,23 +751,513 @@ fn update_plan_ticket(
     ticket_id: String,
     done: bool,
     status: String,
-) -> Result<(), String> {
-    with_db(|conn| db::update_plan_ticket(conn, &project_id, &ticket_id, done, status.trim()))
+    actor: Option<db::Actor>,
+) -> AppResult<()> {
+    let actor = actor.unwrap_or_default();
+    with_db(|conn| db::update_plan_ticket(conn, &project_id, &ticket_id, done, status.trim(), &actor))
+}
+
+/// Args for patch_plan_ticket. In built app, IPC expects payload key `args`.
+#[derive(serde::Deserialize, TS)]
+#[ts(export)]
+struct PatchPlanTicketArgs {
+    #[serde(alias = "projectId")]
+    project_id: String,
+    #[serde(alias = "ticketId")]
+    ticket_id: String,
+    patch: db::PlanTicketPatch,
+    actor: Option<db::Actor>,
+}
+
+/// Edit a plan ticket's fields; only the fields present in `patch` change.
+#[tauri::command]
+fn patch_plan_ticket(args: PatchPlanTicketArgs) -> AppResult<db::PlanTicket> {
+    let actor = args.actor.clone().unwrap_or_default();
+    with_db(|conn| db::patch_plan_ticket(conn, &args.project_id, &args.ticket_id, &args.patch, &actor))
+}
+
+/// Args for bulk_update_plan_tickets. In built app, IPC expects payload key `args`.
+#[derive(serde::Deserialize, TS)]
+#[ts(export)]
+struct BulkUpdatePlanTicketsArgs {
+    #[serde(alias = "projectId")]
+    project_id: String,
+    #[serde(alias = "ticketIds")]
+    ticket_ids: Vec<String>,
+    #[serde(default)]
+    patch: db::PlanTicketPatch,
+    /// Also move every ticket to the end of this column.
+    column: Option<String>,
+    actor: Option<db::Actor>,
+}
+
+/// Apply one patch (and optional move) to the board's selected tickets; nothing changes if any ticket fails.
+#[tauri::command]
+fn bulk_update_plan_tickets(args: BulkUpdatePlanTicketsArgs) -> AppResult<Vec<db::PlanTicket>> {
+    let actor = args.actor.clone().unwrap_or_default();
+    with_db(|conn| {
+        db::bulk_update_plan_tickets(
+            conn,
+            &args.project_id,
+            &args.ticket_ids,
+            &args.patch,
+            args.column.as_deref(),
+            &actor,
+        )
+    })
+}
+
+/// Kanban workflow of a project (columns, transitions, WIP limits); the default until one is saved.
+#[tauri::command]
+fn get_plan_workflow(ProjectIdArg { project_id }: ProjectIdArg) -> AppResult<db::Workflow> {
+    with_db(|conn| db::get_plan_workflow(conn, &project_id))
+}
+
+/// Args for set_plan_workflow. In built app, IPC expects payload key `args`.
+#[derive(serde::Deserialize, TS)]
+#[ts(export)]
+struct SetPlanWorkflowArgs {
+    #[serde(alias = "projectId")]
+    project_id: String,
+    workflow: db::Workflow,
+}
+
+/// Save a project's kanban workflow. Fails while a removed column still holds tickets.
+#[tauri::command]
+fn set_plan_workflow(args: SetPlanWorkflowArgs) -> AppResult<db::Workflow> {
+    with_db(|conn| db::set_plan_workflow(conn, &args.project_id, &args.workflow))
+}
+
+/// Args for move_plan_ticket. In built app, IPC expects payload key `args`.
+#[derive(serde::Deserialize, TS)]
+#[ts(export)]
+struct MovePlanTicketArgs {
+    #[serde(alias = "projectId")]
+    project_id: String,
+    #[serde(alias = "ticketId")]
+    ticket_id: String,
+    column: String,
+    /// 0-based index in the target column; omitted = last.
+    #[ts(as = "Option<f64>")]
+    position: Option<i64>,
+    actor: Option<db::Actor>,
+}
+
+/// Move a plan ticket to a column and position, checking the workflow's transitions and WIP limits.
+#[tauri::command]
+fn move_plan_ticket(args: MovePlanTicketArgs) -> AppResult<db::PlanTicket> {
+    let actor = args.actor.clone().unwrap_or_default();
+    with_db(|conn| db::move_plan_ticket(conn, &args.project_id, &args.ticket_id, &args.column, args.position, &actor))
+}
+
+/// Dependency edges between a project's plan tickets.
+#[tauri::command]
+fn get_plan_ticket_dependencies(
+    ProjectIdArg { project_id }: ProjectIdArg,
+) -> AppResult<Vec<db::PlanTicketDependency>> {
+    with_db(|conn| db::get_plan_ticket_dependencies(conn, &project_id))
+}
+
+/// Args for add/remove_plan_ticket_dependency. In built app, IPC expects payload key `args`.
+#[derive(serde::Deserialize, TS)]
+#[ts(export)]
+struct PlanTicketDependencyArgs {
+    #[serde(alias = "projectId")]
+    project_id: String,
+    #[serde(alias = "ticketId")]
+    ticket_id: String,
+    #[serde(alias = "dependsOnId")]
+    depends_on_id: String,
+}
+
+/// Mark a ticket as blocked by another. Fails with a conflict (and the cycle in details) when it would close a cycle.
+#[tauri::command]
+fn add_plan_ticket_dependency(args: PlanTicketDependencyArgs) -> AppResult<()> {
+    with_db(|conn| db::add_plan_ticket_dependency(conn, &args.project_id, &args.ticket_id, &args.depends_on_id))
+}
+
+/// Remove a dependency edge. Returns false when it did not exist.
+#[tauri::command]
+fn remove_plan_ticket_dependency(args: PlanTicketDependencyArgs) -> AppResult<bool> {
+    with_db(|conn| db::remove_plan_ticket_dependency(conn, &args.project_id, &args.ticket_id, &args.depends_on_id))
 }
 
-/// Delete a plan ticket (for Worker tab Archive; avoids fetch in Tauri).
+/// Open tickets whose dependencies are all done, in the order automated runs should pick them.
 #[tauri::command]
-fn delete_plan_ticket(project_id: String, ticket_id: String) -> Result<(), String> {
-    with_db(|conn| db::delete_plan_ticket(conn, &project_id, &ticket_id))
+fn get_ready_plan_tickets(ProjectIdArg { project_id }: ProjectIdArg) -> AppResult<Vec<db::PlanTicket>> {
+    with_db(|conn| db::get_ready_plan_tickets(conn, &project_id))
 }
 
-/// Ideas list (optional project filter). Used when isTauri to avoid fetch to /api.
+/// Move a plan ticket to the trash (for Worker tab Archive; avoids fetch in Tauri).
 #[tauri::command]
-fn get_ideas_list(ProjectIdArgOptional { project_id }: ProjectIdArgOptional) -> Result<Vec<serde_json::Value>, String> {
-    with_db(|conn| db::get_ideas_list(conn, project_id.as_deref()))
+fn delete_plan_ticket(project_id: String, ticket_id: String, actor: Option<db::Actor>) -> AppResult<()> {
+    let actor = actor.unwrap_or_default();
+    with_db(|conn| db::delete_plan_ticket(conn, &project_id, &ticket_id, &actor))
 }
 
-#[derive(serde::Deserialize)]
+/// Args for claim_plan_ticket. In built app, IPC expects payload key `args`.
+#[derive(serde::Deserialize, TS)]
+#[ts(export)]
+struct ClaimPlanTicketArgs {
+    #[serde(alias = "projectId")]
+    project_id: String,
+    #[serde(alias = "ticketId")]
+    ticket_id: String,
+    #[serde(alias = "runId")]
+    run_id: String,
+}
+
+/// Record that an agent run picked up a ticket (shows in the ticket's history).
+#[tauri::command]
+fn claim_plan_ticket(args: ClaimPlanTicketArgs) -> AppResult<()> {
+    with_db(|conn| db::claim_plan_ticket(conn, &args.project_id, &args.ticket_id, &args.run_id))
+}
+
+/// Args for get_plan_ticket_history. In built app, IPC expects payload key `args`.
+#[derive(serde::Deserialize, TS)]
+#[ts(export)]
+struct PlanTicketHistoryArgs {
+    #[serde(alias = "projectId")]
+    project_id: String,
+    #[serde(alias = "ticketId")]
+    ticket_id: String,
+}
+
+/// Audit trail of one plan ticket, oldest first (also for deleted tickets).
+#[tauri::command]
+fn get_plan_ticket_history(args: PlanTicketHistoryArgs) -> AppResult<Vec<db::PlanTicketEvent>> {
+    with_db(|conn| db::get_plan_ticket_history(conn, &args.project_id, &args.ticket_id))
+}
+
+/// Args for get_plan_ticket_activity. In built app, IPC expects payload key `args`.
+#[derive(serde::Deserialize, TS)]
+#[ts(export)]
+struct PlanTicketActivityArgs {
+    #[serde(alias = "projectId")]
+    project_id: String,
+    /// Page size (default 50, at most 500).
+    #[ts(as = "Option<f64>")]
+    limit: Option<i64>,
+    /// Only events older than this id (the last id of the previous page).
+    #[serde(alias = "beforeId")]
+    #[ts(as = "Option<f64>")]
+    before_id: Option<i64>,
+}
+
+/// Ticket events across a project, newest first.
+#[tauri::command]
+fn get_plan_ticket_activity(args: PlanTicketActivityArgs) -> AppResult<Vec<db::PlanTicketEvent>> {
+    with_db(|conn| db::get_plan_ticket_activity(conn, &args.project_id, args.limit.unwrap_or(50), args.before_id))
+}
+
+/// Args for sync_planner_from_markdown / sync_planner_to_markdown. In built app, IPC expects payload key `args`.
+#[derive(serde::Deserialize, TS)]
+#[ts(export)]
+struct SyncPlannerMarkdownArgs {
+    #[serde(alias = "projectId")]
+    project_id: String,
+    /// Tickets changed on both sides: "report" (default) leaves them alone, "markdown" or "database" picks that side.
+    strategy: Option<String>,
+    /// from_markdown only: delete tickets missing from tickets.md even when that is more than a quarter of them.
+    force: Option<bool>,
+}
+
+/// Reconcile a project's plan_tickets with its .cursor/7. planner/tickets.md in one direction (see planner_md::reconcile).
+fn sync_planner_markdown(
+    conn: &rusqlite::Connection,
+    project_id: &str,
+    direction: planner_md::SyncDirection,
+    strategy: planner_md::ConflictStrategy,
+    deletes: planner_md::MarkdownDeletes,
+) -> AppResult<planner_md::PlannerSyncReport> {
+    use planner_md::{MarkdownDeletes, SyncDirection};
+    let project = db::get_project(conn, project_id)?.ok_or_else(|| AppError::not_found("Project not found"))?;
+    let path = planner_tickets_md_path(&project)?;
+    let existing = match std::fs::read_to_string(&path) {
+        Ok(s) => Some(s),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound && direction == SyncDirection::ToMarkdown => None,
+        Err(e) => return Err(e.into()),
+    };
+    let parsed = planner_md::parse_tickets_md(existing.as_deref().unwrap_or(""));
+    let database: Vec<planner_md::MdTicket> = db::get_plan_tickets_for_project(conn, &project.id)?
+        .iter()
+        .map(planner_md::MdTicket::from)
+        .collect();
+    let baseline = db::get_plan_ticket_md_baseline(conn, &project.id)?;
+    let mut plan = planner_md::reconcile(&parsed.tickets, &database, &baseline, direction, strategy);
+    let mut pending_deletes = vec![];
+    if direction == SyncDirection::FromMarkdown {
+        planner_md::check_markdown_deletes(&parsed, database.len(), plan.db_deletes.len(), deletes).map_err(|m| {
+            AppError::conflict(m).with_details(serde_json::json!({ "deletes": plan.db_deletes, "path": path }))
+        })?;
+        if deletes == MarkdownDeletes::Report {
+            // Keep the baseline so the tickets stay pending until an explicit sync.
+            pending_deletes = std::mem::take(&mut plan.db_deletes);
+            plan.deleted.retain(|n| !pending_deletes.contains(n));
+            plan.baseline.retain(|(n, _)| !pending_deletes.contains(n));
+        }
+    }
+
+    let actor = db::Actor::markdown_sync();
+    let tx = conn.unchecked_transaction()?;
+    let mut written = false;
+    match direction {
+        SyncDirection::FromMarkdown => {
+            if !plan.db_upserts.is_empty() {
+                let milestone_id = db::general_development_milestone_id(&tx, &project.id)?;
+                let workflow = db::get_plan_workflow(&tx, &project.id)?;
+                for t in &plan.db_upserts {
+                    match db::upsert_plan_ticket_from_markdown(&tx, &project.id, t, milestone_id, &workflow, &actor) {
+                        Ok(()) => {}
+                        // The board refused the move a checkbox asked for: keep the ticket pending as a conflict.
+                        Err(e) if matches!(e.code, error::ErrorCode::InvalidArgument | error::ErrorCode::Conflict) => {
+                            plan.created.retain(|n| *n != t.number);
+                            plan.updated.retain(|n| *n != t.number);
+                            plan.baseline.retain(|(n, _)| *n != t.number);
+                            plan.conflicts.push(planner_md::PlannerSyncConflict {
+                                number: t.number,
+                                markdown: Some(t.clone()),
+                                database: database.iter().find(|d| d.number == t.number).cloned(),
+                                reason: Some(e.message),
+                            });
+                        }
+                        Err(e) => return Err(e),
+                    }
+                }
+            }
+            for number in &plan.db_deletes {
+                db::delete_plan_ticket_by_number(&tx, &project.id, *number, &actor)?;
+            }
+        }
+        SyncDirection::ToMarkdown => {
+            if existing.is_none() || plan.markdown != parsed.tickets {
+                let content = planner_md::write_tickets_md(existing.as_deref(), &plan.markdown, &project.name);
+                if let Some(parent) = path.parent() {
+                    std::fs::create_dir_all(parent)?;
+                }
+                std::fs::write(&path, content)?;
+                written = true;
+            }
+        }
+    }
+    for (number, fingerprint) in &plan.baseline {
+        db::set_plan_ticket_md_baseline(&tx, &project.id, *number, fingerprint.as_deref())?;
+    }
+    tx.commit()?;
+    Ok(planner_md::PlannerSyncReport {
+        direction: match direction {
+            SyncDirection::FromMarkdown => "from_markdown",
+            SyncDirection::ToMarkdown => "to_markdown",
+        }
+        .to_string(),
+        path: path.to_string_lossy().to_string(),
+        created: plan.created,
+        updated: plan.updated,
+        deleted: plan.deleted,
+        pending_deletes,
+        unchanged: plan.unchanged,
+        conflicts: plan.conflicts,
+        warnings: parsed.warnings,
+        written,
+    })
+}
+
+/// .cursor/7. planner/tickets.md in the project's repo.
+fn planner_tickets_md_path(project: &Project) -> AppResult<PathBuf> {
+    let repo = project
+        .repo_path
+        .as_deref()
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .ok_or_else(|| AppError::invalid("Project has no repo path"))?;
+    Ok(PathBuf::from(repo).join(planner_md::TICKETS_MD_PATH))
+}
+
+/// Apply edits made in .cursor/7. planner/tickets.md to plan_tickets. Tickets edited on both sides since the last
+/// sync are reported as conflicts unless a strategy picks a side. Refused (conflict) when the file has no ticket
+/// section, or when it would delete more than a quarter of the tickets without `force`.
+#[tauri::command]
+fn sync_planner_from_markdown(args: SyncPlannerMarkdownArgs) -> AppResult<planner_md::PlannerSyncReport> {
+    let strategy = planner_md::ConflictStrategy::parse(args.strategy.as_deref()).map_err(AppError::invalid)?;
+    let deletes = if args.force.unwrap_or(false) {
+        planner_md::MarkdownDeletes::Force
+    } else {
+        planner_md::MarkdownDeletes::Apply
+    };
+    with_db(|conn| {
+        sync_planner_markdown(conn, &args.project_id, planner_md::SyncDirection::FromMarkdown, strategy, deletes)
+    })
+}
+
+/// Write plan_tickets changes into .cursor/7. planner/tickets.md (created if missing). Only the ticket section is
+/// rewritten; edits still pending on the Markdown side are kept.
+#[tauri::command]
+fn sync_planner_to_markdown(args: SyncPlannerMarkdownArgs) -> AppResult<planner_md::PlannerSyncReport> {
+    let strategy = planner_md::ConflictStrategy::parse(args.strategy.as_deref()).map_err(AppError::invalid)?;
+    with_db(|conn| {
+        sync_planner_markdown(
+            conn,
+            &args.project_id,
+            planner_md::SyncDirection::ToMarkdown,
+            strategy,
+            planner_md::MarkdownDeletes::Apply,
+        )
+    })
+}
+
+/// File watchers for tickets.md by project id. Dropping a watcher stops it.
+#[derive(Default)]
+struct PlannerWatchState {
+    watchers: Mutex<HashMap<String, notify::RecommendedWatcher>>,
+}
+
+/// Payload of "planner-markdown-synced", emitted after a watched tickets.md changed and was synced into plan_tickets.
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct PlannerMarkdownSyncedPayload {
+    pub project_id: String,
+    pub report: Option<planner_md::PlannerSyncReport>,
+    pub error: Option<String>,
+}
+
+#[derive(serde::Deserialize, TS)]
+#[ts(export)]
+struct WatchPlannerMarkdownArgs {
+    #[serde(alias = "projectId")]
+    project_id: String,
+    enabled: bool,
+}
+
+/// Start or stop watching a project's tickets.md. While watching, edits (e.g. by agents) are synced into plan_tickets
+/// with the "report" strategy and a "planner-markdown-synced" event is emitted. Tickets missing from the file are never
+/// deleted here, only listed as pending_deletes for an explicit sync_planner_from_markdown. Returns whether a watcher
+/// is active.
+#[tauri::command]
+fn watch_planner_markdown(
+    app: AppHandle,
+    state: State<'_, PlannerWatchState>,
+    args: WatchPlannerMarkdownArgs,
+) -> AppResult<bool> {
+    use notify::Watcher;
+    let project_id = args.project_id.trim().to_string();
+    let mut watchers = state.watchers.lock()?;
+    watchers.remove(&project_id);
+    if !args.enabled {
+        return Ok(false);
+    }
+    let path = with_db(|conn| {
+        let project = db::get_project(conn, &project_id)?.ok_or_else(|| AppError::not_found("Project not found"))?;
+        planner_tickets_md_path(&project)
+    })?;
+    let dir = path.parent().map(Path::to_path_buf).unwrap_or_default();
+    std::fs::create_dir_all(&dir)?;
+
+    let (tx, rx) = std::sync::mpsc::channel::<()>();
+    let file_name = path.file_name().map(|n| n.to_os_string());
+    let mut watcher = notify::recommended_watcher(move |res: notify::Result<notify::Event>| {
+        let Ok(event) = res else { return };
+        let touches_file = event.paths.iter().any(|p| p.file_name().map(|n| n.to_os_string()) == file_name);
+        if touches_file && (event.kind.is_create() || event.kind.is_modify()) {
+            let _ = tx.send(());
+        }
+    })?;
+    // Watch the folder: agents often replace the file instead of writing it in place.
+    watcher.watch(&dir, notify::RecursiveMode::NonRecursive)?;
+
+    let watched_id = project_id.clone();
+    thread::spawn(move || {
+        // Ends when the watcher (and with it the sender) is dropped.
+        while rx.recv().is_ok() {
+            // Let a burst of writes settle before reading the file.
+            while rx.recv_timeout(std::time::Duration::from_millis(300)).is_ok() {}
+            if !path.is_file() {
+                continue;
+            }
+            let result = with_db(|conn| {
+                sync_planner_markdown(
+                    conn,
+                    &watched_id,
+                    planner_md::SyncDirection::FromMarkdown,
+                    planner_md::ConflictStrategy::Report,
+                    planner_md::MarkdownDeletes::Report,
+                )
+            });
+            let (report, error) = match result {
+                Ok(r) => (Some(r), None),
+                Err(e) => (None, Some(e.to_string())),
+            };
+            let _ = app.emit(
+                "planner-markdown-synced",
+                PlannerMarkdownSyncedPayload {
+                    project_id: watched_id.clone(),
+                    report,
+                    error,
+                },
+            );
+        }
+    });
+    watchers.insert(project_id, watcher);
+    Ok(true)
+}
+
+/// Ideas list (optional project filter; optional category/source/status/score filters and sort). Used when isTauri
+/// to avoid fetch to /api.
+#[tauri::command]
+fn get_ideas_list(
+    ProjectIdArgOptional { project_id }: ProjectIdArgOptional,
+    query: Option<db::IdeaListQuery>,
+) -> AppResult<Vec<db::Idea>> {
+    with_db(|conn| db::get_ideas_list(conn, project_id.as_deref(), &query.unwrap_or_default()))
+}
+
+/// Args for rank_ideas. In built app, IPC expects payload key `args`.
+#[derive(serde::Deserialize, TS)]
+#[ts(export)]
+struct RankIdeasArgs {
+    /// Project ideas plus unassigned ones; all ideas when omitted.
+    #[serde(alias = "projectId")]
+    project_id: Option<String>,
+    /// "rice" (default) or "ice".
+    #[serde(default)]
+    method: db::IdeaScoreMethod,
+    /// Default 10.
+    #[ts(as = "Option<f64>")]
+    limit: Option<i64>,
+}
+
+/// Top open ideas by RICE or ICE score with their score breakdown, for triage.
+#[tauri::command]
+fn rank_ideas(args: RankIdeasArgs) -> AppResult<Vec<db::RankedIdea>> {
+    let limit = args.limit.unwrap_or(10).max(1) as usize;
+    with_db(|conn| db::rank_ideas(conn, args.project_id.as_deref(), args.method, limit))
+}
+
+/// Args for search. In built app, IPC expects payload key `args`.
+#[derive(serde::Deserialize, TS)]
+#[ts(export)]
+struct SearchArgs {
+    query: String,
+    /// Limit hits to these kinds; all kinds when omitted or empty.
+    kinds: Option<Vec<db::SearchKind>>,
+    /// Rows of this project plus the tickets, prompts and ideas linked to it; everything when omitted.
+    #[serde(alias = "projectId")]
+    project_id: Option<String>,
+    /// Default 20, at most 100.
+    #[ts(as = "Option<f64>")]
+    limit: Option<i64>,
+}
+
+/// Full-text search over plan tickets, tickets, ideas, milestones, prompts and run logs with highlighted snippets
+/// (used by the command palette).
+#[tauri::command]
+fn search(args: SearchArgs) -> AppResult<Vec<db::SearchHit>> {
+    let limit = args.limit.unwrap_or(20).clamp(1, 100) as usize;
+    let kinds = args.kinds.unwrap_or_default();
+    with_db(|conn| db::search(conn, &args.query, &kinds, args.project_id.as_deref(), limit))
+}
+
+#[derive(serde::Deserialize, TS)]
+#[ts(export)]
 struct CreateIdeaArgs {
     #[serde(alias = "projectId")]
     project_id: Option<String>,
@@ -628,7 +1269,7 @@ struct CreateIdeaArgs {
 
 /// Create one idea (for Idea-driven "create from description" flow). Returns the new idea row.
 #[tauri::command]
-fn create_idea(args: CreateIdeaArgs) -> Result<serde_json::Value, String> {
+fn create_idea(args: CreateIdeaArgs) -> AppResult<db::Idea> {
     with_db(|conn| {
         db::create_idea(
             conn,
@@ -641,7 +1282,70 @@ fn create_idea(args: CreateIdeaArgs) -> Result<serde_json::Value, String> {
     })
 }
 
-#[derive(serde::Deserialize)]
+/// Args for update_idea. In built app, IPC expects payload key `args`.
+#[derive(serde::Deserialize, TS)]
+#[ts(export)]
+struct UpdateIdeaArgs {
+    #[serde(alias = "ideaId")]
+    #[ts(as = "f64")]
+    idea_id: i64,
+    patch: db::IdeaPatch,
+}
+
+/// Edit an idea (title, description, category, project, status).
+#[tauri::command]
+fn update_idea(args: UpdateIdeaArgs) -> AppResult<db::Idea> {
+    with_db(|conn| db::update_idea(conn, args.idea_id, &args.patch))
+}
+
+/// Args for delete_idea. In built app, IPC expects payload key `args`.
+#[derive(serde::Deserialize, TS)]
+#[ts(export)]
+struct DeleteIdeaArgs {
+    #[serde(alias = "ideaId")]
+    #[ts(as = "f64")]
+    idea_id: i64,
+}
+
+/// Delete an idea; tickets created from it are kept and unlinked.
+#[tauri::command]
+fn delete_idea(args: DeleteIdeaArgs) -> AppResult<()> {
+    with_db(|conn| db::delete_idea(conn, args.idea_id, &db::Actor::user()))
+}
+
+/// Args for convert_idea_to_plan. In built app, IPC expects payload key `args`.
+#[derive(serde::Deserialize, TS)]
+#[ts(export)]
+struct ConvertIdeaToPlanArgs {
+    #[serde(alias = "ideaId")]
+    #[ts(as = "f64")]
+    idea_id: i64,
+    #[serde(alias = "projectId")]
+    project_id: String,
+    /// Empty = the idea's title.
+    #[serde(default, alias = "milestoneName")]
+    milestone_name: String,
+    #[serde(default, alias = "ticketTitles")]
+    ticket_titles: Vec<String>,
+}
+
+/// Promote an idea to a milestone with tickets in a project and mark it converted.
+#[tauri::command]
+fn convert_idea_to_plan(args: ConvertIdeaToPlanArgs) -> AppResult<db::IdeaConversion> {
+    with_db(|conn| {
+        db::convert_idea_to_plan(
+            conn,
+            args.idea_id,
+            &args.project_id,
+            &args.milestone_name,
+            &args.ticket_titles,
+            &db::Actor::user(),
+        )
+    })
+}
+
+#[derive(serde::Deserialize, TS)]
+#[ts(export)]
 struct CreateProjectMilestoneArgs {
     #[serde(alias = "projectId")]
     project_id: String,
@@ -652,7 +1356,7 @@ struct CreateProjectMilestoneArgs {
 
 /// Create one milestone for a project (for Idea-driven "create from description" flow). Returns the new milestone row.
 #[tauri::command]
-fn create_project_milestone(args: CreateProjectMilestoneArgs) -> Result<serde_json::Value, String> {
+fn create_project_milestone(args: CreateProjectMilestoneArgs) -> AppResult<db::Milestone> {
     with_db(|conn| {
         db::create_milestone(
             conn,
@@ -664,28 +1368,165 @@ fn create_project_milestone(args: CreateProjectMilestoneArgs) -> Result<serde_js
     })
 }
 
-#[tauri::command]
+/// Args for update_project_milestone. In built app, IPC expects payload key `args`.
+#[derive(serde::Deserialize, TS)]
+#[ts(export)]
+struct UpdateProjectMilestoneArgs {
+    #[serde(alias = "projectId")]
+    project_id: String,
+    #[serde(alias = "milestoneId")]
+    #[ts(as = "f64")]
+    milestone_id: i64,
+    patch: db::MilestonePatch,
+}
+
+/// Rename a milestone or edit its slug/content.
+#[tauri::command]
+fn update_project_milestone(args: UpdateProjectMilestoneArgs) -> AppResult<db::Milestone> {
+    with_db(|conn| db::update_milestone(conn, &args.project_id, args.milestone_id, &args.patch))
+}
+
+/// Args for delete_project_milestone. In built app, IPC expects payload key `args`.
+#[derive(serde::Deserialize, TS)]
+#[ts(export)]
+struct DeleteProjectMilestoneArgs {
+    #[serde(alias = "projectId")]
+    project_id: String,
+    #[serde(alias = "milestoneId")]
+    #[ts(as = "f64")]
+    milestone_id: i64,
+    /// What to do with the milestone's tickets; defaults to moving them to General Development.
+    #[serde(default)]
+    tickets: db::MilestoneTicketPolicy,
+}
+
+/// Delete a milestone and move or delete its tickets. Returns the number of tickets affected.
+#[tauri::command]
+fn delete_project_milestone(args: DeleteProjectMilestoneArgs) -> AppResult<usize> {
+    with_db(|conn| db::delete_milestone(conn, &args.project_id, args.milestone_id, &args.tickets, &db::Actor::user()))
+}
+
+/// Args for merge_project_milestones. In built app, IPC expects payload key `args`.
+#[derive(serde::Deserialize, TS)]
+#[ts(export)]
+struct MergeProjectMilestonesArgs {
+    #[serde(alias = "projectId")]
+    project_id: String,
+    #[serde(alias = "sourceIds")]
+    #[ts(as = "Vec<f64>")]
+    source_ids: Vec<i64>,
+    #[serde(alias = "targetId")]
+    #[ts(as = "f64")]
+    target_id: i64,
+}
+
+/// Merge milestones into one: tickets and content move to the target, the sources are deleted.
+#[tauri::command]
+fn merge_project_milestones(args: MergeProjectMilestonesArgs) -> AppResult<db::Milestone> {
+    with_db(|conn| db::merge_milestones(conn, &args.project_id, &args.source_ids, args.target_id, &db::Actor::user()))
+}
+
+/// Ticket counts, open priorities and recent throughput per milestone.
+#[tauri::command]
+fn get_milestone_progress(ProjectIdArg { project_id }: ProjectIdArg) -> AppResult<Vec<db::MilestoneProgress>> {
+    with_db(|conn| db::get_milestone_progress(conn, &project_id))
+}
+
+/// Args for reorder_project_milestones. In built app, IPC expects payload key `args`.
+#[derive(serde::Deserialize, TS)]
+#[ts(export)]
+struct ReorderProjectMilestonesArgs {
+    #[serde(alias = "projectId")]
+    project_id: String,
+    /// New order; milestones not listed keep their relative order after these.
+    #[serde(alias = "milestoneIds")]
+    #[ts(as = "Vec<f64>")]
+    milestone_ids: Vec<i64>,
+}
+
+/// Reorder a project's milestones. Returns them in the new order.
+#[tauri::command]
+fn reorder_project_milestones(args: ReorderProjectMilestonesArgs) -> AppResult<Vec<db::Milestone>> {
+    with_db(|conn| db::reorder_milestones(conn, &args.project_id, &args.milestone_ids))
+}
+
+/// Milestones in order with completion dates projected from recent implementation throughput.
+#[tauri::command]
+fn get_roadmap(ProjectIdArg { project_id }: ProjectIdArg) -> AppResult<db::Roadmap> {
+    with_db(|conn| db::get_roadmap(conn, &project_id))
+}
+
+/// Args for export_roadmap. In built app, IPC expects payload key `args`.
+#[derive(serde::Deserialize, TS)]
+#[ts(export)]
+struct ExportRoadmapArgs {
+    #[serde(alias = "projectId")]
+    project_id: String,
+    /// "ics", "json" or "all" (default).
+    format: Option<String>,
+}
+
+/// Write the roadmap to .cursor/roadmap/ in the project repo as roadmap.ics and/or roadmap.json. Returns the paths
+/// written.
+#[tauri::command]
+fn export_roadmap(args: ExportRoadmapArgs) -> AppResult<Vec<String>> {
+    let (ics, json) = match args.format.as_deref().map(str::trim).unwrap_or("all") {
+        "ics" => (true, false),
+        "json" => (false, true),
+        "all" | "" => (true, true),
+        other => {
+            return Err(AppError::invalid(format!("Unknown roadmap format '{}' (expected ics, json or all)", other)))
+        }
+    };
+    let (project, roadmap) = with_db(|conn| {
+        let project =
+            db::get_project(conn, &args.project_id)?.ok_or_else(|| AppError::not_found("Project not found"))?;
+        let roadmap = db::get_roadmap(conn, &project.id)?;
+        Ok((project, roadmap))
+    })?;
+    let repo = project
+        .repo_path
+        .as_deref()
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .ok_or_else(|| AppError::invalid("Project has no repo path"))?;
+    let dir = PathBuf::from(repo).join(roadmap::ROADMAP_DIR);
+    std::fs::create_dir_all(&dir)?;
+    let mut written = vec![];
+    if ics {
+        let path = dir.join(roadmap::ICS_FILE);
+        std::fs::write(&path, roadmap::to_ics(&roadmap, &project.name))?;
+        written.push(path.to_string_lossy().to_string());
+    }
+    if json {
+        let path = dir.join(roadmap::JSON_FILE);
+        std::fs::write(&path, serde_json::to_string_pretty(&roadmap::to_gantt_json(&roadmap, &project.name))?)?;
+        written.push(path.to_string_lossy().to_string());
+    }
+    Ok(written)
+}
+
+#[tauri::command]
 fn update_implementation_log_entry_status(
     project_id: String,
     entry_id: i64,
     status: String,
-) -> Result<ImplementationLogEntry, String> {
+) -> AppResult<ImplementationLogEntry> {
     let status = status.trim();
     if status != "accepted" && status != "declined" {
-        return Err("status must be 'accepted' or 'declined'".to_string());
+        return Err(AppError::invalid("status must be 'accepted' or 'declined'"));
     }
     with_db(|conn| {
         let updated = conn
             .execute(
                 "UPDATE implementation_log SET status = ?1 WHERE id = ?2 AND project_id = ?3",
                 rusqlite::params![status, entry_id, project_id.trim()],
-            )
-            .map_err(|e| e.to_string())?;
+            )?;
         if updated == 0 {
-            return Err("Implementation log entry not found".to_string());
+            return Err(AppError::not_found("Implementation log entry not found"));
         }
         let entry = conn.query_row(
-            "SELECT id, project_id, run_id, ticket_number, ticket_title, milestone_id, idea_id, completed_at, files_changed, summary, created_at, status FROM implementation_log WHERE id = ?1",
+            "SELECT id, project_id, run_id, ticket_number, ticket_title, milestone_id, idea_id, completed_at, files_changed, summary, created_at, status, commit_hash FROM implementation_log WHERE id = ?1",
             rusqlite::params![entry_id],
             |row| {
                 Ok(ImplementationLogEntry {
@@ -701,10 +1542,10 @@ fn update_implementation_log_entry_status(
                     summary: row.get(9)?,
                     created_at: row.get(10)?,
                     status: row.get::<_, String>(11).unwrap_or_else(|_| "pending".to_string()),
+                    commit_hash: row.get(12)?,
                 })
             },
-        )
-        .map_err(|e| e.to_string())?;
+        )?;
         Ok(entry)
     })
 }
@@ -720,7 +1561,7 @@ fn append_implementation_log_entry(
     completed_at: String,
     files_changed: String,
     summary: String,
-) -> Result<(), String> {
+) -> AppResult<()> {
     with_db(|conn| {
         conn.execute(
             "INSERT INTO implementation_log (project_id, run_id, ticket_number, ticket_title, milestone_id, idea_id, completed_at, files_changed, summary, created_at) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)",
@@ -736,103 +1577,1984 @@ fn append_implementation_log_entry(
                 summary.trim(),
                 now_iso(),
             ],
-        )
-        .map_err(|e| e.to_string())?;
+        )?;
         Ok(())
     })
 }
 
-/// Return git diff and full file content for a changed file. Used when clicking a file in the Git tab.
-#[derive(serde::Serialize)]
-pub struct GitFileView {
-    pub diff: String,
-    pub full_content: Option<String>,
+/// Return git diff and full file content for a changed file. Used when clicking a file in the Git tab.
+#[derive(serde::Serialize, TS)]
+#[ts(export)]
+pub struct GitFileView {
+    pub diff: String,
+    pub full_content: Option<String>,
+}
+
+#[tauri::command]
+fn get_git_file_view(project_path: String, file_path: String) -> AppResult<GitFileView> {
+    let path_buf = PathBuf::from(project_path.trim());
+    if !path_buf.exists() || !path_buf.is_dir() {
+        return Err(AppError::not_found("Project path does not exist"));
+    }
+    let (path_buf, _) = resolve_git_repo(&path_buf)?;
+    let file_path = file_path.trim();
+    if file_path.is_empty() {
+        return Err(AppError::invalid("File path is empty"));
+    }
+    let full_path = path_buf.join(file_path);
+    let exists = full_path.exists() && full_path.is_file();
+
+    let mut diff_parts = vec![];
+    if let Ok(staged) = run_git(&path_buf, &["diff", "--staged", "--", file_path]) {
+        if !staged.is_empty() {
+            diff_parts.push(format!("=== Staged changes ===\n{}\n", staged));
+        }
+    }
+    if let Ok(unstaged) = run_git(&path_buf, &["diff", "--", file_path]) {
+        if !unstaged.is_empty() {
+            diff_parts.push(format!("=== Unstaged changes ===\n{}\n", unstaged));
+        }
+    }
+    let diff = if diff_parts.is_empty() && exists {
+        "(no changes or untracked file)".to_string()
+    } else {
+        diff_parts.join("\n")
+    };
+
+    let full_content = if exists {
+        std::fs::read_to_string(&full_path).ok()
+    } else {
+        None
+    };
+
+    Ok(GitFileView { diff, full_content })
+}
+
+fn validate_git_repo(project_path: &str) -> AppResult<PathBuf> {
+    let path_buf = PathBuf::from(project_path.trim());
+    if path_buf.as_os_str().is_empty() {
+        return Err(AppError::invalid("Project path is empty"));
+    }
+    if !path_buf.exists() || !path_buf.is_dir() {
+        return Err(AppError::not_found("Project path does not exist or is not a directory"));
+    }
+    let (repo, _) = resolve_git_repo(&path_buf)?;
+    Ok(repo)
+}
+
+/// Default wall-clock limit for fetch/pull/push runs. Override per call with `timeout_secs`.
+const GIT_NETWORK_TIMEOUT_SECS: u64 = 300;
+
+/// Progress update parsed from git's `--progress` output (e.g. "Receiving objects:  45% (9/20)").
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct GitProgressPayload {
+    pub run_id: String,
+    pub phase: String,
+    pub percent: u8,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[ts(as = "Option<f64>")]
+    pub current: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[ts(as = "Option<f64>")]
+    pub total: Option<u64>,
+}
+
+/// Credential prompt raised by the GIT_ASKPASS helper. Answer with `answer_git_prompt`.
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct GitCredentialRequestPayload {
+    pub run_id: String,
+    pub request_id: String,
+    pub prompt: String,
+}
+
+/// GIT_ASKPASS helper: creates the FIFO `<id>.answer`, publishes the prompt as `<id>.prompt` and blocks reading the
+/// FIFO. The app writes "ok\n<answer>\n" or "cancel\n" into it, so answers never touch the disk.
+const GIT_ASKPASS_SCRIPT: &str = r#"#!/bin/sh
+dir="$KWCODE_ASKPASS_DIR"
+[ -n "$dir" ] && [ -d "$dir" ] || exit 1
+id="$(date +%s)-$$"
+mkfifo -m 600 "$dir/$id.answer" || exit 1
+printf '%s' "$1" > "$dir/$id.prompt.tmp" && mv "$dir/$id.prompt.tmp" "$dir/$id.prompt"
+{ IFS= read -r status; IFS= read -r answer; } < "$dir/$id.answer"
+rm -f "$dir/$id.answer" "$dir/$id.prompt"
+[ "$status" = ok ] || exit 1
+printf '%s\n' "$answer"
+"#;
+
+/// Askpass helper of one git run: a freshly created, randomly named 0700 directory holding the 0700 script. The
+/// directory is removed when this is dropped.
+struct GitAskpass {
+    dir: tempfile::TempDir,
+    script: PathBuf,
+}
+
+fn create_git_askpass() -> AppResult<GitAskpass> {
+    let mut builder = tempfile::Builder::new();
+    builder.prefix("kwcode_askpass_");
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        builder.permissions(std::fs::Permissions::from_mode(0o700));
+    }
+    // tempdir() creates the directory exclusively and fails rather than reuse one someone prepared in advance.
+    let dir = builder.tempdir()?;
+    let script = dir.path().join("askpass.sh");
+    let mut options = std::fs::OpenOptions::new();
+    options.write(true).create_new(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o700);
+    }
+    options.open(&script)?.write_all(GIT_ASKPASS_SCRIPT.as_bytes())?;
+    Ok(GitAskpass { dir, script })
+}
+
+/// Hand an answer (or a cancel, for `None`) to the askpass helper waiting on `<request_id>.answer` in `dir`.
+fn answer_git_askpass(dir: &Path, request_id: &str, answer: Option<&str>) -> AppResult<()> {
+    if !dir.join(format!("{}.prompt", request_id)).exists() {
+        return Err(AppError::not_found("Credential request not found or already answered"));
+    }
+    if answer.is_some_and(|a| a.contains('\n')) {
+        return Err(AppError::invalid("Answer must be a single line"));
+    }
+    let message = match answer {
+        Some(text) => format!("ok\n{}\n", text),
+        None => "cancel\n".to_string(),
+    };
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        let fifo = dir.join(format!("{}.answer", request_id));
+        // A non-blocking open fails with ENXIO until the helper has the FIFO open for reading (right after it publishes
+        // the prompt); retry briefly rather than block forever on a helper that died.
+        for _ in 0..50 {
+            match std::fs::OpenOptions::new().write(true).custom_flags(libc::O_NONBLOCK).open(&fifo) {
+                Ok(mut pipe) => {
+                    pipe.write_all(message.as_bytes())?;
+                    return Ok(());
+                }
+                Err(e) if e.raw_os_error() == Some(libc::ENXIO) => {
+                    thread::sleep(std::time::Duration::from_millis(100))
+                }
+                Err(e) => return Err(e.into()),
+            }
+        }
+        Err(AppError::not_found("Credential request is no longer waiting for an answer"))
+    }
+    #[cfg(not(unix))]
+    {
+        let _ = message;
+        Err(AppError::internal("Credential prompts are only supported on Unix"))
+    }
+}
+
+/// `git <args>` for a network operation: no terminal prompts, credentials through the askpass helper.
+fn git_network_command(repo: &Path, git_args: &[String], askpass: &GitAskpass) -> Command {
+    let mut cmd = Command::new("git");
+    cmd.args(git_args)
+        .current_dir(repo)
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .env("GIT_TERMINAL_PROMPT", "0")
+        .env("GIT_ASKPASS", askpass.script.as_os_str())
+        .env("SSH_ASKPASS", askpass.script.as_os_str())
+        .env("SSH_ASKPASS_REQUIRE", "force")
+        .env("KWCODE_ASKPASS_DIR", askpass.dir.path().as_os_str());
+    cmd
+}
+
+/// Parse one segment of git progress output into (phase, percent, current, total). Accepts a "remote: " prefix.
+fn parse_git_progress(line: &str) -> Option<(String, u8, Option<u64>, Option<u64>)> {
+    let line = line.trim().trim_start_matches("remote:").trim();
+    let pct_idx = line.find('%')?;
+    let (head, tail) = line.split_at(pct_idx);
+    let colon = head.rfind(": ")?;
+    let phase = head[..colon].trim().to_string();
+    let percent: u8 = head[colon + 2..].trim().parse().ok()?;
+    if phase.is_empty() || percent > 100 {
+        return None;
+    }
+    let counts = tail
+        .find('(')
+        .and_then(|start| tail[start + 1..].find(')').map(|end| &tail[start + 1..start + 1 + end]))
+        .and_then(|inner| {
+            let (current, total) = inner.split_once('/')?;
+            Some((current.trim().parse::<u64>().ok()?, total.trim().parse::<u64>().ok()?))
+        });
+    Some((phase, percent, counts.map(|c| c.0), counts.map(|c| c.1)))
+}
+
+/// Read git output and call `f` for each segment. Git redraws progress lines with `\r`, so split on both `\r` and `\n`.
+fn for_each_git_output_segment<R: Read>(mut reader: R, mut f: impl FnMut(String)) {
+    let mut pending: Vec<u8> = Vec::new();
+    let mut buf = [0u8; 4096];
+    loop {
+        let n = match reader.read(&mut buf) {
+            Ok(0) | Err(_) => break,
+            Ok(n) => n,
+        };
+        for &b in &buf[..n] {
+            if b == b'\r' || b == b'\n' {
+                if !pending.is_empty() {
+                    f(String::from_utf8_lossy(&pending).to_string());
+                    pending.clear();
+                }
+            } else {
+                pending.push(b);
+            }
+        }
+    }
+    if !pending.is_empty() {
+        f(String::from_utf8_lossy(&pending).to_string());
+    }
+}
+
+/// SIGKILL a child spawned with `process_group(0)` together with everything it started (ssh, credential helpers).
+fn kill_process_group(child: &mut Child) {
+    #[cfg(unix)]
+    {
+        let _ = unsafe { libc::kill(-(child.id() as i32), libc::SIGKILL) };
+    }
+    let _ = child.kill();
+}
+
+/// Run a git network operation (fetch/pull/push) under the run supervisor: output streams as `script-log`,
+/// progress as `git-progress`, credential prompts as `git-credential-request`; the run is killed after `timeout_secs`.
+fn run_git_network_inner(
+    app: AppHandle,
+    state: State<'_, RunningState>,
+    repo: PathBuf,
+    run_id: String,
+    run_label: String,
+    git_args: Vec<String>,
+    timeout_secs: u64,
+) -> AppResult<()> {
+    let run_label_clone = run_label.clone();
+    let askpass = create_git_askpass()?;
+    let mut cmd = git_network_command(&repo, &git_args, &askpass);
+    #[cfg(unix)]
+    cmd.process_group(0);
+
+    let mut child = cmd.spawn().map_err(|e| AppError::spawn_failed(e.to_string()))?;
+    state.askpass_dirs.lock()?.insert(run_id.clone(), askpass.dir.path().to_path_buf());
+    let askpass_dirs = Arc::clone(&state.askpass_dirs);
+    let stdout = child.stdout.take().ok_or("no stdout")?;
+    let stderr = child.stderr.take().ok_or("no stderr")?;
+
+    {
+        let mut guard = state.runs.lock()?;
+        guard.insert(
+            run_id.clone(),
+            RunEntry {
+                child,
+                label: run_label.clone(),
+            },
+        );
+    }
+
+    let app_stdout = app.clone();
+    let app_stderr = app.clone();
+    let app_exited = app.clone();
+    let runs_handle = Arc::clone(&state.runs);
+    let run_id_stdout = run_id.clone();
+    let run_id_stderr = run_id.clone();
+    let run_id_exited = run_id.clone();
+    thread::spawn(move || {
+        for_each_git_output_segment(stdout, |line| {
+            let _ = app_stdout.emit(
+                "script-log",
+                ScriptLogPayload {
+                    run_id: run_id_stdout.clone(),
+                    line,
+                },
+            );
+        });
+    });
+    thread::spawn(move || {
+        let mut last_progress: Option<(String, u8)> = None;
+        for_each_git_output_segment(stderr, |line| {
+            if let Some((phase, percent, current, total)) = parse_git_progress(&line) {
+                let key = (phase.clone(), percent);
+                if last_progress.as_ref() == Some(&key) {
+                    return;
+                }
+                last_progress = Some(key);
+                let _ = app_stderr.emit(
+                    "git-progress",
+                    GitProgressPayload {
+                        run_id: run_id_stderr.clone(),
+                        phase,
+                        percent,
+                        current,
+                        total,
+                    },
+                );
+                // Only the final state of each phase goes to the log; intermediate percentages would flood it.
+                if percent < 100 && !line.contains(", done") {
+                    return;
+                }
+            }
+            let _ = app_stderr.emit(
+                "script-log",
+                ScriptLogPayload {
+                    run_id: run_id_stderr.clone(),
+                    line: format!("[stderr] {}", line),
+                },
+            );
+        });
+    });
+    thread::spawn(move || {
+        let started = std::time::Instant::now();
+        let timeout = std::time::Duration::from_secs(timeout_secs);
+        let mut seen_prompts: HashSet<String> = HashSet::new();
+        loop {
+            let exit_code_to_emit: Option<Option<i32>> = {
+                let mut guard = match runs_handle.lock() {
+                    Ok(g) => g,
+                    Err(_) => break,
+                };
+                if let Some(entry) = guard.get_mut(&run_id_exited) {
+                    if let Some(status) = entry.child.try_wait().ok().flatten() {
+                        let code = status.code();
+                        guard.remove(&run_id_exited);
+                        Some(code)
+                    } else if started.elapsed() >= timeout {
+                        kill_process_group(&mut entry.child);
+                        let _ = entry.child.wait();
+                        guard.remove(&run_id_exited);
+                        let _ = app_exited.emit(
+                            "script-log",
+                            ScriptLogPayload {
+                                run_id: run_id_exited.clone(),
+                                line: format!("[stderr] git timed out after {}s", timeout_secs),
+                            },
+                        );
+                        Some(None)
+                    } else {
+                        None
+                    }
+                } else {
+                    break;
+                }
+            };
+            if let Some(exit_code) = exit_code_to_emit {
+                let conflicts = match exit_code {
+                    Some(0) => None,
+                    _ if git_args.first().is_some_and(|op| op == "pull") => {
+                        git_conflict_state_impl(&repo).ok().filter(|state| !state.files.is_empty())
+                    }
+                    _ => None,
+                };
+                if let Some(state) = conflicts {
+                    let _ = app_exited.emit(
+                        "git-conflict",
+                        GitConflictPayload {
+                            run_id: run_id_exited.clone(),
+                            state,
+                        },
+                    );
+                }
+                let _ = app_exited.emit(
+                    "script-exited",
+                    ScriptExitedPayload {
+                        run_id: run_id_exited.clone(),
+                        label: run_label_clone.clone(),
+                        exit_code,
+                    },
+                );
+                break;
+            }
+            if let Ok(entries) = std::fs::read_dir(askpass.dir.path()) {
+                for entry in entries.flatten() {
+                    let name = entry.file_name().to_string_lossy().to_string();
+                    let Some(request_id) = name.strip_suffix(".prompt") else {
+                        continue;
+                    };
+                    if !seen_prompts.insert(request_id.to_string()) {
+                        continue;
+                    }
+                    let prompt = std::fs::read_to_string(entry.path()).unwrap_or_default();
+                    let _ = app_exited.emit(
+                        "git-credential-request",
+                        GitCredentialRequestPayload {
+                            run_id: run_id_exited.clone(),
+                            request_id: request_id.to_string(),
+                            prompt,
+                        },
+                    );
+                }
+            }
+            thread::sleep(std::time::Duration::from_millis(500));
+        }
+        if let Ok(mut dirs) = askpass_dirs.lock() {
+            dirs.remove(&run_id_exited);
+        }
+        drop(askpass);
+    });
+
+    Ok(())
+}
+
+fn start_git_network_run(
+    app: AppHandle,
+    state: State<'_, RunningState>,
+    project_path: &str,
+    op: &str,
+    timeout_secs: Option<u64>,
+) -> AppResult<RunIdResponse> {
+    let path_buf = validate_git_repo(project_path)?;
+    let run_id = gen_run_id();
+    let label = format!(
+        "git {}: {}",
+        op,
+        path_buf.file_name().and_then(|n| n.to_str()).unwrap_or("project")
+    );
+    let git_args = vec![op.to_string(), "--progress".to_string()];
+    let timeout = timeout_secs.filter(|s| *s > 0).unwrap_or(GIT_NETWORK_TIMEOUT_SECS);
+    run_git_network_inner(app, state, path_buf, run_id.clone(), label, git_args, timeout)?;
+    Ok(RunIdResponse { run_id })
+}
+
+/// Start `git fetch --progress` as a supervised run. Progress and credential prompts arrive as events.
+#[tauri::command]
+async fn git_fetch(
+    app: AppHandle,
+    state: State<'_, RunningState>,
+    project_path: String,
+    timeout_secs: Option<u64>,
+) -> AppResult<RunIdResponse> {
+    start_git_network_run(app, state, &project_path, "fetch", timeout_secs)
+}
+
+/// Start `git pull --progress` as a supervised run.
+#[tauri::command]
+async fn git_pull(
+    app: AppHandle,
+    state: State<'_, RunningState>,
+    project_path: String,
+    timeout_secs: Option<u64>,
+) -> AppResult<RunIdResponse> {
+    start_git_network_run(app, state, &project_path, "pull", timeout_secs)
+}
+
+/// Start `git push --progress` as a supervised run.
+#[tauri::command]
+async fn git_push(
+    app: AppHandle,
+    state: State<'_, RunningState>,
+    project_path: String,
+    timeout_secs: Option<u64>,
+) -> AppResult<RunIdResponse> {
+    start_git_network_run(app, state, &project_path, "push", timeout_secs)
+}
+
+/// Answer a pending `git-credential-request`. `answer: None` cancels the prompt and git fails authentication.
+#[tauri::command]
+fn answer_git_prompt(
+    state: State<'_, RunningState>,
+    run_id: String,
+    request_id: String,
+    answer: Option<String>,
+) -> AppResult<()> {
+    let request_id = request_id.trim();
+    if request_id.is_empty() || !request_id.chars().all(|c| c.is_ascii_alphanumeric() || c == '-') {
+        return Err(AppError::invalid("Invalid request id"));
+    }
+    let dir = state
+        .askpass_dirs
+        .lock()?
+        .get(run_id.trim())
+        .cloned()
+        .ok_or_else(|| AppError::not_found("Git run not found or already finished"))?;
+    answer_git_askpass(&dir, request_id, answer.as_deref())
+}
+
+#[tauri::command]
+fn git_commit(project_path: String, message: String) -> AppResult<String> {
+    let path_buf = validate_git_repo(&project_path)?;
+    let msg = message.trim();
+    if msg.is_empty() {
+        return Err(AppError::invalid("Commit message cannot be empty"));
+    }
+    run_git(&path_buf, &["add", "-A"])?;
+    run_git(&path_buf, &["commit", "-m", msg])
+}
+
+/// One submodule from `git submodule status --recursive`.
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct GitSubmodule {
+    pub path: String,
+    pub name: Option<String>,
+    pub url: Option<String>,
+    pub commit: String,
+    /// "up_to_date", "uninitialized", "modified" (checked-out commit differs from the recorded one) or "conflict".
+    pub status: String,
+    /// `git describe` of the checked-out commit, when available.
+    pub describe: Option<String>,
+}
+
+fn parse_submodule_status_line(line: &str) -> Option<GitSubmodule> {
+    // run_git trims output, so the first line may have lost its leading ' ' flag.
+    let (flag, rest) = match line.chars().next()? {
+        c @ ('-' | '+' | 'U' | ' ') => (c, line.get(1..)?.trim()),
+        _ => (' ', line.trim()),
+    };
+    let (commit, rest) = rest.split_once(' ')?;
+    let rest = rest.trim();
+    let (path, describe) = match rest.rfind(" (") {
+        Some(i) if rest.ends_with(')') => (&rest[..i], Some(rest[i + 2..rest.len() - 1].to_string())),
+        _ => (rest, None),
+    };
+    let status = match flag {
+        '-' => "uninitialized",
+        '+' => "modified",
+        'U' => "conflict",
+        _ => "up_to_date",
+    };
+    Some(GitSubmodule {
+        path: path.to_string(),
+        name: None,
+        url: None,
+        commit: commit.to_string(),
+        status: status.to_string(),
+        describe,
+    })
+}
+
+/// Submodules of the project's repository (recursive) with their checkout status and configured URL.
+#[tauri::command]
+fn list_submodules(project_path: String) -> AppResult<Vec<GitSubmodule>> {
+    let repo = validate_git_repo(&project_path)?;
+    let loc = discover_git_repo(&repo).ok_or_else(|| AppError::not_a_git_repo("Not a git repository"))?;
+    let toplevel = loc.toplevel;
+    if !toplevel.join(".gitmodules").is_file() {
+        return Ok(vec![]);
+    }
+    // submodule.<name>.path <path> — the name can differ from the path.
+    let mut names_by_path: HashMap<String, String> = HashMap::new();
+    if let Ok(out) = run_git(&toplevel, &["config", "-f", ".gitmodules", "--get-regexp", r"^submodule\..*\.path$"]) {
+        for line in out.lines() {
+            if let Some((key, path)) = line.split_once(' ') {
+                let name = key.trim_start_matches("submodule.").trim_end_matches(".path");
+                names_by_path.insert(path.trim().to_string(), name.to_string());
+            }
+        }
+    }
+    let out = run_git(&toplevel, &["submodule", "status", "--recursive"])?;
+    let mut subs: Vec<GitSubmodule> = out.lines().filter_map(parse_submodule_status_line).collect();
+    for sub in subs.iter_mut() {
+        sub.name = names_by_path.get(&sub.path).cloned();
+        if let Some(name) = sub.name.as_deref() {
+            sub.url = run_git(&toplevel, &["config", "-f", ".gitmodules", "--get", &format!("submodule.{}.url", name)]).ok();
+        }
+    }
+    Ok(subs)
+}
+
+/// `git submodule update` (with --init and --recursive by default; `remote` pulls the tracked branch instead of the
+/// recorded commit). Runs without credential prompts so it fails instead of hanging.
+#[tauri::command]
+async fn update_submodules(
+    project_path: String,
+    init: Option<bool>,
+    recursive: Option<bool>,
+    remote: Option<bool>,
+) -> AppResult<String> {
+    let repo = validate_git_repo(&project_path)?;
+    let loc = discover_git_repo(&repo).ok_or_else(|| AppError::not_a_git_repo("Not a git repository"))?;
+    let mut args = vec!["submodule", "update"];
+    if init.unwrap_or(true) {
+        args.push("--init");
+    }
+    if recursive.unwrap_or(true) {
+        args.push("--recursive");
+    }
+    if remote.unwrap_or(false) {
+        args.push("--remote");
+    }
+    run_git_noninteractive(&loc.toplevel, &args, GIT_NETWORK_TIMEOUT_SECS)
+}
+
+/// Default commit template. Placeholders: {type} {scope} {scope_paren} {subject} {ticket} {ticket_number} {refs} {summary} {files}.
+const DEFAULT_COMMIT_TEMPLATE: &str = "{type}{scope_paren}: {subject}\n\n{summary}\n\n{refs}";
+
+/// Directories that only group code; the scope is taken one level below them.
+const COMMIT_SCOPE_CONTAINER_DIRS: &[&str] = &["src", "lib", "app", "packages", "crates"];
+
+/// Suggested Conventional Commits message plus the parts it was built from.
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct CommitMessageSuggestion {
+    pub message: String,
+    #[serde(rename = "type")]
+    pub commit_type: String,
+    pub scope: Option<String>,
+    pub subject: String,
+    #[ts(as = "Option<f64>")]
+    pub ticket_number: Option<i64>,
+    pub files: Vec<String>,
+}
+
+/// Files that the next git_commit would include: the staged set, or every changed file when nothing is staged (git_commit runs `add -A`).
+fn commit_candidate_files(repo: &Path) -> AppResult<Vec<String>> {
+    let staged = run_git(&repo.to_path_buf(), &["diff", "--cached", "--name-only"])?;
+    let mut files: Vec<String> = staged.lines().map(|l| l.trim().to_string()).filter(|l| !l.is_empty()).collect();
+    if files.is_empty() {
+        let status = run_git(&repo.to_path_buf(), &["status", "--porcelain"])?;
+        files = status
+            .lines()
+            .filter_map(|l| l.get(3..))
+            .map(|p| p.rsplit(" -> ").next().unwrap_or(p).trim_matches('"').to_string())
+            .filter(|p| !p.is_empty())
+            .collect();
+    }
+    Ok(files)
+}
+
+/// Most common top-level directory among `files`, descending one level into generic containers like src/.
+fn commit_scope_from_files(files: &[String]) -> Option<String> {
+    let mut counts: HashMap<String, usize> = HashMap::new();
+    for f in files {
+        let parts: Vec<&str> = f.split('/').collect();
+        if parts.len() < 2 {
+            continue;
+        }
+        let dir = if COMMIT_SCOPE_CONTAINER_DIRS.contains(&parts[0]) && parts.len() > 2 {
+            parts[1]
+        } else {
+            parts[0]
+        };
+        *counts.entry(dir.trim_start_matches('.').to_lowercase()).or_insert(0) += 1;
+    }
+    counts
+        .into_iter()
+        .filter(|(d, _)| !d.is_empty())
+        .max_by(|a, b| a.1.cmp(&b.1).then_with(|| b.0.cmp(&a.0)))
+        .map(|(d, _)| d)
+}
+
+/// Endings a word may add to a commit type keyword and still count as it ("tests", "fixed", "crashes", "refactoring").
+const COMMIT_KEYWORD_SUFFIXES: &[&str] = &["", "s", "es", "d", "ed", "ing", "ings"];
+
+/// Conventional Commits type from ticket feature/title keywords, then priority (P0 = fix), else from the changed files.
+fn commit_type_for(feature: &str, title: &str, priority: &str, files: &[String], has_ticket: bool) -> String {
+    let text = format!("{} {}", feature, title).to_lowercase();
+    let keyword_types: &[(&[&str], &str)] = &[
+        (&["bug", "fix", "hotfix", "crash", "regression"], "fix"),
+        (&["doc", "docs", "documentation", "document", "readme"], "docs"),
+        (&["test", "e2e", "coverage"], "test"),
+        (&["refactor", "cleanup", "clean up"], "refactor"),
+        (&["perf", "performance", "speed"], "perf"),
+        (&["ci", "pipeline"], "ci"),
+        (&["build"], "build"),
+    ];
+    let words: Vec<&str> = text.split(|c: char| !c.is_alphanumeric()).filter(|w| !w.is_empty()).collect();
+    for (keywords, ty) in keyword_types {
+        let matches = |k: &&str| {
+            if k.contains(' ') {
+                return text.contains(*k);
+            }
+            words.iter().any(|w| w.strip_prefix(*k).is_some_and(|rest| COMMIT_KEYWORD_SUFFIXES.contains(&rest)))
+        };
+        if keywords.iter().any(matches) {
+            return ty.to_string();
+        }
+    }
+    if has_ticket {
+        return if priority == "P0" { "fix" } else { "feat" }.to_string();
+    }
+    if !files.is_empty() && files.iter().all(|f| f.ends_with(".md")) {
+        return "docs".to_string();
+    }
+    if !files.is_empty() && files.iter().all(|f| f.contains("test") || f.contains("spec")) {
+        return "test".to_string();
+    }
+    "chore".to_string()
+}
+
+/// Fill `{key}` placeholders in one pass, so placeholder-like text inside a value (a ticket titled "{files}") stays as
+/// written. Unknown placeholders are kept.
+fn render_commit_template(template: &str, values: &[(&str, String)]) -> String {
+    let mut out = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(open) = rest.find('{') {
+        out.push_str(&rest[..open]);
+        let after = &rest[open + 1..];
+        let value = after
+            .find('}')
+            .and_then(|close| values.iter().find(|(k, _)| *k == &after[..close]).map(|(_, v)| (close, v)));
+        match value {
+            Some((close, v)) => {
+                out.push_str(v);
+                rest = &after[close + 1..];
+            }
+            None => {
+                out.push('{');
+                rest = after;
+            }
+        }
+    }
+    out.push_str(rest);
+    // Collapse the blank lines left by empty placeholders.
+    let mut lines: Vec<&str> = vec![];
+    for line in out.lines().map(str::trim_end) {
+        if line.is_empty() && lines.last().is_none_or(|l| l.is_empty()) {
+            continue;
+        }
+        lines.push(line);
+    }
+    lines.join("\n").trim().to_string()
+}
+
+/// Longest first line (the `type(scope): subject` header) of a suggested commit message.
+const COMMIT_HEADER_MAX_CHARS: usize = 72;
+
+/// Render `template` with `subject`, shortening the subject (ending it with "…") when the first line would be longer
+/// than COMMIT_HEADER_MAX_CHARS. Returns the message and the subject used.
+fn render_commit_message(template: &str, values: &[(&str, String)], subject: &str) -> (String, String) {
+    let render = |subject: &str| {
+        let mut all = values.to_vec();
+        all.push(("subject", subject.to_string()));
+        render_commit_template(template, &all)
+    };
+    let message = render(subject);
+    let header = message.lines().next().unwrap_or("").chars().count();
+    let subject_in_header = template.trim_start().lines().next().is_some_and(|l| l.contains("{subject}"));
+    if header <= COMMIT_HEADER_MAX_CHARS || !subject_in_header {
+        return (message, subject.to_string());
+    }
+    let keep = subject.chars().count().saturating_sub(header - COMMIT_HEADER_MAX_CHARS + 1);
+    let short = subject.chars().take(keep).collect::<String>().trim_end().to_string() + "…";
+    (render(&short), short)
+}
+
+#[derive(serde::Deserialize, TS)]
+#[ts(export)]
+struct SuggestCommitMessageArgs {
+    #[serde(alias = "projectId")]
+    project_id: String,
+    #[serde(alias = "ticketId", default)]
+    ticket_id: Option<String>,
+}
+
+/// Build a Conventional Commits message from the pending changes and the linked plan ticket (if any).
+#[tauri::command]
+fn suggest_commit_message(args: SuggestCommitMessageArgs) -> AppResult<CommitMessageSuggestion> {
+    let project_id = args.project_id.trim().to_string();
+    let (project, ticket, template) = with_db(|conn| {
+        let project = list_projects_impl(conn)?
+            .into_iter()
+            .find(|p| p.id == project_id)
+            .ok_or_else(|| AppError::not_found("Project not found"))?;
+        let ticket = match args.ticket_id.as_deref().map(str::trim).filter(|s| !s.is_empty()) {
+            Some(tid) => Some(db::get_plan_ticket(conn, &project_id, tid)?.ok_or_else(|| AppError::not_found("Ticket not found"))?),
+            None => None,
+        };
+        let template = db::get_commit_template(conn, &project_id)?;
+        Ok((project, ticket, template))
+    })?;
+    let repo = validate_git_repo(project.repo_path.as_deref().unwrap_or(""))?;
+    let files = commit_candidate_files(&repo)?;
+
+    let str_field = |f: fn(&db::PlanTicket) -> &str| ticket.as_ref().map(f).unwrap_or("").trim().to_string();
+    let ticket_number = ticket.as_ref().map(|t| t.number);
+    let title = str_field(|t| &t.title);
+    let feature = str_field(|t| &t.feature_name);
+    let commit_type = commit_type_for(&feature, &title, &str_field(|t| &t.priority), &files, ticket.is_some());
+    let scope = commit_scope_from_files(&files);
+    let summary = match ticket_number {
+        Some(n) => with_db(|conn| db::get_latest_implementation_summary(conn, &project_id, n))?.unwrap_or_default(),
+        None => String::new(),
+    };
+    let subject = if title.is_empty() {
+        match files.len() {
+            0 => "update project".to_string(),
+            1 => format!("update {}", files[0]),
+            n => format!("update {} files", n),
+        }
+    } else {
+        let mut chars = title.chars();
+        let first = chars.next().map(|c| c.to_lowercase().to_string()).unwrap_or_default();
+        format!("{}{}", first, chars.as_str()).trim_end_matches('.').to_string()
+    };
+    let ticket_ref = ticket_number.map(|n| format!("#{}", n)).unwrap_or_default();
+    let values = [
+        ("type", commit_type.clone()),
+        ("scope", scope.clone().unwrap_or_default()),
+        ("scope_paren", scope.as_ref().map(|s| format!("({})", s)).unwrap_or_default()),
+        ("ticket", ticket_ref.clone()),
+        ("ticket_number", ticket_number.map(|n| n.to_string()).unwrap_or_default()),
+        ("refs", if ticket_ref.is_empty() { String::new() } else { format!("Refs {}", ticket_ref) }),
+        ("summary", summary),
+        ("files", files.join("\n")),
+    ];
+    let (message, subject) =
+        render_commit_message(template.as_deref().unwrap_or(DEFAULT_COMMIT_TEMPLATE), &values, &subject);
+    Ok(CommitMessageSuggestion {
+        message,
+        commit_type,
+        scope,
+        subject,
+        ticket_number,
+        files,
+    })
+}
+
+/// Per-project commit message template used by suggest_commit_message (None = default template).
+#[tauri::command]
+fn get_commit_template(ProjectIdArg { project_id }: ProjectIdArg) -> AppResult<Option<String>> {
+    with_db(|conn| db::get_commit_template(conn, &project_id))
+}
+
+#[derive(serde::Deserialize, TS)]
+#[ts(export)]
+struct SetCommitTemplateArgs {
+    #[serde(alias = "projectId")]
+    project_id: String,
+    template: Option<String>,
+}
+
+/// Save the project's commit template; empty or null resets to the default.
+#[tauri::command]
+fn set_commit_template(args: SetCommitTemplateArgs) -> AppResult<()> {
+    with_db(|conn| db::set_commit_template(conn, &args.project_id, args.template.as_deref()))
+}
+
+/// First line of every hook we install; used to recognise (and only ever overwrite/remove) our own hook.
+const COMMIT_MSG_HOOK_MARKER: &str = "# kwcode commit-msg hook";
+
+/// commit-msg hook. In "append" mode a missing reference is taken from $KWCODE_TICKET, `git config kwcode.currentTicket`
+/// or the branch name (ticket-12, feature/12-foo); in "check" mode the commit is rejected instead.
+fn commit_msg_hook_script(mode: &str) -> String {
+    format!(
+        r#"#!/bin/sh
+{marker} (mode: {mode})
+msg_file="$1"
+first_line="$(head -n 1 "$msg_file")"
+case "$first_line" in
+  Merge*|Revert*|fixup!*|squash!*) exit 0 ;;
+esac
+if grep -Eq '(^|[^[:alnum:]&])#[0-9]+' "$msg_file"; then
+  exit 0
+fi
+if [ "{mode}" = "append" ]; then
+  ticket="${{KWCODE_TICKET:-$(git config --get kwcode.currentTicket)}}"
+  if [ -z "$ticket" ]; then
+    branch="$(git symbolic-ref --short -q HEAD)"
+    ticket="$(printf '%s' "$branch" | sed -nE 's#^(.*/)?(ticket-?)?([0-9]+)([-_].*)?$#\3#p')"
+  fi
+  if [ -n "$ticket" ]; then
+    printf '\nRefs #%s\n' "$ticket" >> "$msg_file"
+    exit 0
+  fi
+fi
+echo "commit-msg: message must reference a ticket, e.g. #12 (set one with: git config kwcode.currentTicket 12)" >&2
+exit 1
+"#,
+        marker = COMMIT_MSG_HOOK_MARKER,
+        mode = mode
+    )
+}
+
+/// Path of the repo's commit-msg hook, honouring core.hooksPath and linked worktrees.
+fn commit_msg_hook_path(repo: &PathBuf) -> AppResult<PathBuf> {
+    let hooks = run_git(repo, &["rev-parse", "--git-path", "hooks"])?;
+    let hooks = PathBuf::from(hooks.trim());
+    let hooks = if hooks.is_absolute() { hooks } else { repo.join(hooks) };
+    Ok(hooks.join("commit-msg"))
+}
+
+/// Install the opt-in commit-msg hook. mode: "check" (reject commits without #N) or "append" (add "Refs #N" when known).
+/// Refuses to replace a commit-msg hook that was not installed by the app.
+#[tauri::command]
+fn install_commit_msg_hook(project_path: String, mode: Option<String>) -> AppResult<String> {
+    let repo = validate_git_repo(&project_path)?;
+    let mode = mode.as_deref().map(str::trim).filter(|m| !m.is_empty()).unwrap_or("check");
+    if mode != "check" && mode != "append" {
+        return Err(AppError::invalid("mode must be 'check' or 'append'"));
+    }
+    let hook = commit_msg_hook_path(&repo)?;
+    if hook.exists() {
+        let existing = std::fs::read_to_string(&hook).unwrap_or_default();
+        if !existing.contains(COMMIT_MSG_HOOK_MARKER) {
+            return Err(AppError::conflict(format!(
+                "A commit-msg hook already exists and was not installed by this app: {}",
+                hook.to_string_lossy()
+            )));
+        }
+    }
+    if let Some(parent) = hook.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    std::fs::write(&hook, commit_msg_hook_script(mode))?;
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        std::fs::set_permissions(&hook, std::fs::Permissions::from_mode(0o755))?;
+    }
+    Ok(hook.to_string_lossy().to_string())
+}
+
+/// Remove the commit-msg hook if (and only if) it was installed by the app.
+#[tauri::command]
+fn uninstall_commit_msg_hook(project_path: String) -> AppResult<()> {
+    let repo = validate_git_repo(&project_path)?;
+    let hook = commit_msg_hook_path(&repo)?;
+    if !hook.exists() {
+        return Ok(());
+    }
+    let existing = std::fs::read_to_string(&hook).unwrap_or_default();
+    if !existing.contains(COMMIT_MSG_HOOK_MARKER) {
+        return Err(AppError::conflict("commit-msg hook was not installed by this app; leaving it in place"));
+    }
+    std::fs::remove_file(&hook).map_err(AppError::from)
+}
+
+/// Ticket the append-mode hook uses when the message has no reference (`git config kwcode.currentTicket`). None clears it.
+#[tauri::command]
+fn set_commit_ticket(project_path: String, ticket_number: Option<i64>) -> AppResult<()> {
+    let repo = validate_git_repo(&project_path)?;
+    match ticket_number {
+        Some(n) if n > 0 => run_git(&repo, &["config", "kwcode.currentTicket", &n.to_string()]).map(|_| ()),
+        Some(_) => Err(AppError::invalid("ticket_number must be positive")),
+        None => {
+            // `--unset` fails when the key is absent; that's fine.
+            let _ = run_git(&repo, &["config", "--unset", "kwcode.currentTicket"]);
+            Ok(())
+        }
+    }
+}
+
+const TICKET_CLOSING_KEYWORDS: &[&str] = &["close", "closes", "closed", "fix", "fixes", "fixed", "resolve", "resolves", "resolved"];
+
+/// Ticket references (#N) in a commit message. The bool is true when preceded by a closing keyword ("fixes #3").
+fn parse_ticket_refs(text: &str) -> Vec<(i64, bool)> {
+    let mut out: Vec<(i64, bool)> = vec![];
+    let bytes = text.as_bytes();
+    for (i, _) in text.match_indices('#') {
+        if i > 0 && (bytes[i - 1].is_ascii_alphanumeric() || bytes[i - 1] == b'&') {
+            continue;
+        }
+        let digits: String = text[i + 1..].chars().take_while(|c| c.is_ascii_digit()).collect();
+        let Ok(number) = digits.parse::<i64>() else {
+            continue;
+        };
+        let before = text[..i].trim_end().trim_end_matches(':').trim_end();
+        let prev_word = before
+            .rsplit(|c: char| !c.is_ascii_alphabetic())
+            .next()
+            .unwrap_or("")
+            .to_lowercase();
+        let closes = TICKET_CLOSING_KEYWORDS.contains(&prev_word.as_str());
+        match out.iter_mut().find(|(n, _)| *n == number) {
+            Some(existing) => existing.1 |= closes,
+            None => out.push((number, closes)),
+        }
+    }
+    out
+}
+
+/// Result of scan_commit_ticket_refs.
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct CommitScanResult {
+    pub commits_scanned: u32,
+    pub links_added: u32,
+    #[ts(as = "Vec<f64>")]
+    pub tickets_closed: Vec<i64>,
+    pub links: Vec<db::TicketCommitLink>,
+}
+
+#[derive(serde::Deserialize, TS)]
+#[ts(export)]
+struct ScanCommitTicketRefsArgs {
+    #[serde(alias = "projectId")]
+    project_id: String,
+    /// Revision range passed to git log (e.g. "v1.2..HEAD"). Default: the whole current branch.
+    #[serde(alias = "revRange", default)]
+    rev_range: Option<String>,
+    /// Mark tickets referenced with fixes/closes/resolves #N as Done.
+    #[serde(alias = "autoClose", default)]
+    auto_close: bool,
+}
+
+/// Scan `git log` for #N references and record commit hashes against plan tickets and implementation_log entries.
+#[tauri::command]
+fn scan_commit_ticket_refs(args: ScanCommitTicketRefsArgs) -> AppResult<CommitScanResult> {
+    let project_id = args.project_id.trim().to_string();
+    let project = with_db(|conn| {
+        list_projects_impl(conn)?
+            .into_iter()
+            .find(|p| p.id == project_id)
+            .ok_or(AppError::not_found("Project not found"))
+    })?;
+    let repo = validate_git_repo(project.repo_path.as_deref().unwrap_or(""))?;
+    let range = args.rev_range.as_deref().map(str::trim).filter(|r| !r.is_empty()).unwrap_or("HEAD");
+    if range.starts_with('-') {
+        return Err(AppError::invalid("Invalid revision range"));
+    }
+    // Fields separated by \x1f, commits by \x1e so multi-line bodies survive.
+    let log = run_git(&repo, &["log", "--format=%H%x1f%cI%x1f%s%x1f%b%x1e", range])?;
+    let known_numbers: HashSet<i64> = with_db(|conn| db::get_plan_tickets_for_project(conn, &project_id))?
+        .iter()
+        .map(|t| t.number)
+        .collect();
+
+    let mut commits_scanned = 0u32;
+    let mut links: Vec<db::TicketCommitLink> = vec![];
+    for record in log.split('\u{1e}') {
+        let fields: Vec<&str> = record.trim_start_matches('\n').splitn(4, '\u{1f}').collect();
+        if fields.len() < 3 || fields[0].trim().is_empty() {
+            continue;
+        }
+        commits_scanned += 1;
+        let subject = fields[2].to_string();
+        let message = format!("{}\n{}", fields[2], fields.get(3).copied().unwrap_or(""));
+        for (number, closes) in parse_ticket_refs(&message) {
+            if !known_numbers.contains(&number) {
+                continue;
+            }
+            links.push(db::TicketCommitLink {
+                project_id: project_id.clone(),
+                ticket_number: number,
+                commit_hash: fields[0].trim().to_string(),
+                subject: subject.clone(),
+                closes,
+                committed_at: fields[1].trim().to_string(),
+            });
+        }
+    }
+
+    with_db(|conn| {
+        let mut links_added = 0u32;
+        let mut tickets_closed: Vec<i64> = vec![];
+        for link in &links {
+            if db::record_ticket_commit(conn, link)? {
+                links_added += 1;
+            }
+            if args.auto_close
+                && link.closes
+                && !tickets_closed.contains(&link.ticket_number)
+                && db::close_plan_ticket_by_number(
+                    conn,
+                    &project_id,
+                    link.ticket_number,
+                    &db::Actor::commit(&link.commit_hash),
+                )?
+            {
+                tickets_closed.push(link.ticket_number);
+            }
+        }
+        Ok(CommitScanResult {
+            commits_scanned,
+            links_added,
+            tickets_closed,
+            links: links.clone(),
+        })
+    })
+}
+
+/// Commits recorded against one plan ticket (see scan_commit_ticket_refs).
+#[tauri::command]
+fn get_plan_ticket_commits(project_id: String, ticket_id: String) -> AppResult<Vec<db::TicketCommitLink>> {
+    with_db(|conn| {
+        let ticket = db::get_plan_ticket(conn, &project_id, &ticket_id)?.ok_or_else(|| AppError::not_found("Ticket not found"))?;
+        db::get_ticket_commits(conn, &project_id, ticket.number)
+    })
+}
+
+/// One line of release notes: a done ticket and/or accepted implementation_log entries for it.
+#[derive(Debug, Clone)]
+struct ReleaseNoteItem {
+    number: i64,
+    title: String,
+    feature: String,
+    priority: String,
+    commit_hashes: Vec<String>,
+    summary: Option<String>,
+}
+
+fn release_note_line(item: &ReleaseNoteItem, with_feature: bool) -> String {
+    let mut line = if with_feature {
+        format!("- **{}**: {} (#{})", item.feature, item.title, item.number)
+    } else {
+        format!("- {} (#{}, {})", item.title, item.number, item.priority)
+    };
+    if !item.commit_hashes.is_empty() {
+        let short: Vec<String> = item.commit_hashes.iter().map(|h| h.chars().take(7).collect()).collect();
+        line.push_str(&format!(" — {}", short.join(", ")));
+    }
+    if let Some(summary) = item.summary.as_deref().filter(|s| !s.trim().is_empty()) {
+        line.push_str(&format!("\n  {}", summary.trim().replace('\n', "\n  ")));
+    }
+    line
+}
+
+/// Plain Markdown: one section per feature, tickets ordered by priority.
+fn render_release_notes_markdown(title: &str, date: &str, items: &[ReleaseNoteItem]) -> String {
+    let mut out = format!("## {} ({})\n", title, date);
+    let mut features: Vec<&str> = items.iter().map(|i| i.feature.as_str()).collect();
+    features.sort();
+    features.dedup();
+    for feature in features {
+        out.push_str(&format!("\n### {}\n\n", feature));
+        for item in items.iter().filter(|i| i.feature == feature) {
+            out.push_str(&release_note_line(item, false));
+            out.push('\n');
+        }
+    }
+    out
+}
+
+/// Keep a Changelog (keepachangelog.com): Added / Fixed / Changed, inferred like commit types.
+fn render_release_notes_keep_a_changelog(version: &str, date: &str, items: &[ReleaseNoteItem]) -> String {
+    let mut out = format!("## [{}] - {}\n", version, date);
+    for (section, types) in [
+        ("Added", &["feat"][..]),
+        ("Fixed", &["fix"][..]),
+        ("Changed", &["docs", "test", "refactor", "perf", "build", "chore"][..]),
+    ] {
+        let section_items: Vec<&ReleaseNoteItem> = items
+            .iter()
+            .filter(|i| types.contains(&commit_type_for(&i.feature, &i.title, &i.priority, &[], true).as_str()))
+            .collect();
+        if section_items.is_empty() {
+            continue;
+        }
+        out.push_str(&format!("\n### {}\n\n", section));
+        for item in section_items {
+            out.push_str(&release_note_line(item, true));
+            out.push('\n');
+        }
+    }
+    out
+}
+
+/// Insert `notes` into CHANGELOG.md below its header (before the first "## " section), creating the file if needed.
+fn prepend_to_changelog(repo: &Path, notes: &str) -> AppResult<PathBuf> {
+    let path = repo.join("CHANGELOG.md");
+    let existing = if path.exists() {
+        std::fs::read_to_string(&path)?
+    } else {
+        "# Changelog\n\nAll notable changes to this project are documented in this file.\n".to_string()
+    };
+    let insert_at = existing
+        .match_indices("\n## ")
+        .next()
+        .map(|(i, _)| i + 1)
+        .unwrap_or(existing.len());
+    let (head, tail) = existing.split_at(insert_at);
+    let mut content = head.trim_end().to_string();
+    content.push_str("\n\n");
+    content.push_str(notes.trim_end());
+    content.push('\n');
+    if !tail.trim().is_empty() {
+        content.push('\n');
+        content.push_str(tail);
+    }
+    std::fs::write(&path, content)?;
+    Ok(path)
+}
+
+#[derive(serde::Deserialize, TS)]
+#[ts(export)]
+struct GenerateReleaseNotesArgs {
+    #[serde(alias = "projectId")]
+    project_id: String,
+    #[serde(alias = "milestoneId", default)]
+    #[ts(as = "Option<f64>")]
+    milestone_id: Option<i64>,
+    /// Ref range alternative to milestone_id: commits in from_ref..to_ref (to_ref defaults to HEAD).
+    #[serde(alias = "fromRef", default)]
+    from_ref: Option<String>,
+    #[serde(alias = "toRef", default)]
+    to_ref: Option<String>,
+    /// "markdown" (default) or "keep-a-changelog".
+    #[serde(default)]
+    format: Option<String>,
+    /// Heading / version label; defaults to the milestone name or to_ref.
+    #[serde(default)]
+    version: Option<String>,
+    #[serde(alias = "writeChangelog", default)]
+    write_changelog: bool,
+    /// Create an annotated tag with this name whose message is the release notes.
+    #[serde(default)]
+    tag: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct ReleaseNotes {
+    pub content: String,
+    pub format: String,
+    pub ticket_count: u32,
+    pub changelog_path: Option<String>,
+    pub tag: Option<String>,
+}
+
+/// Release notes from done plan tickets and accepted implementation_log entries, for a milestone or a ref range.
+/// Optionally prepends them to CHANGELOG.md (not committed) and creates an annotated tag.
+#[tauri::command]
+fn generate_release_notes(args: GenerateReleaseNotesArgs) -> AppResult<ReleaseNotes> {
+    let project_id = args.project_id.trim().to_string();
+    let format = match args.format.as_deref().map(str::trim).unwrap_or("markdown") {
+        "" | "markdown" | "md" => "markdown",
+        "keep-a-changelog" | "keepachangelog" => "keep-a-changelog",
+        other => return Err(AppError::invalid(format!("Unknown format: {}", other))),
+    };
+    let from_ref = args.from_ref.as_deref().map(str::trim).filter(|r| !r.is_empty());
+    let to_ref = args.to_ref.as_deref().map(str::trim).filter(|r| !r.is_empty()).unwrap_or("HEAD");
+    if args.milestone_id.is_none() && from_ref.is_none() {
+        return Err(AppError::invalid("milestone_id or from_ref is required"));
+    }
+    if from_ref.is_some_and(|r| r.starts_with('-')) || to_ref.starts_with('-') {
+        return Err(AppError::invalid("Invalid revision"));
+    }
+    let (project, tickets, log_entries, milestone_name) = with_db(|conn| {
+        let project = list_projects_impl(conn)?
+            .into_iter()
+            .find(|p| p.id == project_id)
+            .ok_or_else(|| AppError::not_found("Project not found"))?;
+        let tickets = db::get_plan_tickets_for_project(conn, &project_id)?;
+        let entries = query_implementation_log(conn, &project_id)?;
+        let milestone_name = match args.milestone_id {
+            Some(mid) => Some(
+                db::get_milestones_for_project(conn, &project_id)?
+                    .into_iter()
+                    .find(|m| m.id == mid)
+                    .map(|m| m.name)
+                    .ok_or_else(|| AppError::not_found("Milestone not found"))?,
+            ),
+            None => None,
+        };
+        Ok((project, tickets, entries, milestone_name))
+    })?;
+    let repo_path = project.repo_path.as_deref().unwrap_or("");
+    let tag_name = args.tag.as_deref().map(str::trim).filter(|t| !t.is_empty());
+    let needs_repo = from_ref.is_some() || args.write_changelog || tag_name.is_some();
+    let repo = if needs_repo { Some(validate_git_repo(repo_path)?) } else { None };
+    if let (Some(name), Some(repo)) = (tag_name, repo.as_ref()) {
+        let ref_name = format!("refs/tags/{}", name);
+        if name.starts_with('-') || run_git(repo, &["check-ref-format", &ref_name]).is_err() {
+            return Err(AppError::invalid(format!("Invalid tag name: {}", name)));
+        }
+        if run_git(repo, &["rev-parse", "--verify", "--quiet", &ref_name]).is_ok() {
+            return Err(AppError::conflict(format!("Tag {} already exists", name)));
+        }
+    }
+
+    // Ticket number -> commit hashes, from the range's git log or from recorded links.
+    let mut hashes_by_ticket: HashMap<i64, Vec<String>> = HashMap::new();
+    if let (Some(from), Some(repo)) = (from_ref, repo.as_ref()) {
+        let range = format!("{}..{}", from, to_ref);
+        let log = run_git(repo, &["log", "--format=%H%x1f%s%x1f%b%x1e", &range])?;
+        for record in log.split('\u{1e}') {
+            let fields: Vec<&str> = record.trim_start_matches('\n').splitn(3, '\u{1f}').collect();
+            if fields.len() < 2 || fields[0].trim().is_empty() {
+                continue;
+            }
+            let message = format!("{}\n{}", fields[1], fields.get(2).copied().unwrap_or(""));
+            for (number, _) in parse_ticket_refs(&message) {
+                hashes_by_ticket.entry(number).or_default().push(fields[0].trim().to_string());
+            }
+        }
+    } else {
+        with_db(|conn| {
+            for t in &tickets {
+                let links = db::get_ticket_commits(conn, &project_id, t.number)?;
+                if !links.is_empty() {
+                    hashes_by_ticket.insert(t.number, links.into_iter().map(|l| l.commit_hash).collect());
+                }
+            }
+            Ok(())
+        })?;
+    }
+    let in_scope = |number: i64, milestone_id: Option<i64>| match args.milestone_id {
+        Some(mid) => milestone_id == Some(mid),
+        None => hashes_by_ticket.contains_key(&number),
+    };
+
+    let mut items: Vec<ReleaseNoteItem> = vec![];
+    for t in &tickets {
+        if !t.done || !in_scope(t.number, t.milestone_id) {
+            continue;
+        }
+        let field = |v: &str, default: &str| {
+            let v = v.trim();
+            if v.is_empty() { default } else { v }.to_string()
+        };
+        items.push(ReleaseNoteItem {
+            number: t.number,
+            title: field(&t.title, ""),
+            feature: field(&t.feature_name, "General"),
+            priority: field(&t.priority, "P1"),
+            commit_hashes: hashes_by_ticket.get(&t.number).cloned().unwrap_or_default(),
+            summary: None,
+        });
+    }
+    // Accepted implementation_log entries add summaries, hashes, and tickets that are no longer on the board.
+    for entry in log_entries.iter().filter(|e| e.status == "accepted") {
+        if !in_scope(entry.ticket_number, entry.milestone_id) {
+            continue;
+        }
+        let idx = match items.iter().position(|i| i.number == entry.ticket_number) {
+            Some(idx) => idx,
+            None => {
+                items.push(ReleaseNoteItem {
+                    number: entry.ticket_number,
+                    title: entry.ticket_title.clone(),
+                    feature: "General".to_string(),
+                    priority: "P1".to_string(),
+                    commit_hashes: hashes_by_ticket.get(&entry.ticket_number).cloned().unwrap_or_default(),
+                    summary: None,
+                });
+                items.len() - 1
+            }
+        };
+        let item = &mut items[idx];
+        if item.summary.is_none() && !entry.summary.trim().is_empty() {
+            item.summary = Some(entry.summary.clone());
+        }
+        if let Some(hash) = entry.commit_hash.as_ref() {
+            if !item.commit_hashes.contains(hash) {
+                item.commit_hashes.push(hash.clone());
+            }
+        }
+    }
+    items.sort_by(|a, b| {
+        a.feature
+            .cmp(&b.feature)
+            .then_with(|| a.priority.cmp(&b.priority))
+            .then_with(|| a.number.cmp(&b.number))
+    });
+
+    let version = args
+        .version
+        .as_deref()
+        .map(str::trim)
+        .filter(|v| !v.is_empty())
+        .map(String::from)
+        .or_else(|| args.tag.clone())
+        .or(milestone_name)
+        .unwrap_or_else(|| to_ref.to_string());
+    let date = chrono::Local::now().format("%Y-%m-%d").to_string();
+    let content = if format == "keep-a-changelog" {
+        render_release_notes_keep_a_changelog(&version, &date, &items)
+    } else {
+        render_release_notes_markdown(&version, &date, &items)
+    };
+
+    // Tag first: a refused tag must not leave CHANGELOG.md changed, and a failed changelog write removes the tag.
+    let mut tag = None;
+    if let (Some(name), Some(repo)) = (tag_name, repo.as_ref()) {
+        run_git(repo, &["tag", "-a", name, "-m", &content, to_ref])?;
+        tag = Some(name.to_string());
+    }
+    let mut changelog_path = None;
+    if args.write_changelog {
+        if let Some(repo) = repo.as_ref() {
+            match prepend_to_changelog(repo, &content) {
+                Ok(path) => changelog_path = Some(path.to_string_lossy().to_string()),
+                Err(e) => {
+                    if let Some(name) = tag.as_deref() {
+                        let _ = run_git(repo, &["tag", "-d", name]);
+                    }
+                    return Err(e);
+                }
+            }
+        }
+    }
+    Ok(ReleaseNotes {
+        content,
+        format: format.to_string(),
+        ticket_count: items.len() as u32,
+        changelog_path,
+        tag,
+    })
+}
+
+/// One unmerged path with the index blobs for each stage (1 = base, 2 = ours, 3 = theirs).
+#[derive(Debug, Clone, Serialize, Deserialize, Default, TS)]
+#[ts(export)]
+pub struct GitConflictFile {
+    pub path: String,
+    /// Porcelain XY code, e.g. "UU" (both modified), "AA" (both added), "DU" (deleted by us).
+    pub status: String,
+    pub base_blob: Option<String>,
+    pub ours_blob: Option<String>,
+    pub theirs_blob: Option<String>,
+}
+
+/// Operation the repo is in the middle of (merge / rebase / cherry-pick / revert) and its conflicted files.
+#[derive(Debug, Clone, Serialize, Deserialize, Default, TS)]
+#[ts(export)]
+pub struct GitConflictState {
+    pub operation: Option<String>,
+    pub files: Vec<GitConflictFile>,
+}
+
+/// Detect an in-progress merge, rebase, cherry-pick or revert from the git dir markers.
+fn git_operation_in_progress(repo: &PathBuf) -> AppResult<Option<String>> {
+    for (marker, op) in [
+        ("rebase-merge", "rebase"),
+        ("rebase-apply", "rebase"),
+        ("MERGE_HEAD", "merge"),
+        ("CHERRY_PICK_HEAD", "cherry-pick"),
+        ("REVERT_HEAD", "revert"),
+    ] {
+        let p = PathBuf::from(run_git(repo, &["rev-parse", "--git-path", marker])?.trim());
+        let p = if p.is_absolute() { p } else { repo.join(p) };
+        if p.exists() {
+            return Ok(Some(op.to_string()));
+        }
+    }
+    Ok(None)
+}
+
+/// Unmerged index entries from `git ls-files -u -z` ("<mode> <blob> <stage>\t<path>\0"), one file per path.
+fn parse_unmerged_entries(out: &str) -> Vec<GitConflictFile> {
+    let mut files: Vec<GitConflictFile> = vec![];
+    for entry in out.split('\0') {
+        let Some((meta, path)) = entry.split_once('\t') else {
+            continue;
+        };
+        let meta: Vec<&str> = meta.split_whitespace().collect();
+        if meta.len() < 3 {
+            continue;
+        }
+        let idx = match files.iter().position(|f| f.path == path) {
+            Some(i) => i,
+            None => {
+                files.push(GitConflictFile {
+                    path: path.to_string(),
+                    ..Default::default()
+                });
+                files.len() - 1
+            }
+        };
+        let blob = Some(meta[1].to_string());
+        match meta[2] {
+            "1" => files[idx].base_blob = blob,
+            "2" => files[idx].ours_blob = blob,
+            "3" => files[idx].theirs_blob = blob,
+            _ => {}
+        }
+    }
+    files
+}
+
+/// Set each file's XY code from `git status --porcelain -z` ("XY <path>\0"; renames and copies are followed by their
+/// source path as an extra entry).
+fn apply_porcelain_status(files: &mut [GitConflictFile], out: &str) {
+    let mut entries = out.split('\0');
+    while let Some(entry) = entries.next() {
+        let (Some(code), Some(path)) = (entry.get(..2), entry.get(3..)) else {
+            continue;
+        };
+        if code.contains('R') || code.contains('C') {
+            entries.next();
+        }
+        if let Some(f) = files.iter_mut().find(|f| f.path == path) {
+            f.status = code.to_string();
+        }
+    }
+}
+
+fn git_conflict_state_impl(repo: &PathBuf) -> AppResult<GitConflictState> {
+    let raw = |args: &[&str]| run_git_raw(repo, args).map(|out| String::from_utf8_lossy(&out).to_string());
+    let mut files = parse_unmerged_entries(&raw(&["ls-files", "-u", "-z"])?);
+    if !files.is_empty() {
+        apply_porcelain_status(&mut files, &raw(&["status", "--porcelain", "-z"])?);
+    }
+    Ok(GitConflictState {
+        operation: git_operation_in_progress(repo)?,
+        files,
+    })
+}
+
+/// Merge/rebase/cherry-pick in progress and the conflicted files with their base/ours/theirs blobs.
+#[tauri::command]
+fn get_git_conflict_state(project_path: String) -> AppResult<GitConflictState> {
+    let repo = validate_git_repo(&project_path)?;
+    git_conflict_state_impl(&repo)
+}
+
+/// Conflicts a pull run stopped on. Emitted as `git-conflict` right before the run's `script-exited`.
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct GitConflictPayload {
+    pub run_id: String,
+    pub state: GitConflictState,
+}
+
+/// Conflict error for a failed `op` (pull, merge) that left unmerged paths; None when the index has none.
+fn unmerged_paths_error(repo: &PathBuf, op: &str) -> Option<AppError> {
+    let state = git_conflict_state_impl(repo).ok().filter(|s| !s.files.is_empty())?;
+    let paths: Vec<&str> = state.files.iter().map(|f| f.path.as_str()).collect();
+    Some(
+        AppError::conflict(format!("{} stopped with conflicts in {} file(s): {}", op, paths.len(), paths.join(", ")))
+            .with_details(serde_json::json!(state)),
+    )
+}
+
+/// A repo-relative path that cannot escape the repository.
+fn validate_repo_relative_path(path: &str) -> AppResult<&str> {
+    let path = path.trim();
+    if path.is_empty() {
+        return Err(AppError::invalid("File path is empty"));
+    }
+    let p = Path::new(path);
+    if p.is_absolute() || p.components().any(|c| matches!(c, std::path::Component::ParentDir)) {
+        return Err(AppError::invalid("File path must be relative to the repository"));
+    }
+    Ok(path)
+}
+
+/// Contents of each side of a conflicted file, byte for byte. Missing stages (e.g. deleted by them) and sides that are
+/// not UTF-8 text are None.
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct GitConflictFileVersions {
+    pub path: String,
+    pub base: Option<String>,
+    pub ours: Option<String>,
+    pub theirs: Option<String>,
+    /// Working tree file with conflict markers.
+    pub working: Option<String>,
+}
+
+#[tauri::command]
+fn get_conflict_file_versions(project_path: String, path: String) -> AppResult<GitConflictFileVersions> {
+    let repo = validate_git_repo(&project_path)?;
+    let path = validate_repo_relative_path(&path)?;
+    let stage = |n: u8| {
+        run_git_raw(&repo, &["show", &format!(":{}:{}", n, path)])
+            .ok()
+            .and_then(|bytes| String::from_utf8(bytes).ok())
+    };
+    Ok(GitConflictFileVersions {
+        path: path.to_string(),
+        base: stage(1),
+        ours: stage(2),
+        theirs: stage(3),
+        working: std::fs::read_to_string(repo.join(path)).ok(),
+    })
+}
+
+/// Resolve one conflicted file: `strategy` "ours" / "theirs" / "base" takes that side, or `content` is written as the result.
+/// The file is then staged (or removed when the chosen side deleted it).
+#[tauri::command]
+fn resolve_conflict(
+    project_path: String,
+    path: String,
+    strategy: Option<String>,
+    content: Option<String>,
+) -> AppResult<GitConflictState> {
+    let repo = validate_git_repo(&project_path)?;
+    let path = validate_repo_relative_path(&path)?;
+    let state = git_conflict_state_impl(&repo)?;
+    let file = state
+        .files
+        .iter()
+        .find(|f| f.path == path)
+        .ok_or_else(|| AppError::invalid("File is not in conflict"))?;
+    match (strategy.as_deref().map(str::trim).filter(|s| !s.is_empty()), content) {
+        (Some(_), Some(_)) => return Err(AppError::invalid("Pass either strategy or content, not both")),
+        (None, None) => return Err(AppError::invalid("strategy or content is required")),
+        (None, Some(text)) => {
+            std::fs::write(repo.join(path), text)?;
+            run_git(&repo, &["add", "--", path])?;
+        }
+        (Some(strategy), None) => {
+            let (stage, blob) = match strategy {
+                "base" => (1, &file.base_blob),
+                "ours" => (2, &file.ours_blob),
+                "theirs" => (3, &file.theirs_blob),
+                _ => return Err(AppError::invalid("strategy must be 'ours', 'theirs' or 'base'")),
+            };
+            if blob.is_some() {
+                let bytes = run_git_raw(&repo, &["show", &format!(":{}:{}", stage, path)])?;
+                std::fs::write(repo.join(path), bytes)?;
+                run_git(&repo, &["add", "--", path])?;
+            } else {
+                // The chosen side deleted the file.
+                run_git(&repo, &["rm", "--quiet", "--force", "--", path])?;
+            }
+        }
+    }
+    git_conflict_state_impl(&repo)
 }
 
+/// Abort the in-progress merge / rebase / cherry-pick / revert and restore the pre-operation state.
 #[tauri::command]
-fn get_git_file_view(project_path: String, file_path: String) -> Result<GitFileView, String> {
-    let path_buf = PathBuf::from(project_path.trim());
-    if !path_buf.exists() || !path_buf.is_dir() {
-        return Err("Project path does not exist".to_string());
+fn git_merge_abort(project_path: String) -> AppResult<String> {
+    let repo = validate_git_repo(&project_path)?;
+    let op = git_operation_in_progress(&repo)?.ok_or_else(|| AppError::conflict("No merge, rebase, cherry-pick or revert in progress"))?;
+    run_git(&repo, &[op.as_str(), "--abort"])
+}
+
+/// Finish the in-progress operation once every conflict is resolved. Uses the prepared message (no editor).
+#[tauri::command]
+fn git_merge_continue(project_path: String) -> AppResult<String> {
+    let repo = validate_git_repo(&project_path)?;
+    let state = git_conflict_state_impl(&repo)?;
+    let op = state.operation.ok_or_else(|| AppError::conflict("No merge, rebase, cherry-pick or revert in progress"))?;
+    if !state.files.is_empty() {
+        return Err(AppError::conflict(format!("{} file(s) still have conflicts", state.files.len())));
+    }
+    if op == "merge" {
+        run_git(&repo, &["commit", "--no-edit"])
+    } else {
+        run_git(&repo, &["-c", "core.editor=true", op.as_str(), "--continue"])
     }
-    let git_dir = path_buf.join(".git");
-    if !git_dir.exists() {
-        return Err("Not a git repository".to_string());
+}
+
+/// Maximum prompt bytes of conflict hunks per file; huge generated files would drown the agent prompt.
+const CONFLICT_PROMPT_MAX_HUNK_CHARS: usize = 6000;
+
+/// The `<<<<<<< ... >>>>>>>` regions of a file with conflict markers.
+fn extract_conflict_hunks(content: &str) -> Vec<String> {
+    let mut hunks = vec![];
+    let mut current: Option<Vec<&str>> = None;
+    for line in content.lines() {
+        if line.starts_with("<<<<<<<") {
+            current = Some(vec![line]);
+        } else if let Some(lines) = current.as_mut() {
+            lines.push(line);
+            if line.starts_with(">>>>>>>") {
+                hunks.push(lines.join("\n"));
+                current = None;
+            }
+        }
     }
-    let file_path = file_path.trim();
-    if file_path.is_empty() {
-        return Err("File path is empty".to_string());
+    hunks
+}
+
+fn conflict_resolution_prompt(repo: &Path, state: &GitConflictState) -> String {
+    let op = state.operation.as_deref().unwrap_or("merge");
+    let mut prompt = format!(
+        "This repository is in the middle of a git {op} with {} conflicted file(s). Resolve every conflict:\n\
+         keep the intent of both sides where possible, remove all conflict markers, make sure the code builds,\n\
+         and stage each resolved file with `git add`. Do not run `git {op} --continue` or `--abort`.\n",
+        state.files.len(),
+        op = op
+    );
+    for f in &state.files {
+        prompt.push_str(&format!("\n## {} ({})\n", f.path, f.status));
+        let content = std::fs::read_to_string(repo.join(&f.path)).unwrap_or_default();
+        let hunks = extract_conflict_hunks(&content).join("\n\n");
+        if hunks.is_empty() {
+            prompt.push_str("(no text conflict markers; one side modified and the other deleted, or a binary file)\n");
+        } else if hunks.len() > CONFLICT_PROMPT_MAX_HUNK_CHARS {
+            let cut: String = hunks.chars().take(CONFLICT_PROMPT_MAX_HUNK_CHARS).collect();
+            prompt.push_str(&format!("```\n{}\n... (truncated)\n```\n", cut));
+        } else {
+            prompt.push_str(&format!("```\n{}\n```\n", hunks));
+        }
     }
-    let full_path = path_buf.join(file_path);
-    let exists = full_path.exists() && full_path.is_file();
+    prompt
+}
 
-    let mut diff_parts = vec![];
-    if let Ok(staged) = run_git(&path_buf, &["diff", "--staged", "--", file_path]) {
-        if !staged.is_empty() {
-            diff_parts.push(format!("=== Staged changes ===\n{}\n", staged));
+/// Start a debug-mode terminal agent whose prompt is pre-filled with the conflict hunks.
+#[tauri::command]
+async fn run_conflict_resolution_agent(
+    app: AppHandle,
+    state: State<'_, RunningState>,
+    project_path: String,
+) -> AppResult<RunIdResponse> {
+    let repo = validate_git_repo(&project_path)?;
+    let conflicts = git_conflict_state_impl(&repo)?;
+    if conflicts.files.is_empty() {
+        return Err(AppError::conflict("No conflicted files"));
+    }
+    let prompt_content = conflict_resolution_prompt(&repo, &conflicts);
+    run_run_terminal_agent(
+        app,
+        state,
+        RunTerminalAgentArgs {
+            project_path: repo.to_string_lossy().to_string(),
+            prompt_content,
+            label: format!("Resolve conflicts ({})", conflicts.files.len()),
+            agent_mode: Some("debug".to_string()),
+        },
+    )
+    .await
+}
+
+/// Default number of repos processed at once by the bulk git commands.
+const GIT_BULK_DEFAULT_CONCURRENCY: usize = 4;
+
+/// A repo known to the app: a Project with repoPath, or an entry of all_projects.
+#[derive(Debug, Clone)]
+struct RegisteredRepo {
+    project_id: Option<String>,
+    name: String,
+    path: PathBuf,
+}
+
+/// Repos from list_projects (repoPath) and get_all_projects, deduplicated by path. Projects win so their id and name are kept.
+fn registered_repos(conn: &rusqlite::Connection) -> AppResult<Vec<RegisteredRepo>> {
+    let mut out: Vec<RegisteredRepo> = vec![];
+    let mut seen: HashSet<PathBuf> = HashSet::new();
+    for p in list_projects_impl(conn)? {
+        let Some(repo_path) = p.repo_path.as_deref().map(str::trim).filter(|s| !s.is_empty()) else {
+            continue;
+        };
+        let path = PathBuf::from(repo_path);
+        if seen.insert(path.clone()) {
+            out.push(RegisteredRepo {
+                project_id: Some(p.id.clone()),
+                name: if p.name.is_empty() { repo_path.to_string() } else { p.name.clone() },
+                path,
+            });
         }
     }
-    if let Ok(unstaged) = run_git(&path_buf, &["diff", "--", file_path]) {
-        if !unstaged.is_empty() {
-            diff_parts.push(format!("=== Unstaged changes ===\n{}\n", unstaged));
+    for raw in db::get_all_projects(conn)? {
+        let path = PathBuf::from(raw.trim());
+        if path.as_os_str().is_empty() || !seen.insert(path.clone()) {
+            continue;
         }
+        let name = path.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_else(|| raw.clone());
+        out.push(RegisteredRepo {
+            project_id: None,
+            name,
+            path,
+        });
     }
-    let diff = if diff_parts.is_empty() && exists {
-        "(no changes or untracked file)".to_string()
-    } else {
-        diff_parts.join("\n")
-    };
+    Ok(out)
+}
 
-    let full_content = if exists {
-        std::fs::read_to_string(&full_path).ok()
-    } else {
-        None
+/// Map `items` through `f` on at most `concurrency` threads. Results keep the input order.
+fn run_bounded_parallel<T, R, F>(items: Vec<T>, concurrency: usize, f: F) -> Vec<R>
+where
+    T: Send,
+    R: Send,
+    F: Fn(T) -> R + Sync,
+{
+    let total = items.len();
+    let queue: Mutex<std::collections::VecDeque<(usize, T)>> = Mutex::new(items.into_iter().enumerate().collect());
+    let results: Mutex<Vec<Option<R>>> = Mutex::new((0..total).map(|_| None).collect());
+    let workers = concurrency.max(1).min(total.max(1));
+    thread::scope(|scope| {
+        for _ in 0..workers {
+            scope.spawn(|| loop {
+                let next = queue.lock().ok().and_then(|mut q| q.pop_front());
+                let Some((idx, item)) = next else {
+                    break;
+                };
+                let r = f(item);
+                if let Ok(mut guard) = results.lock() {
+                    guard[idx] = Some(r);
+                }
+            });
+        }
+    });
+    results
+        .into_inner()
+        .unwrap_or_default()
+        .into_iter()
+        .flatten()
+        .collect()
+}
+
+/// Like run_git but never waits for credentials: bulk operations must fail fast instead of hanging on a prompt. The
+/// askpass helper runs without its prompt directory, so it refuses every prompt; the user's ssh command and
+/// credential helpers are left alone. Git and everything it started are killed after `timeout_secs`.
+fn run_git_noninteractive(project_path: &Path, args: &[&str], timeout_secs: u64) -> AppResult<String> {
+    let askpass = create_git_askpass()?;
+    let git_args: Vec<String> = args.iter().map(|a| a.to_string()).collect();
+    let mut cmd = git_network_command(project_path, &git_args, &askpass);
+    cmd.env_remove("KWCODE_ASKPASS_DIR");
+    #[cfg(unix)]
+    cmd.process_group(0);
+    let mut child = cmd.spawn().map_err(|e| AppError::spawn_failed(e.to_string()))?;
+    let read_all = |mut reader: Box<dyn Read + Send>| {
+        thread::spawn(move || {
+            let mut buf = vec![];
+            let _ = reader.read_to_end(&mut buf);
+            String::from_utf8_lossy(&buf).trim().to_string()
+        })
     };
+    let stdout = read_all(Box::new(child.stdout.take().ok_or("no stdout")?));
+    let stderr = read_all(Box::new(child.stderr.take().ok_or("no stderr")?));
+    let started = std::time::Instant::now();
+    let status = loop {
+        if let Some(status) = child.try_wait()? {
+            break Some(status);
+        }
+        if started.elapsed() >= std::time::Duration::from_secs(timeout_secs) {
+            kill_process_group(&mut child);
+            let _ = child.wait();
+            break None;
+        }
+        thread::sleep(std::time::Duration::from_millis(50));
+    };
+    let stdout = stdout.join().unwrap_or_default();
+    let stderr = stderr.join().unwrap_or_default();
+    match status {
+        Some(status) if status.success() => Ok(if stdout.is_empty() { stderr } else { stdout }),
+        Some(_) => Err(AppError::git(if stderr.is_empty() { stdout } else { stderr })),
+        None => Err(AppError::git(format!("git {} timed out after {}s", args.join(" "), timeout_secs))
+            .with_details(serde_json::json!({ "stderr": stderr }))),
+    }
+}
 
-    Ok(GitFileView { diff, full_content })
+/// One row of the multi-repo git dashboard.
+#[derive(Debug, Clone, Serialize, Deserialize, Default, TS)]
+#[ts(export)]
+pub struct GitRepoStatus {
+    pub project_id: Option<String>,
+    pub name: String,
+    pub path: String,
+    pub branch: String,
+    pub dirty_count: u32,
+    pub ahead: u32,
+    pub behind: u32,
+    pub has_upstream: bool,
+    /// Dirty, ahead/behind, or unreadable.
+    pub needs_attention: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
 }
 
-fn validate_git_repo(project_path: &str) -> Result<PathBuf, String> {
-    let path_buf = PathBuf::from(project_path.trim());
-    if path_buf.as_os_str().is_empty() {
-        return Err("Project path is empty".to_string());
-    }
-    if !path_buf.exists() || !path_buf.is_dir() {
-        return Err("Project path does not exist or is not a directory".to_string());
-    }
-    if !path_buf.join(".git").exists() {
-        return Err("Not a git repository (no .git)".to_string());
+fn git_repo_status(repo: &RegisteredRepo) -> GitRepoStatus {
+    let mut status = GitRepoStatus {
+        project_id: repo.project_id.clone(),
+        name: repo.name.clone(),
+        path: repo.path.to_string_lossy().to_string(),
+        ..Default::default()
+    };
+    if !repo.path.is_dir() {
+        status.error = Some("Project path does not exist or is not a directory".to_string());
+        status.needs_attention = true;
+        return status;
+    }
+    match run_git_noninteractive(&repo.path, &["status", "--porcelain=v2", "--branch"], GIT_NETWORK_TIMEOUT_SECS) {
+        Ok(out) => {
+            for line in out.lines() {
+                if let Some(head) = line.strip_prefix("# branch.head ") {
+                    status.branch = head.trim().to_string();
+                } else if line.starts_with("# branch.upstream ") {
+                    status.has_upstream = true;
+                } else if let Some(ab) = line.strip_prefix("# branch.ab ") {
+                    for part in ab.split_whitespace() {
+                        if let Some(n) = part.strip_prefix('+') {
+                            status.ahead = n.parse().unwrap_or(0);
+                        } else if let Some(n) = part.strip_prefix('-') {
+                            status.behind = n.parse().unwrap_or(0);
+                        }
+                    }
+                } else if !line.starts_with('#') && !line.trim().is_empty() {
+                    status.dirty_count += 1;
+                }
+            }
+            status.needs_attention = status.dirty_count > 0 || status.ahead > 0 || status.behind > 0;
+        }
+        Err(e) => {
+            status.error = Some(e.to_string());
+            status.needs_attention = true;
+        }
     }
-    Ok(path_buf)
+    status
 }
 
+/// Branch, dirty count and ahead/behind for every registered repo, collected in parallel.
 #[tauri::command]
-fn git_fetch(project_path: String) -> Result<String, String> {
-    let path_buf = validate_git_repo(&project_path)?;
-    run_git(&path_buf, &["fetch"])
+async fn git_bulk_status(concurrency: Option<usize>) -> AppResult<Vec<GitRepoStatus>> {
+    let repos = with_db(registered_repos)?;
+    let limit = concurrency.filter(|n| *n > 0).unwrap_or(GIT_BULK_DEFAULT_CONCURRENCY * 2);
+    Ok(tauri::async_runtime::spawn_blocking(move || run_bounded_parallel(repos, limit, |repo| git_repo_status(&repo)))
+        .await?)
+}
+
+/// Per-repo outcome of git_bulk_fetch / git_bulk_pull. Also emitted as `git-bulk-result` as each repo finishes.
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct GitBulkOpResult {
+    pub operation: String,
+    pub project_id: Option<String>,
+    pub name: String,
+    pub path: String,
+    pub ok: bool,
+    pub output: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    /// "conflict" when a pull stopped on unmerged paths (listed in `error`).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error_code: Option<error::ErrorCode>,
+}
+
+/// Run `git <op>` in each repo, at most `concurrency` at a time; `on_result` sees each outcome as it finishes.
+fn git_bulk_op(
+    repos: Vec<RegisteredRepo>,
+    op: &str,
+    concurrency: usize,
+    timeout_secs: u64,
+    on_result: impl Fn(&GitBulkOpResult) + Sync,
+) -> Vec<GitBulkOpResult> {
+    run_bounded_parallel(repos, concurrency, |repo| {
+        let outcome = validate_git_repo(&repo.path.to_string_lossy()).and_then(|path| {
+            run_git_noninteractive(&path, &[op], timeout_secs)
+                .map_err(|e| if op == "pull" { unmerged_paths_error(&path, op).unwrap_or(e) } else { e })
+        });
+        let result = GitBulkOpResult {
+            operation: op.to_string(),
+            project_id: repo.project_id.clone(),
+            name: repo.name.clone(),
+            path: repo.path.to_string_lossy().to_string(),
+            ok: outcome.is_ok(),
+            output: outcome.as_ref().cloned().unwrap_or_default(),
+            error: outcome.as_ref().err().map(|e| e.to_string()),
+            error_code: outcome.err().map(|e| e.code),
+        };
+        on_result(&result);
+        result
+    })
 }
 
-#[tauri::command]
-fn git_pull(project_path: String) -> Result<String, String> {
-    let path_buf = validate_git_repo(&project_path)?;
-    run_git(&path_buf, &["pull"])
+/// git_bulk_op over every registered repo on a blocking thread, emitting `git-bulk-result` per repo.
+async fn git_bulk_op_all(
+    app: AppHandle,
+    op: &'static str,
+    concurrency: Option<usize>,
+    timeout_secs: Option<u64>,
+) -> AppResult<Vec<GitBulkOpResult>> {
+    let repos = with_db(registered_repos)?;
+    let limit = concurrency.filter(|n| *n > 0).unwrap_or(GIT_BULK_DEFAULT_CONCURRENCY);
+    let timeout = timeout_secs.filter(|s| *s > 0).unwrap_or(GIT_NETWORK_TIMEOUT_SECS);
+    Ok(tauri::async_runtime::spawn_blocking(move || {
+        git_bulk_op(repos, op, limit, timeout, |result| {
+            let _ = app.emit("git-bulk-result", result.clone());
+        })
+    })
+    .await?)
 }
 
+/// `git fetch` in every registered repo, at most `concurrency` at a time (default 4). Each repo is killed after
+/// `timeout_secs` (default 300).
 #[tauri::command]
-fn git_push(project_path: String) -> Result<String, String> {
-    let path_buf = validate_git_repo(&project_path)?;
-    run_git(&path_buf, &["push"])
+async fn git_bulk_fetch(
+    app: AppHandle,
+    concurrency: Option<usize>,
+    timeout_secs: Option<u64>,
+) -> AppResult<Vec<GitBulkOpResult>> {
+    git_bulk_op_all(app, "fetch", concurrency, timeout_secs).await
 }
 
+/// `git pull` in every registered repo, at most `concurrency` at a time (default 4). Each repo is killed after
+/// `timeout_secs` (default 300).
 #[tauri::command]
-fn git_commit(project_path: String, message: String) -> Result<String, String> {
-    let path_buf = validate_git_repo(&project_path)?;
-    let msg = message.trim();
-    if msg.is_empty() {
-        return Err("Commit message cannot be empty".to_string());
-    }
-    run_git(&path_buf, &["add", "-A"])?;
-    run_git(&path_buf, &["commit", "-m", msg])
+async fn git_bulk_pull(
+    app: AppHandle,
+    concurrency: Option<usize>,
+    timeout_secs: Option<u64>,
+) -> AppResult<Vec<GitBulkOpResult>> {
+    git_bulk_op_all(app, "pull", concurrency, timeout_secs).await
 }
 
 fn is_valid_workspace(p: &PathBuf) -> bool {
@@ -868,9 +3590,9 @@ fn run_terminal_agent_script_path(ws: &PathBuf) -> PathBuf {
 
 /// Resolve project root (contains script/ and data/). Tries current working directory first,
 /// then walks up from the executable path so the app finds data when launched from any cwd.
-fn project_root() -> Result<PathBuf, String> {
+fn project_root() -> AppResult<PathBuf> {
     // 1) Try current working directory (e.g. when running `tauri dev` from repo root)
-    let mut candidate = std::env::current_dir().map_err(|e| e.to_string())?;
+    let mut candidate = std::env::current_dir()?;
     if candidate.join("src-tauri").exists() {
         candidate = candidate.parent().unwrap_or(&candidate).to_path_buf();
     }
@@ -900,18 +3622,18 @@ fn project_root() -> Result<PathBuf, String> {
     let err_msg = "Project root not found. Run the app from the repo root (contains script/worker/implement_all.sh and data/).";
     debug_log("lib.rs:project_root", "project_root returning Err", &[("err", err_msg)]);
     // #endregion
-    Err(err_msg.to_string())
+    Err(AppError::not_found(err_msg))
 }
 
 /// App data directory for the built app (e.g. ~/Library/Application Support/com.kwcode.app/data). Created if missing.
-fn app_data_data_dir() -> Result<PathBuf, String> {
+fn app_data_data_dir() -> AppResult<PathBuf> {
     let home = std::env::var("HOME").map_err(|_| "HOME not set".to_string())?;
     #[cfg(target_os = "macos")]
     let app_data = PathBuf::from(&home).join("Library").join("Application Support").join("com.kwcode.app");
     #[cfg(not(target_os = "macos"))]
     let app_data = PathBuf::from(&home).join(".local").join("share").join("com.kwcode.app");
     let data = app_data.join("data");
-    std::fs::create_dir_all(&data).map_err(|e| e.to_string())?;
+    std::fs::create_dir_all(&data)?;
     Ok(data)
 }
 
@@ -940,14 +3662,14 @@ fn frontend_debug_log(location: String, message: String, data: Option<serde_json
 
 /// Returns the app version (from Cargo.toml package version) for display in Configuration and support.
 #[tauri::command]
-fn get_app_version(app: AppHandle) -> Result<String, String> {
+fn get_app_version(app: AppHandle) -> AppResult<String> {
     Ok(app.package_info().version.to_string())
 }
 
 /// Navigate the webview by setting window.location.href via eval. We never parse the URL in Rust nor call w.navigate(),
 /// so the user never sees "The string did not match the expected pattern" (url crate / WebView).
 #[tauri::command]
-fn navigate_webview_to(app: AppHandle, url: String) -> Result<(), String> {
+fn navigate_webview_to(app: AppHandle, url: String) -> AppResult<()> {
     let escaped = serde_json::to_string(&url).unwrap_or_else(|_| "\"/\"".to_string());
     let app_clone = app.clone();
     let _ = app.run_on_main_thread(move || {
@@ -959,7 +3681,7 @@ fn navigate_webview_to(app: AppHandle, url: String) -> Result<(), String> {
 }
 
 /// Directory that holds app.db and february-dir.txt. In dev: repo data dir; when bundled: app data dir so DB and config work.
-fn data_root() -> Result<PathBuf, String> {
+fn data_root() -> AppResult<PathBuf> {
     // #region agent log
     debug_log("lib.rs:data_root", "data_root called", &[]);
     // #endregion
@@ -977,19 +3699,20 @@ fn data_root() -> Result<PathBuf, String> {
 
 /// Read a file from disk and return its contents as base64 (for sending to API for PDF/text extraction).
 #[tauri::command]
-fn read_file_as_base64(path: String) -> Result<String, String> {
+fn read_file_as_base64(path: String) -> AppResult<String> {
     let path_buf = PathBuf::from(path.trim());
     if !path_buf.exists() {
-        return Err("File does not exist".to_string());
+        return Err(AppError::not_found("File does not exist"));
     }
     if !path_buf.is_file() {
-        return Err("Path is not a file".to_string());
+        return Err(AppError::invalid("Path is not a file"));
     }
-    let bytes = std::fs::read(&path_buf).map_err(|e| e.to_string())?;
+    let bytes = std::fs::read(&path_buf)?;
     Ok(base64::engine::general_purpose::STANDARD.encode(&bytes))
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export)]
 pub struct FileEntry {
     pub name: String,
     pub path: String,
@@ -997,51 +3720,53 @@ pub struct FileEntry {
 
 /// Read a text file under project root (script/ or data/). Path must be relative to project root or absolute under it.
 #[tauri::command]
-fn read_file_text(path: String) -> Result<String, String> {
+fn read_file_text(path: String) -> AppResult<String> {
     let ws = project_root()?;
     let path_buf = PathBuf::from(path.trim());
     let canonical = if path_buf.is_absolute() {
-        path_buf.canonicalize().map_err(|e| e.to_string())?
+        path_buf.canonicalize()?
     } else {
-        ws.join(path_buf).canonicalize().map_err(|e| e.to_string())?
+        ws.join(path_buf).canonicalize()?
     };
-    if !canonical.starts_with(ws.canonicalize().map_err(|e| e.to_string())?) {
-        return Err("Path must be under project root".to_string());
+    if !canonical.starts_with(ws.canonicalize()?) {
+        return Err(AppError::path_outside_root("Path must be under project root"));
     }
     if !canonical.is_file() {
-        return Err("Path is not a file".to_string());
+        return Err(AppError::invalid("Path is not a file"));
     }
-    let content = std::fs::read_to_string(&canonical).map_err(|e| e.to_string())?;
+    let content = std::fs::read_to_string(&canonical)?;
     Ok(content)
 }
 
 /// Read a text file under a given root (e.g. project repo path). Use for project spec files from .cursor in another repo.
 #[tauri::command]
-fn read_file_text_under_root(root: String, path: String) -> Result<String, String> {
+fn read_file_text_under_root(root: String, path: String) -> AppResult<String> {
     let root_buf = PathBuf::from(root.trim());
-    let root_canonical = root_buf.canonicalize().map_err(|e| e.to_string())?;
+    let root_canonical = root_buf.canonicalize()?;
     let path_buf = PathBuf::from(path.trim());
     let canonical = if path_buf.is_absolute() {
-        path_buf.canonicalize().map_err(|e| e.to_string())?
+        path_buf.canonicalize()?
     } else {
-        root_canonical.join(path_buf).canonicalize().map_err(|e| e.to_string())?
+        root_canonical.join(path_buf).canonicalize()?
     };
     if !canonical.starts_with(&root_canonical) {
-        return Err("Path must be under project root".to_string());
+        return Err(AppError::path_outside_root("Path must be under project root"));
     }
     if !canonical.is_file() {
-        return Err("Path is not a file".to_string());
+        return Err(AppError::invalid("Path is not a file"));
     }
-    let content = std::fs::read_to_string(&canonical).map_err(|e| e.to_string())?;
+    let content = std::fs::read_to_string(&canonical)?;
     Ok(content)
 }
 
 /// Entry for one directory listing (one level under a root). Matches frontend FileEntry shape.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export)]
 pub struct DirListingEntry {
     pub name: String,
     #[serde(rename = "isDirectory")]
     pub is_directory: bool,
+    #[ts(as = "f64")]
     pub size: u64,
     #[serde(rename = "updatedAt")]
     pub updated_at: String,
@@ -1049,9 +3774,9 @@ pub struct DirListingEntry {
 
 /// List one level of files/dirs under a given root (e.g. project repo path). Used by Project Files in Stakeholder tab.
 #[tauri::command]
-fn list_files_under_root(root: String, path: String) -> Result<Vec<DirListingEntry>, String> {
+fn list_files_under_root(root: String, path: String) -> AppResult<Vec<DirListingEntry>> {
     let root_buf = PathBuf::from(root.trim());
-    let root_canonical = root_buf.canonicalize().map_err(|e| e.to_string())?;
+    let root_canonical = root_buf.canonicalize()?;
     let path_buf = PathBuf::from(path.trim().trim_start_matches('/'));
     let full = if path_buf.as_os_str().is_empty() || path_buf == Path::new(".") {
         root_canonical.clone()
@@ -1061,22 +3786,22 @@ fn list_files_under_root(root: String, path: String) -> Result<Vec<DirListingEnt
     if !full.exists() {
         return Ok(vec![]);
     }
-    let dir = full.canonicalize().map_err(|e| e.to_string())?;
+    let dir = full.canonicalize()?;
     if !dir.starts_with(&root_canonical) {
-        return Err("Path must be under project root".to_string());
+        return Err(AppError::path_outside_root("Path must be under project root"));
     }
     if !dir.is_dir() {
-        return Err("Path is not a directory".to_string());
+        return Err(AppError::invalid("Path is not a directory"));
     }
     let mut entries = Vec::new();
-    for e in std::fs::read_dir(&dir).map_err(|e| e.to_string())? {
-        let e = e.map_err(|e| e.to_string())?;
+    for e in std::fs::read_dir(&dir)? {
+        let e = e?;
         let path = e.path();
         let name = path.file_name().and_then(|n| n.to_str()).unwrap_or("").to_string();
         if name.is_empty() || name == "." || name == ".." {
             continue;
         }
-        let meta = std::fs::metadata(&path).map_err(|e| e.to_string())?;
+        let meta = std::fs::metadata(&path)?;
         let is_directory = path.is_dir();
         let size = if is_directory { 0 } else { meta.len() };
         let updated_at = meta
@@ -1105,15 +3830,15 @@ fn list_files_under_root(root: String, path: String) -> Result<Vec<DirListingEnt
 
 /// List files in script/ directory.
 #[tauri::command]
-fn list_scripts() -> Result<Vec<FileEntry>, String> {
+fn list_scripts() -> AppResult<Vec<FileEntry>> {
     let ws = project_root()?;
     let script_dir = ws.join("script");
     if !script_dir.is_dir() {
         return Ok(vec![]);
     }
     let mut entries = vec![];
-    for e in std::fs::read_dir(&script_dir).map_err(|e| e.to_string())? {
-        let e = e.map_err(|e| e.to_string())?;
+    for e in std::fs::read_dir(&script_dir)? {
+        let e = e?;
         let path = e.path();
         if path.is_file() {
             let name = path.file_name().and_then(|n| n.to_str()).unwrap_or("").to_string();
@@ -1127,15 +3852,15 @@ fn list_scripts() -> Result<Vec<FileEntry>, String> {
 
 /// List all files under project_path/.cursor (recursive). Returns empty vec if .cursor does not exist or is not a directory.
 #[tauri::command]
-fn list_cursor_folder(project_path: String) -> Result<Vec<FileEntry>, String> {
+fn list_cursor_folder(project_path: String) -> AppResult<Vec<FileEntry>> {
     let base = PathBuf::from(project_path.trim()).join(".cursor");
     if !base.exists() || !base.is_dir() {
         return Ok(vec![]);
     }
     let mut entries = vec![];
-    fn collect_files(dir: &Path, out: &mut Vec<FileEntry>) -> Result<(), String> {
-        for e in std::fs::read_dir(dir).map_err(|e| e.to_string())? {
-            let e = e.map_err(|e| e.to_string())?;
+    fn collect_files(dir: &Path, out: &mut Vec<FileEntry>) -> AppResult<()> {
+        for e in std::fs::read_dir(dir)? {
+            let e = e?;
             let path = e.path();
             if path.is_file() {
                 let name = path.file_name().and_then(|n| n.to_str()).unwrap_or("").to_string();
@@ -1154,25 +3879,25 @@ fn list_cursor_folder(project_path: String) -> Result<Vec<FileEntry>, String> {
 
 /// Read all files under the init template dir (relative to app/project root) and return a map of relative path -> content for Initialize.
 #[tauri::command]
-fn get_cursor_init_template() -> Result<std::collections::HashMap<String, String>, String> {
+fn get_cursor_init_template() -> AppResult<std::collections::HashMap<String, String>> {
     let root = project_root()?;
     let template_dir = root.join(".cursor_template");
     if !template_dir.exists() || !template_dir.is_dir() {
-        return Err("Template folder not found".to_string());
+        return Err(AppError::not_found("Template folder not found"));
     }
     let mut out = std::collections::HashMap::new();
     fn collect(
         dir: &std::path::Path,
         base: &std::path::Path,
         out: &mut std::collections::HashMap<String, String>,
-    ) -> Result<(), String> {
-        for e in std::fs::read_dir(dir).map_err(|e| e.to_string())? {
-            let e = e.map_err(|e| e.to_string())?;
+    ) -> AppResult<()> {
+        for e in std::fs::read_dir(dir)? {
+            let e = e?;
             let path = e.path();
             if path.is_file() {
-                let rel = path.strip_prefix(base).map_err(|e| e.to_string())?;
+                let rel = path.strip_prefix(base)?;
                 let rel_str = rel.to_string_lossy().replace('\\', "/");
-                let content = std::fs::read_to_string(&path).map_err(|e| e.to_string())?;
+                let content = std::fs::read_to_string(&path)?;
                 out.insert(rel_str, content);
             } else if path.is_dir() {
                 collect(&path, base, out)?;
@@ -1185,7 +3910,7 @@ fn get_cursor_init_template() -> Result<std::collections::HashMap<String, String
 }
 
 /// Resolve project_template.zip: try bundled resource first, then project root (for dev).
-fn resolve_project_template_zip(app: &AppHandle) -> Result<PathBuf, String> {
+fn resolve_project_template_zip(app: &AppHandle) -> AppResult<PathBuf> {
     if let Ok(path) = app.path().resolve("project_template.zip", BaseDirectory::Resource) {
         if path.exists() && path.is_file() {
             return Ok(path);
@@ -1196,27 +3921,27 @@ fn resolve_project_template_zip(app: &AppHandle) -> Result<PathBuf, String> {
     if zip_path.exists() && zip_path.is_file() {
         Ok(zip_path)
     } else {
-        Err("project_template.zip not found (bundle resource or next to app)".to_string())
+        Err(AppError::not_found("project_template.zip not found (bundle resource or next to app)"))
     }
 }
 
 /// Unzip project_template.zip (from bundle resource or next to app) into target_path. Strips a single top-level
 /// directory (e.g. project_template/) so the template contents land at target_path root.
 #[tauri::command]
-fn unzip_project_template(app: AppHandle, target_path: String) -> Result<(), String> {
+fn unzip_project_template(app: AppHandle, target_path: String) -> AppResult<()> {
     let zip_path = resolve_project_template_zip(&app)?;
     let target = PathBuf::from(target_path.trim());
     if !target.exists() || !target.is_dir() {
-        return Err("Target path does not exist or is not a directory".to_string());
+        return Err(AppError::not_found("Target path does not exist or is not a directory"));
     }
 
-    let file = std::fs::File::open(&zip_path).map_err(|e| e.to_string())?;
-    let mut archive = zip::ZipArchive::new(file).map_err(|e| e.to_string())?;
+    let file = std::fs::File::open(&zip_path)?;
+    let mut archive = zip::ZipArchive::new(file)?;
 
     // Detect single top-level dir: if every entry starts with the same segment (e.g. "project_template/"), strip it.
     let mut prefix: Option<String> = None;
     for i in 0..archive.len() {
-        let entry = archive.by_index(i).map_err(|e| e.to_string())?;
+        let entry = archive.by_index(i)?;
         let name = entry.name().replace('\\', "/").trim_end_matches('/').to_string();
         if name.is_empty() || name.contains("..") {
             continue;
@@ -1237,7 +3962,7 @@ fn unzip_project_template(app: AppHandle, target_path: String) -> Result<(), Str
     let strip_prefix = prefix.filter(|p| !p.is_empty());
 
     for i in 0..archive.len() {
-        let mut entry = archive.by_index(i).map_err(|e| e.to_string())?;
+        let mut entry = archive.by_index(i)?;
         let raw_name = entry.name().replace('\\', "/");
         let name = raw_name.trim_end_matches('/');
         if name.is_empty() || name.contains("..") {
@@ -1253,21 +3978,21 @@ fn unzip_project_template(app: AppHandle, target_path: String) -> Result<(), Str
         }
         let out_path = target.join(relative);
         if entry.is_dir() {
-            std::fs::create_dir_all(&out_path).map_err(|e| e.to_string())?;
+            std::fs::create_dir_all(&out_path)?;
         } else {
             if let Some(parent) = out_path.parent() {
-                std::fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+                std::fs::create_dir_all(parent)?;
             }
             let mut buf = Vec::new();
-            entry.read_to_end(&mut buf).map_err(|e| e.to_string())?;
-            std::fs::write(&out_path, &buf).map_err(|e| e.to_string())?;
+            entry.read_to_end(&mut buf)?;
+            std::fs::write(&out_path, &buf)?;
         }
     }
     Ok(())
 }
 
 /// Resolve .cursor_init.zip: try bundled resource first (cursor_init.zip), then project root for dev.
-fn resolve_cursor_init_zip(app: &AppHandle) -> Result<PathBuf, String> {
+fn resolve_cursor_init_zip(app: &AppHandle) -> AppResult<PathBuf> {
     if let Ok(path) = app.path().resolve("cursor_init.zip", BaseDirectory::Resource) {
         if path.exists() && path.is_file() {
             return Ok(path);
@@ -1279,26 +4004,26 @@ fn resolve_cursor_init_zip(app: &AppHandle) -> Result<PathBuf, String> {
             return Ok(zip_path);
         }
     }
-    Err(".cursor_init.zip not found (bundle resource or next to app)".to_string())
+    Err(AppError::not_found(".cursor_init.zip not found (bundle resource or next to app)"))
 }
 
 /// Unzip .cursor_init.zip into target_path/.cursor/. Strips a single top-level directory (e.g. cursor_init/).
 /// If merge_if_exists is true and a destination file already exists, skip it (only add missing files/folders).
 #[tauri::command]
-fn unzip_cursor_init(app: AppHandle, target_path: String, merge_if_exists: bool) -> Result<(), String> {
+fn unzip_cursor_init(app: AppHandle, target_path: String, merge_if_exists: bool) -> AppResult<()> {
     let zip_path = resolve_cursor_init_zip(&app)?;
     let target_base = PathBuf::from(target_path.trim());
     if !target_base.exists() || !target_base.is_dir() {
-        return Err("Target path does not exist or is not a directory".to_string());
+        return Err(AppError::not_found("Target path does not exist or is not a directory"));
     }
     let cursor_dir = target_base.join(".cursor");
 
-    let file = std::fs::File::open(&zip_path).map_err(|e| e.to_string())?;
-    let mut archive = zip::ZipArchive::new(file).map_err(|e| e.to_string())?;
+    let file = std::fs::File::open(&zip_path)?;
+    let mut archive = zip::ZipArchive::new(file)?;
 
     let mut prefix: Option<String> = None;
     for i in 0..archive.len() {
-        let entry = archive.by_index(i).map_err(|e| e.to_string())?;
+        let entry = archive.by_index(i)?;
         let name = entry.name().replace('\\', "/").trim_end_matches('/').to_string();
         if name.is_empty() || name.contains("..") {
             continue;
@@ -1318,7 +4043,7 @@ fn unzip_cursor_init(app: AppHandle, target_path: String, merge_if_exists: bool)
     let strip_prefix = prefix.filter(|p| !p.is_empty());
 
     for i in 0..archive.len() {
-        let mut entry = archive.by_index(i).map_err(|e| e.to_string())?;
+        let mut entry = archive.by_index(i)?;
         let raw_name = entry.name().replace('\\', "/");
         let name = raw_name.trim_end_matches('/');
         if name.is_empty() || name.contains("..") {
@@ -1335,18 +4060,18 @@ fn unzip_cursor_init(app: AppHandle, target_path: String, merge_if_exists: bool)
         let out_path = cursor_dir.join(relative);
         if entry.is_dir() {
             if !out_path.exists() {
-                std::fs::create_dir_all(&out_path).map_err(|e| e.to_string())?;
+                std::fs::create_dir_all(&out_path)?;
             }
         } else {
             if merge_if_exists && out_path.exists() {
                 continue;
             }
             if let Some(parent) = out_path.parent() {
-                std::fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+                std::fs::create_dir_all(parent)?;
             }
             let mut buf = Vec::new();
-            entry.read_to_end(&mut buf).map_err(|e| e.to_string())?;
-            std::fs::write(&out_path, &buf).map_err(|e| e.to_string())?;
+            entry.read_to_end(&mut buf)?;
+            std::fs::write(&out_path, &buf)?;
         }
     }
     Ok(())
@@ -1355,26 +4080,26 @@ fn unzip_cursor_init(app: AppHandle, target_path: String, merge_if_exists: bool)
 /// Write a spec file into the project directory (e.g. project_path + "/.cursor/design-x.md").
 /// Creates parent directories if needed. relative_path should be like ".cursor/design-abc.md".
 #[tauri::command]
-fn write_spec_file(project_path: String, relative_path: String, content: String) -> Result<(), String> {
+fn write_spec_file(project_path: String, relative_path: String, content: String) -> AppResult<()> {
     let base = PathBuf::from(project_path.trim());
     if !base.exists() || !base.is_dir() {
-        return Err("Project path does not exist or is not a directory".to_string());
+        return Err(AppError::not_found("Project path does not exist or is not a directory"));
     }
     let full = base.join(relative_path.trim().trim_start_matches('/'));
     if let Some(parent) = full.parent() {
-        std::fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+        std::fs::create_dir_all(parent)?;
     }
-    std::fs::write(&full, content).map_err(|e| e.to_string())?;
+    std::fs::write(&full, content)?;
     Ok(())
 }
 
 /// Archive .cursor/7. planner/tickets.md or .cursor/7. planner/features.md to .cursor/legacy/{file}-YYYY-MM-DD.md and create a new empty file.
 /// file_kind must be "tickets" or "features".
 #[tauri::command]
-fn archive_cursor_file(project_path: String, file_kind: String) -> Result<(), String> {
+fn archive_cursor_file(project_path: String, file_kind: String) -> AppResult<()> {
     let base = PathBuf::from(project_path.trim());
     if !base.exists() || !base.is_dir() {
-        return Err("Project path does not exist or is not a directory".to_string());
+        return Err(AppError::not_found("Project path does not exist or is not a directory"));
     }
     let (cursor_file, legacy_prefix, minimal_content) = match file_kind.trim() {
         "tickets" => (
@@ -1394,7 +4119,7 @@ fn archive_cursor_file(project_path: String, file_kind: String) -> Result<(), St
             "features",
             "# Features roadmap\n\nFeatures below are derived from .cursor/7. planner/tickets.md. Add features as checklist items with ticket refs, e.g. `- [ ] Feature name — #1, #2`.\n\n## Major features\n\n- [ ] (add feature)\n",
         ),
-        _ => return Err("file_kind must be 'tickets' or 'features'".to_string()),
+        _ => return Err(AppError::invalid("file_kind must be 'tickets' or 'features'")),
     };
     let cursor_path = base.join(cursor_file);
     let content = if cursor_path.exists() {
@@ -1405,17 +4130,18 @@ fn archive_cursor_file(project_path: String, file_kind: String) -> Result<(), St
     let date = chrono::Utc::now().format("%Y-%m-%d").to_string();
     let legacy_name = format!("{}-{}.md", legacy_prefix, date);
     let legacy_path = base.join(".cursor").join("legacy");
-    std::fs::create_dir_all(&legacy_path).map_err(|e| e.to_string())?;
+    std::fs::create_dir_all(&legacy_path)?;
     let legacy_full = legacy_path.join(&legacy_name);
-    std::fs::write(&legacy_full, content).map_err(|e| e.to_string())?;
-    std::fs::write(&cursor_path, minimal_content).map_err(|e| e.to_string())?;
+    std::fs::write(&legacy_full, content)?;
+    std::fs::write(&cursor_path, minimal_content)?;
     Ok(())
 }
 
 /// List JSON files in data/ directory.
 
 /// Result of analyzing a project directory for AI ticket generation.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export)]
 pub struct ProjectAnalysis {
     pub name: String,
     pub path: String,
@@ -1435,10 +4161,10 @@ const CONFIG_MAX_CHARS: usize = 4000;
 /// Analyze a project directory for AI ticket generation: read package.json, README, list top-level structure.
 /// Path must be an existing directory (e.g. from all_projects list).
 #[tauri::command]
-fn analyze_project_for_tickets(project_path: String) -> Result<ProjectAnalysis, String> {
+fn analyze_project_for_tickets(project_path: String) -> AppResult<ProjectAnalysis> {
     let path_buf = PathBuf::from(project_path.trim());
     if !path_buf.exists() || !path_buf.is_dir() {
-        return Err("Project path does not exist or is not a directory".to_string());
+        return Err(AppError::not_found("Project path does not exist or is not a directory"));
     }
     let name = path_buf
         .file_name()
@@ -1510,26 +4236,193 @@ fn analyze_project_for_tickets(project_path: String) -> Result<ProjectAnalysis,
     })
 }
 
-fn with_db<F, T>(f: F) -> Result<T, String>
+/// The app's SQLite connection, opened once (migrations and seeding included) and shared by every command.
+/// Commands are serialised on the mutex; SQLite itself waits up to DB_BUSY_TIMEOUT for other processes.
+#[derive(Default)]
+pub struct DbState {
+    conn: Mutex<Option<rusqlite::Connection>>,
+    /// Last check_db_health result; taken whenever the connection is opened.
+    health: Mutex<Option<db::DbHealth>>,
+}
+
+const DB_BUSY_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(5);
+const DB_STATEMENT_CACHE_CAPACITY: usize = 64;
+
+impl DbState {
+    fn open() -> AppResult<rusqlite::Connection> {
+        let data = data_root()?;
+        let conn = db::open_db(&data.join("app.db"))?;
+        conn.busy_timeout(DB_BUSY_TIMEOUT)?;
+        conn.set_prepared_statement_cache_capacity(DB_STATEMENT_CACHE_CAPACITY);
+        seed_initial_data(&conn)?;
+        Ok(conn)
+    }
+
+    /// Open the connection if needed and run the startup health check on it.
+    fn connect(&self, slot: &mut Option<rusqlite::Connection>) -> AppResult<()> {
+        if slot.is_some() {
+            return Ok(());
+        }
+        let conn = Self::open()?;
+        match db::check_db_health(&conn) {
+            Ok(health) => {
+                if !health.ok {
+                    eprintln!("Database health check found {} issue(s); see get_db_health", health.issues.len());
+                }
+                self.set_health(health);
+            }
+            Err(e) => eprintln!("Database health check failed: {}", e),
+        }
+        *slot = Some(conn);
+        Ok(())
+    }
+
+    fn set_health(&self, health: db::DbHealth) {
+        *self.health.lock().unwrap_or_else(|e| e.into_inner()) = Some(health);
+    }
+
+    fn cached_health(&self) -> Option<db::DbHealth> {
+        self.health.lock().unwrap_or_else(|e| e.into_inner()).clone()
+    }
+
+    /// Run `f` on the shared connection, opening it on first use.
+    fn with<F, T>(&self, f: F) -> AppResult<T>
+    where
+        F: FnOnce(&rusqlite::Connection) -> AppResult<T>,
+    {
+        // A panic inside another command must not make the database unusable for the rest of the session.
+        let mut guard = self.conn.lock().unwrap_or_else(|e| e.into_inner());
+        self.connect(&mut guard)?;
+        f(guard.as_ref().ok_or_else(|| AppError::db_unavailable("Database is not open"))?)
+    }
+}
+
+/// How often the background thread checks whether a scheduled backup is due.
+const BACKUP_CHECK_INTERVAL: std::time::Duration = std::time::Duration::from_secs(60 * 60);
+
+/// Shared with Tauri's managed state in run(); with_db goes through the same instance so helpers without a State
+/// parameter use the same connection.
+fn db_state() -> Arc<DbState> {
+    static DB: std::sync::OnceLock<Arc<DbState>> = std::sync::OnceLock::new();
+    DB.get_or_init(|| Arc::new(DbState::default())).clone()
+}
+
+fn with_db<F, T>(f: F) -> AppResult<T>
 where
-    F: FnOnce(&rusqlite::Connection) -> Result<T, String>,
+    F: FnOnce(&rusqlite::Connection) -> AppResult<T>,
 {
+    db_state().with(f)
+}
+
+/// Zip app.db (online backup snapshot), february-dir.txt and the data JSON files. `dest` is a .zip path or a
+/// directory; the backups folder in the data directory when omitted. The DB lock is held only for the snapshot.
+#[tauri::command]
+fn create_backup(dest: Option<String>) -> AppResult<backup::BackupInfo> {
+    let data = data_root()?;
+    let dest = dest.map(|d| PathBuf::from(d.trim())).filter(|d| !d.as_os_str().is_empty());
+    with_db(|conn| backup::start_backup(conn, &data, dest.as_deref()))?.finish(&data)
+}
+
+/// Backups in the data directory (manual, scheduled, pre-migration and pre-restore), newest first.
+#[tauri::command]
+fn list_backups() -> AppResult<Vec<backup::BackupInfo>> {
+    backup::list_backups(&data_root()?)
+}
+
+#[tauri::command]
+fn get_backup_schedule() -> AppResult<db::BackupSchedule> {
+    with_db(db::get_backup_schedule)
+}
+
+#[tauri::command]
+fn set_backup_schedule(schedule: db::BackupSchedule) -> AppResult<db::BackupSchedule> {
+    with_db(|conn| {
+        db::set_backup_schedule(conn, &schedule)?;
+        db::get_backup_schedule(conn)
+    })
+}
+
+/// Replace the database with a backup (zip or .db) after an integrity check, keeping a safety copy of the current one.
+/// When the current database cannot be opened, the file itself is moved aside and replaced.
+#[tauri::command]
+fn restore_backup(path: String) -> AppResult<backup::RestoreResult> {
+    let data = data_root()?;
+    let path = PathBuf::from(path.trim());
+    let latest = db::latest_schema_version();
+    let state = db_state();
+    let mut guard = state.conn.lock().unwrap_or_else(|e| e.into_inner());
+    let result = match state.connect(&mut guard) {
+        Ok(()) => {
+            let conn = guard.as_mut().ok_or_else(|| AppError::db_unavailable("Database is not open"))?;
+            backup::restore_backup(conn, &data, &path, latest)
+        }
+        Err(e) => {
+            eprintln!("Restoring over a database that cannot be opened: {}", e);
+            backup::restore_backup_file(&data.join("app.db"), &data, &path, latest)
+        }
+    };
+    // Reopened (and migrated) by the next command.
+    *guard = None;
+    result
+}
+
+/// Last database health report (from startup), or a fresh check when `refresh` is set or none was taken.
+#[tauri::command]
+fn get_db_health(refresh: Option<bool>) -> AppResult<db::DbHealth> {
+    let state = db_state();
+    if !refresh.unwrap_or(false) {
+        if let Some(health) = state.cached_health() {
+            return Ok(health);
+        }
+    }
+    let health = state.with(db::check_db_health)?;
+    state.set_health(health.clone());
+    Ok(health)
+}
+
+/// Copy the database to a pre-repair backup, fix what check_db_health found where that is safe (dangling links,
+/// derived rows, orphan ideas, missing milestones) and move the remaining bad rows to the quarantine table.
+#[tauri::command]
+fn repair_db() -> AppResult<db::RepairReport> {
     let data = data_root()?;
-    let conn = db::open_db(&data.join("app.db"))?;
-    seed_initial_data(&conn)?; // New seeding call
-    f(&conn)
+    let state = db_state();
+    let report = state.with(|conn| {
+        let copy = backup::pre_repair_copy(conn, &data)?;
+        let mut report = db::repair_db(conn)?;
+        report.backup_path = Some(copy.path);
+        Ok(report)
+    })?;
+    state.set_health(report.health.clone());
+    Ok(report)
+}
+
+/// Take scheduled backups in the background for the life of the app.
+fn spawn_backup_scheduler() {
+    std::thread::spawn(|| loop {
+        let result = data_root().and_then(|data| {
+            let pending = with_db(|conn| backup::start_scheduled_backup(conn, &data))?;
+            pending.map(|p| p.finish(&data)).transpose()
+        });
+        match result {
+            Ok(Some(b)) => println!("Scheduled backup written to {}", b.path),
+            Ok(None) => {}
+            Err(e) => eprintln!("Scheduled backup failed: {}", e),
+        }
+        std::thread::sleep(BACKUP_CHECK_INTERVAL);
+    });
 }
 
 /// Resolve data directory from DB (ADR 069). Uses path stored in kv_store, or fallback from data root, and persists it.
 #[tauri::command]
-fn resolve_data_dir() -> Result<PathBuf, String> {
+fn resolve_data_dir() -> AppResult<PathBuf> {
     let fallback = data_root()?;
     with_db(|conn| Ok(db::get_data_dir(conn, &fallback)))
 }
 
-/// Project record (camelCase for frontend). Stored in kv_store "projects" as JSON array.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+/// Project record (camelCase for frontend). Stored in the projects table; the id lists live in project_* link tables.
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
 #[serde(rename_all = "camelCase")]
+#[ts(export)]
 pub struct Project {
     /// Optional on create; generated if missing or empty.
     #[serde(default)]
@@ -1544,12 +4437,14 @@ pub struct Project {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub run_port: Option<u16>,
     #[serde(default)]
+    #[ts(as = "Vec<f64>")]
     pub prompt_ids: Vec<i64>,
     #[serde(default)]
     pub ticket_ids: Vec<String>,
     #[serde(default)]
     pub feature_ids: Vec<String>,
     #[serde(default)]
+    #[ts(as = "Vec<f64>")]
     pub idea_ids: Vec<i64>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub design_ids: Option<Vec<String>>,
@@ -1569,54 +4464,46 @@ pub struct Project {
     pub updated_at: Option<String>,
 }
 
-fn list_projects_impl(conn: &rusqlite::Connection) -> Result<Vec<Project>, String> {
-    let json = db::get_projects_json(conn)?;
-    let arr: Vec<Project> = serde_json::from_str(&json).unwrap_or_default();
-    Ok(arr)
+fn list_projects_impl(conn: &rusqlite::Connection) -> AppResult<Vec<Project>> {
+    db::get_projects(conn)
 }
 
 #[tauri::command]
-fn list_projects() -> Result<Vec<Project>, String> {
+fn list_projects() -> AppResult<Vec<Project>> {
     with_db(list_projects_impl)
 }
 
 #[tauri::command]
-fn get_project(id: String) -> Result<Option<Project>, String> {
-    with_db(|conn| {
-        let projects = list_projects_impl(conn)?;
-        Ok(projects.into_iter().find(|p| p.id == id))
-    })
+fn get_project(id: String) -> AppResult<Option<Project>> {
+    with_db(|conn| db::get_project(conn, &id))
 }
 
 #[tauri::command]
-fn create_project(project: Project) -> Result<Project, String> {
+fn create_project(project: Project) -> AppResult<Project> {
     // #region agent log
     debug_log("lib.rs:create_project", "create_project command entered", &[("name", project.name.as_str())]);
     // #endregion
     with_db(|conn| {
-        let mut projects = list_projects_impl(conn)?;
         let now = now_iso();
         let mut p = project;
         if p.id.is_empty() {
             p.id = uuid::Uuid::new_v4().to_string();
+        } else if db::get_project(conn, &p.id)?.is_some() {
+            return Err(AppError::conflict("A project with this id already exists"));
         }
         p.created_at.get_or_insert(now.clone());
         p.updated_at = Some(now);
-        projects.push(p.clone());
-        let json = serde_json::to_string(&projects).map_err(|e| e.to_string())?;
-        db::save_projects_json(conn, &json)?;
+        db::save_project(conn, &p)?;
         Ok(p)
     })
 }
 
 #[tauri::command]
-fn update_project(id: String, project: serde_json::Value) -> Result<Project, String> {
+fn update_project(id: String, project: serde_json::Value) -> AppResult<Project> {
     with_db(|conn| {
-        let mut projects = list_projects_impl(conn)?;
-        let idx = projects.iter().position(|p| p.id == id).ok_or("Project not found")?;
+        let base = db::get_project(conn, &id)?.ok_or_else(|| AppError::not_found("Project not found"))?;
         let now = now_iso();
-        let base = projects[idx].clone();
-        let updated: Project = serde_json::from_value(project).map_err(|e| e.to_string())?;
+        let updated: Project = serde_json::from_value(project)?;
         let merged = Project {
             id: base.id,
             name: if updated.name.is_empty() { base.name } else { updated.name },
@@ -1636,39 +4523,94 @@ fn update_project(id: String, project: serde_json::Value) -> Result<Project, Str
             created_at: base.created_at,
             updated_at: Some(now),
         };
-        projects[idx] = merged.clone();
-        let json = serde_json::to_string(&projects).map_err(|e| e.to_string())?;
-        db::save_projects_json(conn, &json)?;
+        db::save_project(conn, &merged)?;
         Ok(merged)
     })
 }
 
+/// Move a project to the trash. policy "cascade" takes its plan tickets, milestones, ideas and implementation log with
+/// it; "restrict" refuses while any of them exist. There is no default: the caller chooses. empty_trash deletes it for
+/// good.
 #[tauri::command]
-fn delete_project(id: String) -> Result<(), String> {
+fn delete_project(id: String, policy: String) -> AppResult<()> {
+    let cascade = match policy.trim() {
+        "cascade" => true,
+        "restrict" => false,
+        other => {
+            return Err(AppError::invalid(format!(
+                "Unknown delete policy: {:?} (expected \"cascade\" or \"restrict\")",
+                other
+            )))
+        }
+    };
     with_db(|conn| {
-        let mut projects = list_projects_impl(conn)?;
-        let len = projects.len();
-        projects.retain(|p| p.id != id);
-        if projects.len() == len {
-            return Err("Project not found".to_string());
-        }
-        let json = serde_json::to_string(&projects).map_err(|e| e.to_string())?;
-        db::save_projects_json(conn, &json)?;
+        if !db::delete_project(conn, &id, cascade)? {
+            return Err(AppError::not_found("Project not found"));
+        }
         Ok(())
     })
 }
 
+/// Counts of the data a cascading delete_project would remove.
+#[tauri::command]
+fn get_project_dependents(id: String) -> AppResult<db::ProjectDependents> {
+    with_db(|conn| db::get_project_dependents(conn, &id))
+}
+
+/// Deleted projects, plan tickets, tickets, features, prompts and designs, most recent first. With project_id: that
+/// project and its plan tickets.
+#[tauri::command]
+fn list_trash(project_id: Option<String>) -> AppResult<Vec<db::TrashItem>> {
+    with_db(|conn| db::list_trash(conn, project_id.as_deref()))
+}
+
+/// Args for restore_from_trash. In built app, IPC expects payload key `args`.
+#[derive(serde::Deserialize, TS)]
+#[ts(export)]
+struct RestoreFromTrashArgs {
+    kind: db::TrashKind,
+    id: String,
+    actor: Option<db::Actor>,
+}
+
+/// Take a row out of the trash; returns the restored item.
+#[tauri::command]
+fn restore_from_trash(args: RestoreFromTrashArgs) -> AppResult<db::TrashItem> {
+    let actor = args.actor.unwrap_or_default();
+    with_db(|conn| db::restore_from_trash(conn, args.kind, &args.id, &actor))
+}
+
+/// Args for empty_trash. In built app, IPC expects payload key `args`.
+#[derive(serde::Deserialize, TS)]
+#[ts(export)]
+struct EmptyTrashArgs {
+    /// Only this kind; everything when omitted.
+    kind: Option<db::TrashKind>,
+    /// Only rows deleted more than this many days ago.
+    #[serde(alias = "olderThanDays")]
+    #[ts(as = "Option<f64>")]
+    older_than_days: Option<i64>,
+}
+
+/// Permanently delete rows in the trash. Returns how many were deleted.
+#[tauri::command]
+fn empty_trash(args: EmptyTrashArgs) -> AppResult<usize> {
+    with_db(|conn| db::empty_trash(conn, args.kind, args.older_than_days))
+}
+
 /// Resolved project: project + linked prompts, tickets, features, ideas (empty), designs, architectures (empty).
 #[tauri::command]
-fn get_project_resolved(id: String) -> Result<serde_json::Value, String> {
-    let project = with_db(|conn| {
-        let projects = list_projects_impl(conn)?;
-        projects.into_iter().find(|p| p.id == id).ok_or("Project not found".to_string())
+fn get_project_resolved(id: String) -> AppResult<serde_json::Value> {
+    let (project, tickets, features, prompts, designs) = with_db(|conn| {
+        let project = db::get_project(conn, &id)?.ok_or_else(|| AppError::not_found("Project not found"))?;
+        Ok((
+            project,
+            db::get_tickets(conn).unwrap_or_default(),
+            db::get_features(conn).unwrap_or_default(),
+            db::get_prompts(conn).unwrap_or_default(),
+            db::get_designs(conn).unwrap_or_default(),
+        ))
     })?;
-    let tickets = with_db(db::get_tickets).unwrap_or_default();
-    let features = with_db(db::get_features).unwrap_or_default();
-    let prompts = with_db(db::get_prompts).unwrap_or_default();
-    let designs = with_db(db::get_designs).unwrap_or_default();
     let prompt_ids: Vec<i64> = project.prompt_ids.iter().copied().collect();
     let ticket_ids: Vec<String> = project.ticket_ids.clone();
     let feature_ids: Vec<String> = project.feature_ids.clone();
@@ -1745,7 +4687,7 @@ fn get_project_resolved(id: String) -> Result<serde_json::Value, String> {
 }
 
 #[tauri::command]
-fn get_project_export(id: String, category: String) -> Result<String, String> {
+fn get_project_export(id: String, category: String) -> AppResult<String> {
     let resolved = get_project_resolved(id)?;
     let value = match category.as_str() {
         "prompts" => resolved.get("prompts"),
@@ -1755,23 +4697,23 @@ fn get_project_export(id: String, category: String) -> Result<String, String> {
         "designs" => resolved.get("designs"),
         "architectures" => resolved.get("architectures"),
         "project" => Some(&resolved),
-        _ => return Err(format!("Unknown category: {}", category)),
+        _ => return Err(AppError::invalid(format!("Unknown category: {}", category))),
     };
     let out = value
-        .map(|v| serde_json::to_string(v).map_err(|e| e.to_string()))
+        .map(|v| serde_json::to_string(v).map_err(AppError::from))
         .unwrap_or_else(|| Ok("[]".to_string()))?;
     Ok(out)
 }
 
 #[tauri::command]
-fn get_all_projects() -> Result<Vec<String>, String> {
+fn get_all_projects() -> AppResult<Vec<String>> {
     with_db(db::get_all_projects)
 }
 
 /// Collect direct subdirectory paths under `dir` only (one level). Include every entry that is a directory or a symlink (so we don't drop folders or symlinks on macOS).
-fn list_subdir_paths(dir: &Path) -> Result<Vec<String>, String> {
+fn list_subdir_paths(dir: &Path) -> AppResult<Vec<String>> {
     let mut paths = vec![];
-    let entries = std::fs::read_dir(dir).map_err(|e| e.to_string())?;
+    let entries = std::fs::read_dir(dir)?;
     for entry in entries {
         let entry = match entry {
             Ok(e) => e,
@@ -1990,9 +4932,9 @@ fn resolve_february_dirs() -> Vec<PathBuf> {
 }
 
 /// Debug: list every read_dir entry with included/is_dir/is_symlink so we can see why a folder is skipped.
-fn list_subdir_paths_debug(dir: &Path) -> Result<Vec<FebruaryFolderDebugEntry>, String> {
+fn list_subdir_paths_debug(dir: &Path) -> AppResult<Vec<FebruaryFolderDebugEntry>> {
     let mut out = vec![];
-    let entries = std::fs::read_dir(dir).map_err(|e| e.to_string())?;
+    let entries = std::fs::read_dir(dir)?;
     for entry in entries {
         let (name_str, is_dir, is_symlink, file_type_err) = match &entry {
             Ok(e) => {
@@ -2035,7 +4977,7 @@ fn list_subdir_paths_debug(dir: &Path) -> Result<Vec<FebruaryFolderDebugEntry>,
 
 /// Debug: return raw folder names from the first configured root (to verify what the backend sees).
 #[tauri::command]
-fn list_february_folders_debug() -> Result<serde_json::Value, String> {
+fn list_february_folders_debug() -> AppResult<serde_json::Value> {
     let mut candidates: Vec<PathBuf> = resolve_february_dirs()
         .into_iter()
         .filter(|pb| pb.is_dir())
@@ -2067,7 +5009,7 @@ fn list_february_folders_debug() -> Result<serde_json::Value, String> {
 
 /// Debug: return every read_dir entry with included/is_dir/is_symlink/file_type_err so we can see why a folder is skipped.
 #[tauri::command]
-fn list_february_folders_debug_entries() -> Result<serde_json::Value, String> {
+fn list_february_folders_debug_entries() -> AppResult<serde_json::Value> {
     let mut candidates: Vec<PathBuf> = resolve_february_dirs()
         .into_iter()
         .filter(|pb| pb.is_dir())
@@ -2110,7 +5052,7 @@ fn list_february_folders_debug_entries() -> Result<serde_json::Value, String> {
 /// List all subdirectories of the configured projects root(s). Used by Projects page Local repos card.
 /// No filter by name—every folder is included. Paths from data/february-dir.txt (one per line) or FEBRUARY_DIR (; or , separated); else parent of project root.
 #[tauri::command]
-fn list_february_folders() -> Result<Vec<String>, String> {
+fn list_february_folders() -> AppResult<Vec<String>> {
     let mut candidates: Vec<PathBuf> = resolve_february_dirs()
         .into_iter()
         .filter(|pb| pb.is_dir())
@@ -2146,23 +5088,24 @@ fn list_february_folders() -> Result<Vec<String>, String> {
 }
 
 #[tauri::command]
-fn get_active_projects() -> Result<Vec<String>, String> {
+fn get_active_projects() -> AppResult<Vec<String>> {
     with_db(db::get_active_projects)
 }
 
 #[tauri::command]
-fn get_prompts() -> Result<Vec<Prompt>, String> {
+fn get_prompts() -> AppResult<Vec<Prompt>> {
     with_db(db::get_prompts)
 }
 
+/// Replace all prompts; ones left out go to the trash. Leaving out every prompt needs `clear_all`.
 #[tauri::command]
-fn save_prompts(prompts: Vec<Prompt>) -> Result<(), String> {
-    with_db(|conn| db::save_prompts(conn, &prompts))
+fn save_prompts(prompts: Vec<Prompt>, clear_all: Option<bool>) -> AppResult<()> {
+    with_db(|conn| db::save_prompts(conn, &prompts, clear_all.unwrap_or(false)))
 }
 
 #[tauri::command]
-fn add_prompt(title: String, content: String) -> Result<Prompt, String> {
-    let mut prompts = with_db(db::get_prompts).map_err(|e| e.to_string())?;
+fn add_prompt(title: String, content: String) -> AppResult<Prompt> {
+    let mut prompts = with_db(db::get_prompts)?;
     let next_id: i64 = prompts
         .iter()
         .filter_map(|p| p.id.parse().ok())
@@ -2178,64 +5121,74 @@ fn add_prompt(title: String, content: String) -> Result<Prompt, String> {
         updated_at: now,
     };
     prompts.push(new_prompt.clone());
-    with_db(|conn| db::save_prompts(conn, &prompts))?;
+    with_db(|conn| db::save_prompts(conn, &prompts, false))?;
     Ok(new_prompt)
 }
 
 #[tauri::command]
-fn get_designs() -> Result<Vec<Design>, String> {
+fn get_designs() -> AppResult<Vec<Design>> {
     with_db(db::get_designs)
 }
 
+/// Replace all designs; ones left out go to the trash. Leaving out every design needs `clear_all`.
 #[tauri::command]
-fn save_designs(designs: Vec<Design>) -> Result<(), String> {
-    with_db(|conn| db::save_designs(conn, &designs))
+fn save_designs(designs: Vec<Design>, clear_all: Option<bool>) -> AppResult<()> {
+    with_db(|conn| db::save_designs(conn, &designs, clear_all.unwrap_or(false)))
 }
 
 #[tauri::command]
-fn save_active_projects(projects: Vec<String>) -> Result<(), String> {
+fn save_active_projects(projects: Vec<String>) -> AppResult<()> {
     with_db(|conn| db::save_active_projects(conn, &projects))
 }
 
 #[tauri::command]
-fn get_tickets() -> Result<Vec<Ticket>, String> {
+fn get_tickets() -> AppResult<Vec<Ticket>> {
     with_db(db::get_tickets)
 }
 
+/// Replace all tickets; ones left out go to the trash. Leaving out every ticket needs `clear_all`.
 #[tauri::command]
-fn save_tickets(tickets: Vec<Ticket>) -> Result<(), String> {
-    with_db(|conn| db::save_tickets(conn, &tickets))
+fn save_tickets(tickets: Vec<Ticket>, clear_all: Option<bool>) -> AppResult<()> {
+    with_db(|conn| db::save_tickets(conn, &tickets, clear_all.unwrap_or(false)))
 }
 
 #[tauri::command]
-fn get_features() -> Result<Vec<Feature>, String> {
+fn get_features() -> AppResult<Vec<Feature>> {
     with_db(db::get_features)
 }
 
+/// Replace all features; ones left out go to the trash. Leaving out every feature needs `clear_all`.
 #[tauri::command]
-fn save_features(features: Vec<Feature>) -> Result<(), String> {
-    with_db(|conn| db::save_features(conn, &features))
+fn save_features(features: Vec<Feature>, clear_all: Option<bool>) -> AppResult<()> {
+    with_db(|conn| db::save_features(conn, &features, clear_all.unwrap_or(false)))
 }
 
 #[tauri::command]
-fn get_kv_store_entries() -> Result<Vec<db::KvEntry>, String> {
+fn get_kv_store_entries() -> AppResult<Vec<db::KvEntry>> {
     with_db(db::get_kv_store_entries)
 }
 
+/// Schema version and applied migrations (with their pre-migration backups) for the Configuration page.
+#[tauri::command]
+fn get_schema_status() -> AppResult<db::SchemaStatus> {
+    with_db(db::get_schema_status)
+}
+
 /// Return the data directory path (from DB, ADR 069). Used by UI to show where data is stored.
 #[tauri::command]
-fn get_data_dir() -> Result<String, String> {
+fn get_data_dir() -> AppResult<String> {
     resolve_data_dir().map(|p| p.to_string_lossy().to_string())
 }
 
 /// Path to february-dir.txt (one path per line = project roots). In dev: repo data dir; when bundled: app data dir so the built app can find config.
 #[tauri::command]
-fn get_february_dir_config_path() -> Result<String, String> {
+fn get_february_dir_config_path() -> AppResult<String> {
     data_root().map(|d| d.join("february-dir.txt").to_string_lossy().to_string())
 }
 
 /// Aggregated counts for the dashboard metrics view.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export)]
 pub struct DashboardMetrics {
     pub tickets_count: u32,
     pub features_count: u32,
@@ -2246,7 +5199,7 @@ pub struct DashboardMetrics {
 }
 
 #[tauri::command]
-fn get_dashboard_metrics() -> Result<DashboardMetrics, String> {
+fn get_dashboard_metrics() -> AppResult<DashboardMetrics> {
     with_db(|conn| {
         let tickets = db::get_tickets(conn)?;
         let features = db::get_features(conn)?;
@@ -2275,7 +5228,7 @@ fn run_script_inner(
     combined_prompt: Option<String>,
     active_projects: Vec<String>,
     timing: TimingParams,
-) -> Result<(), String> {
+) -> AppResult<()> {
     let run_label_clone = run_label.clone();
     let script = script_path(&ws);
     let prompt_ids_str: Vec<String> = prompt_ids.iter().map(|n| n.to_string()).collect();
@@ -2286,15 +5239,15 @@ fn run_script_inner(
     } else {
         let tmp = std::env::temp_dir().join(format!("run_prompts_{}.json", run_id));
         let content =
-            serde_json::to_string_pretty(&active_projects).map_err(|e| e.to_string())?;
-        std::fs::write(&tmp, content).map_err(|e| e.to_string())?;
+            serde_json::to_string_pretty(&active_projects)?;
+        std::fs::write(&tmp, content)?;
         tmp
     };
 
     let prompt_file_path: Option<PathBuf> = match combined_prompt {
         Some(content) => {
             let tmp = std::env::temp_dir().join(format!("run_combined_prompt_{}.txt", run_id));
-            std::fs::write(&tmp, content).map_err(|e| e.to_string())?;
+            std::fs::write(&tmp, content)?;
             Some(tmp)
         }
         None => None,
@@ -2324,12 +5277,12 @@ fn run_script_inner(
         .env("SLEEP_BETWEEN_PROJECTS", timing.sleep_between_projects.to_string())
         .env("SLEEP_BETWEEN_ROUNDS", timing.sleep_between_rounds.to_string());
 
-    let mut child = cmd.spawn().map_err(|e| e.to_string())?;
+    let mut child = cmd.spawn()?;
     let stdout = child.stdout.take().ok_or("no stdout")?;
     let stderr = child.stderr.take().ok_or("no stderr")?;
 
     {
-        let mut guard = state.runs.lock().map_err(|e| e.to_string())?;
+        let mut guard = state.runs.lock()?;
         guard.insert(
             run_id.clone(),
             RunEntry {
@@ -2418,14 +5371,14 @@ fn run_analysis_script_inner(
     run_id: String,
     run_label: String,
     project_path: String,
-) -> Result<(), String> {
+) -> AppResult<()> {
     let run_label_clone = run_label.clone();
     let script = analysis_script_path(&ws);
     if !script.exists() {
-        return Err(format!(
+        return Err(AppError::not_found(format!(
             "Analysis script not found: {}",
             script.to_string_lossy()
-        ));
+        )));
     }
     let mut cmd = Command::new("bash");
     cmd.arg(script.as_os_str())
@@ -2437,12 +5390,12 @@ fn run_analysis_script_inner(
     #[cfg(unix)]
     cmd.process_group(0);
 
-    let mut child = cmd.spawn().map_err(|e| e.to_string())?;
+    let mut child = cmd.spawn()?;
     let stdout = child.stdout.take().ok_or("no stdout")?;
     let stderr = child.stderr.take().ok_or("no stderr")?;
 
     {
-        let mut guard = state.runs.lock().map_err(|e| e.to_string())?;
+        let mut guard = state.runs.lock()?;
         guard.insert(
             run_id.clone(),
             RunEntry {
@@ -2533,19 +5486,19 @@ fn run_implement_all_script_inner(
     project_path: String,
     slot: Option<u8>,
     prompt_content: Option<String>,
-) -> Result<(), String> {
+) -> AppResult<()> {
     let run_label_clone = run_label.clone();
     let script = implement_all_script_path(&ws);
     if !script.exists() {
-        return Err(format!(
+        return Err(AppError::not_found(format!(
             "Implement All script not found: {}",
             script.to_string_lossy()
-        ));
+        )));
     }
     let prompt_path: Option<PathBuf> = match &prompt_content {
         Some(content) => {
             let p = std::env::temp_dir().join(format!("kw_implement_all_prompt_{}.txt", run_id));
-            std::fs::write(&p, content).map_err(|e| e.to_string())?;
+            std::fs::write(&p, content)?;
             Some(p)
         }
         None => None,
@@ -2568,12 +5521,12 @@ fn run_implement_all_script_inner(
     #[cfg(unix)]
     cmd.process_group(0);
 
-    let mut child = cmd.spawn().map_err(|e| e.to_string())?;
+    let mut child = cmd.spawn()?;
     let stdout = child.stdout.take().ok_or("no stdout")?;
     let stderr = child.stderr.take().ok_or("no stderr")?;
 
     {
-        let mut guard = state.runs.lock().map_err(|e| e.to_string())?;
+        let mut guard = state.runs.lock()?;
         guard.insert(
             run_id.clone(),
             RunEntry {
@@ -2667,13 +5620,13 @@ fn run_run_terminal_agent_script_inner(
     project_path: String,
     prompt_content: String,
     agent_mode: Option<String>,
-) -> Result<(), String> {
+) -> AppResult<()> {
     let run_label_clone = run_label.clone();
     if !script_path.exists() {
-        return Err(format!(
+        return Err(AppError::not_found(format!(
             "Run terminal agent script not found: {}",
             script_path.to_string_lossy()
-        ));
+        )));
     }
     // Write prompt file inside the project dir so the script (and sandboxed child) can always read it.
     let p = Path::new(&project_path).join(format!(".kwcode_run_prompt_{}.txt", run_id));
@@ -2735,14 +5688,14 @@ fn run_run_terminal_agent_script_inner(
             let err_s = e.to_string();
             session_log_c29a12("lib.rs:run_run_terminal_agent_script_inner", "spawn failed", &[("error", &err_s)], "H5");
             session_log_415745("lib.rs:run_run_terminal_agent_script_inner", "spawn failed", &[("error", &err_s)], "H5");
-            return Err(err_s);
+            return Err(AppError::spawn_failed(err_s));
         }
     };
     let stdout = child.stdout.take().ok_or("no stdout")?;
     let stderr = child.stderr.take().ok_or("no stderr")?;
 
     {
-        let mut guard = state.runs.lock().map_err(|e| e.to_string())?;
+        let mut guard = state.runs.lock()?;
         guard.insert(
             run_id.clone(),
             RunEntry {
@@ -2827,11 +5780,11 @@ fn run_run_terminal_agent_script_inner(
 /// Opens 3 Terminal.app windows (macOS only), each running `cd project_path && agent`.
 /// Gives you a real TTY so Cursor CLI runs in interactive mode instead of "print mode".
 #[tauri::command]
-async fn open_implement_all_in_system_terminal(project_path: String) -> Result<(), String> {
+async fn open_implement_all_in_system_terminal(project_path: String) -> AppResult<()> {
     #[cfg(not(target_os = "macos"))]
     {
         let _ = project_path;
-        return Err("Open in system terminal is only supported on macOS.".to_string());
+        return Err(AppError::invalid("Open in system terminal is only supported on macOS."));
     }
     #[cfg(target_os = "macos")]
     {
@@ -2849,7 +5802,7 @@ async fn open_implement_all_in_system_terminal(project_path: String) -> Result<(
                 .arg("-e")
                 .arg(&script)
                 .spawn()
-                .map_err(|e| format!("Failed to open Terminal: {}", e))?;
+                .map_err(|e| AppError::spawn_failed(format!("Failed to open Terminal: {}", e)))?;
         }
         Ok(())
     }
@@ -2857,11 +5810,11 @@ async fn open_implement_all_in_system_terminal(project_path: String) -> Result<(
 
 /// Opens Terminal.app (macOS) and runs `npm run <script_name>` in the project directory.
 #[tauri::command]
-async fn run_npm_script_in_external_terminal(project_path: String, script_name: String) -> Result<(), String> {
+async fn run_npm_script_in_external_terminal(project_path: String, script_name: String) -> AppResult<()> {
     #[cfg(not(target_os = "macos"))]
     {
         let _ = (project_path, script_name);
-        return Err("External terminal is only supported on macOS.".to_string());
+        return Err(AppError::invalid("External terminal is only supported on macOS."));
     }
     #[cfg(target_os = "macos")]
     {
@@ -2870,13 +5823,13 @@ async fn run_npm_script_in_external_terminal(project_path: String, script_name:
                 .chars()
                 .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_' || c == ':')
         {
-            return Err("Invalid script name: only letters, numbers, hyphen, underscore and colon allowed".to_string());
+            return Err(AppError::invalid("Invalid script name: only letters, numbers, hyphen, underscore and colon allowed"));
         }
         let dir = Path::new(&project_path)
             .canonicalize()
-            .map_err(|e| format!("Project path invalid: {}", e))?;
+            .map_err(|e| AppError::invalid(format!("Project path invalid: {}", e)))?;
         if !dir.is_dir() {
-            return Err("Project path is not a directory".to_string());
+            return Err(AppError::invalid("Project path is not a directory"));
         }
         let path_str = dir.to_string_lossy();
         let path_escaped = path_str.replace('\'', "'\\''");
@@ -2890,7 +5843,7 @@ async fn run_npm_script_in_external_terminal(project_path: String, script_name:
             .arg("-e")
             .arg(&script)
             .spawn()
-            .map_err(|e| format!("Failed to open Terminal: {}", e))?;
+            .map_err(|e| AppError::spawn_failed(format!("Failed to open Terminal: {}", e)))?;
         Ok(())
     }
 }
@@ -2898,10 +5851,10 @@ async fn run_npm_script_in_external_terminal(project_path: String, script_name:
 /// Opens Terminal.app (macOS) and runs `npm run build:desktop` in the current working directory.
 /// Use when running the app via `tauri dev` so the cwd is the project root.
 #[tauri::command]
-fn run_build_desktop() -> Result<(), String> {
+fn run_build_desktop() -> AppResult<()> {
     #[cfg(not(target_os = "macos"))]
     {
-        return Err("Rebuild on desktop is only supported on macOS.".to_string());
+        return Err(AppError::invalid("Rebuild on desktop is only supported on macOS."));
     }
     #[cfg(target_os = "macos")]
     {
@@ -2918,7 +5871,7 @@ fn run_build_desktop() -> Result<(), String> {
             .arg("-e")
             .arg(&script)
             .spawn()
-            .map_err(|e| format!("Failed to open Terminal: {}", e))?;
+            .map_err(|e| AppError::spawn_failed(format!("Failed to open Terminal: {}", e)))?;
         Ok(())
     }
 }
@@ -2926,19 +5879,19 @@ fn run_build_desktop() -> Result<(), String> {
 /// Opens one system terminal (Terminal.app on macOS) with the project path as the current working directory.
 /// On non-macOS returns an error; same as open_implement_all_in_system_terminal and run_npm_script_in_external_terminal.
 #[tauri::command]
-fn open_project_in_system_terminal(project_path: String) -> Result<(), String> {
+fn open_project_in_system_terminal(project_path: String) -> AppResult<()> {
     #[cfg(not(target_os = "macos"))]
     {
         let _ = project_path;
-        return Err("Open in terminal is only supported on macOS.".to_string());
+        return Err(AppError::invalid("Open in terminal is only supported on macOS."));
     }
     #[cfg(target_os = "macos")]
     {
         let dir = Path::new(project_path.trim())
             .canonicalize()
-            .map_err(|e| format!("Project path invalid: {}", e))?;
+            .map_err(|e| AppError::invalid(format!("Project path invalid: {}", e)))?;
         if !dir.is_dir() {
-            return Err("Project path is not a directory".to_string());
+            return Err(AppError::invalid("Project path is not a directory"));
         }
         let path_str = dir.to_string_lossy();
         let path_escaped = path_str.replace('\'', "'\\''");
@@ -2952,7 +5905,7 @@ fn open_project_in_system_terminal(project_path: String) -> Result<(), String> {
             .arg("-e")
             .arg(&script)
             .spawn()
-            .map_err(|e| format!("Failed to open Terminal: {}", e))?;
+            .map_err(|e| AppError::spawn_failed(format!("Failed to open Terminal: {}", e)))?;
         Ok(())
     }
 }
@@ -2961,16 +5914,16 @@ fn open_project_in_system_terminal(project_path: String) -> Result<(), String> {
 /// `editor` must be "cursor" or "vscode". On macOS uses `open -a "Cursor" path` / `open -a "Visual Studio Code" path`;
 /// on Windows/Linux spawns the editor CLI with the path (cursor/code in PATH when installed).
 #[tauri::command]
-fn open_project_in_editor(project_path: String, editor: String) -> Result<(), String> {
+fn open_project_in_editor(project_path: String, editor: String) -> AppResult<()> {
     let path = project_path.trim();
     if path.is_empty() {
-        return Err("Project path is empty".to_string());
+        return Err(AppError::invalid("Project path is empty"));
     }
     let dir = Path::new(path)
         .canonicalize()
-        .map_err(|e| format!("Invalid project path: {}", e))?;
+        .map_err(|e| AppError::invalid(format!("Invalid project path: {}", e)))?;
     if !dir.is_dir() {
-        return Err("Project path is not a directory".to_string());
+        return Err(AppError::invalid("Project path is not a directory"));
     }
     let path_str = dir.to_string_lossy();
     let editor_lower = editor.trim().to_lowercase();
@@ -2980,14 +5933,14 @@ fn open_project_in_editor(project_path: String, editor: String) -> Result<(), St
         let (app_name, label) = match editor_lower.as_str() {
             "cursor" => ("Cursor", "Cursor"),
             "vscode" | "code" => ("Visual Studio Code", "VS Code"),
-            _ => return Err(format!("Unknown editor '{}'. Use 'cursor' or 'vscode'.", editor.trim())),
+            _ => return Err(AppError::invalid(format!("Unknown editor '{}'. Use 'cursor' or 'vscode'.", editor.trim()))),
         };
         Command::new("open")
             .arg("-a")
             .arg(app_name)
             .arg(path_str.as_ref())
             .spawn()
-            .map_err(|e| format!("Failed to open in {}: {}", label, e))?;
+            .map_err(|e| AppError::spawn_failed(format!("Failed to open in {}: {}", label, e)))?;
         Ok(())
     }
 
@@ -2996,19 +5949,19 @@ fn open_project_in_editor(project_path: String, editor: String) -> Result<(), St
         let (bin, label) = match editor_lower.as_str() {
             "cursor" => ("cursor", "Cursor"),
             "vscode" | "code" => ("code", "VS Code"),
-            _ => return Err(format!("Unknown editor '{}'. Use 'cursor' or 'vscode'.", editor.trim())),
+            _ => return Err(AppError::invalid(format!("Unknown editor '{}'. Use 'cursor' or 'vscode'.", editor.trim()))),
         };
         Command::new(bin)
             .arg(path_str.as_ref())
             .spawn()
-            .map_err(|e| format!("Failed to open in {}: {}. Is {} installed and in PATH?", label, e, label))?;
+            .map_err(|e| AppError::spawn_failed(format!("Failed to open in {}: {}. Is {} installed and in PATH?", label, e, label)))?;
         Ok(())
     }
 }
 
 /// Opens the given directory in the system file manager (Finder on macOS, Explorer on Windows, xdg-open on Linux).
 /// Caller must ensure `dir` exists and is a directory.
-fn open_dir_in_file_manager(dir: &Path) -> Result<(), String> {
+fn open_dir_in_file_manager(dir: &Path) -> AppResult<()> {
     let path_str = dir.to_string_lossy();
 
     #[cfg(target_os = "macos")]
@@ -3016,7 +5969,7 @@ fn open_dir_in_file_manager(dir: &Path) -> Result<(), String> {
         Command::new("open")
             .arg(path_str.as_ref())
             .spawn()
-            .map_err(|e| format!("Failed to open in Finder: {}", e))?;
+            .map_err(|e| AppError::spawn_failed(format!("Failed to open in Finder: {}", e)))?;
     }
 
     #[cfg(target_os = "windows")]
@@ -3025,7 +5978,7 @@ fn open_dir_in_file_manager(dir: &Path) -> Result<(), String> {
         Command::new("explorer")
             .arg(&path_win)
             .spawn()
-            .map_err(|e| format!("Failed to open in Explorer: {}", e))?;
+            .map_err(|e| AppError::spawn_failed(format!("Failed to open in Explorer: {}", e)))?;
     }
 
     #[cfg(not(any(target_os = "macos", target_os = "windows")))]
@@ -3033,7 +5986,7 @@ fn open_dir_in_file_manager(dir: &Path) -> Result<(), String> {
         Command::new("xdg-open")
             .arg(path_str.as_ref())
             .spawn()
-            .map_err(|e| format!("Failed to open in file manager: {}", e))?;
+            .map_err(|e| AppError::spawn_failed(format!("Failed to open in file manager: {}", e)))?;
     }
 
     Ok(())
@@ -3041,23 +5994,23 @@ fn open_dir_in_file_manager(dir: &Path) -> Result<(), String> {
 
 /// Opens the given path in the system file manager (Finder on macOS, Explorer on Windows, xdg-open on Linux).
 #[tauri::command]
-fn open_path_in_file_manager(path: String) -> Result<(), String> {
+fn open_path_in_file_manager(path: String) -> AppResult<()> {
     let path = path.trim();
     if path.is_empty() {
-        return Err("Path is empty".to_string());
+        return Err(AppError::invalid("Path is empty"));
     }
     let dir = Path::new(path)
         .canonicalize()
-        .map_err(|e| format!("Invalid path: {}", e))?;
+        .map_err(|e| AppError::invalid(format!("Invalid path: {}", e)))?;
     if !dir.is_dir() {
-        return Err("Path is not a directory".to_string());
+        return Err(AppError::invalid("Path is not a directory"));
     }
     open_dir_in_file_manager(&dir)
 }
 
 /// Opens the app repo's .cursor/documentation folder (or .cursor if documentation subfolder is missing) in the system file manager.
 #[tauri::command]
-fn open_documentation_folder() -> Result<(), String> {
+fn open_documentation_folder() -> AppResult<()> {
     let root = project_root()?;
     let doc_dir = root.join(".cursor").join("documentation");
     let cursor_dir = root.join(".cursor");
@@ -3066,14 +6019,14 @@ fn open_documentation_folder() -> Result<(), String> {
     } else if cursor_dir.is_dir() {
         cursor_dir
     } else {
-        return Err("Documentation folder (.cursor/documentation or .cursor) not found.".to_string());
+        return Err(AppError::not_found("Documentation folder (.cursor/documentation or .cursor) not found."));
     };
     open_dir_in_file_manager(&to_open)
 }
 
 /// Returns the app repo's .cursor/documentation folder path (or .cursor if documentation subfolder is missing). Used for copy-to-clipboard (ADR 0215).
 #[tauri::command]
-fn get_documentation_folder_path() -> Result<String, String> {
+fn get_documentation_folder_path() -> AppResult<String> {
     let root = project_root()?;
     let doc_dir = root.join(".cursor").join("documentation");
     let cursor_dir = root.join(".cursor");
@@ -3082,14 +6035,14 @@ fn get_documentation_folder_path() -> Result<String, String> {
     } else if cursor_dir.is_dir() {
         cursor_dir
     } else {
-        return Err("Documentation folder (.cursor/documentation or .cursor) not found.".to_string());
+        return Err(AppError::not_found("Documentation folder (.cursor/documentation or .cursor) not found."));
     };
     Ok(to_open.to_string_lossy().to_string())
 }
 
 /// Opens the app repo's .cursor/technologies folder (or .cursor if technologies subfolder is missing) in the system file manager.
 #[tauri::command]
-fn open_technologies_folder() -> Result<(), String> {
+fn open_technologies_folder() -> AppResult<()> {
     let root = project_root()?;
     let tech_dir = root.join(".cursor").join("technologies");
     let cursor_dir = root.join(".cursor");
@@ -3098,14 +6051,14 @@ fn open_technologies_folder() -> Result<(), String> {
     } else if cursor_dir.is_dir() {
         cursor_dir
     } else {
-        return Err("Technologies folder (.cursor/technologies or .cursor) not found.".to_string());
+        return Err(AppError::not_found("Technologies folder (.cursor/technologies or .cursor) not found."));
     };
     open_dir_in_file_manager(&to_open)
 }
 
 /// Returns the app repo's .cursor/technologies folder path (or .cursor if technologies subfolder is missing). Used for copy-to-clipboard (ADR 0216).
 #[tauri::command]
-fn get_technologies_folder_path() -> Result<String, String> {
+fn get_technologies_folder_path() -> AppResult<String> {
     let root = project_root()?;
     let tech_dir = root.join(".cursor").join("technologies");
     let cursor_dir = root.join(".cursor");
@@ -3114,14 +6067,14 @@ fn get_technologies_folder_path() -> Result<String, String> {
     } else if cursor_dir.is_dir() {
         cursor_dir
     } else {
-        return Err("Technologies folder (.cursor/technologies or .cursor) not found.".to_string());
+        return Err(AppError::not_found("Technologies folder (.cursor/technologies or .cursor) not found."));
     };
     Ok(path.to_string_lossy().to_string())
 }
 
 /// Opens the app repo's .cursor/0. ideas folder (or .cursor if that subfolder is missing) in the system file manager.
 #[tauri::command]
-fn open_ideas_folder() -> Result<(), String> {
+fn open_ideas_folder() -> AppResult<()> {
     let root = project_root()?;
     let ideas_dir = root.join(".cursor").join("0. ideas");
     let cursor_dir = root.join(".cursor");
@@ -3130,14 +6083,14 @@ fn open_ideas_folder() -> Result<(), String> {
     } else if cursor_dir.is_dir() {
         cursor_dir
     } else {
-        return Err("Ideas folder (.cursor/0. ideas or .cursor) not found.".to_string());
+        return Err(AppError::not_found("Ideas folder (.cursor/0. ideas or .cursor) not found."));
     };
     open_dir_in_file_manager(&to_open)
 }
 
 /// Returns the path to the ideas folder (.cursor/0. ideas or .cursor) for clipboard copy (ADR 0219).
 #[tauri::command]
-fn get_ideas_folder_path() -> Result<String, String> {
+fn get_ideas_folder_path() -> AppResult<String> {
     let root = project_root()?;
     let ideas_dir = root.join(".cursor").join("0. ideas");
     let cursor_dir = root.join(".cursor");
@@ -3146,14 +6099,14 @@ fn get_ideas_folder_path() -> Result<String, String> {
     } else if cursor_dir.is_dir() {
         cursor_dir
     } else {
-        return Err("Ideas folder (.cursor/0. ideas or .cursor) not found.".to_string());
+        return Err(AppError::not_found("Ideas folder (.cursor/0. ideas or .cursor) not found."));
     };
     Ok(path.to_string_lossy().to_string())
 }
 
 /// Opens the app repo's .cursor/7. planner folder (or .cursor if that subfolder is missing) in the system file manager.
 #[tauri::command]
-fn open_planner_folder() -> Result<(), String> {
+fn open_planner_folder() -> AppResult<()> {
     let root = project_root()?;
     let planner_dir = root.join(".cursor").join("7. planner");
     let cursor_dir = root.join(".cursor");
@@ -3162,14 +6115,14 @@ fn open_planner_folder() -> Result<(), String> {
     } else if cursor_dir.is_dir() {
         cursor_dir
     } else {
-        return Err("Planner folder (.cursor/7. planner or .cursor) not found.".to_string());
+        return Err(AppError::not_found("Planner folder (.cursor/7. planner or .cursor) not found."));
     };
     open_dir_in_file_manager(&to_open)
 }
 
 /// Returns the app repo's .cursor/7. planner folder path (or .cursor if that subfolder is missing). Used for copy-to-clipboard.
 #[tauri::command]
-fn get_planner_folder_path() -> Result<String, String> {
+fn get_planner_folder_path() -> AppResult<String> {
     let root = project_root()?;
     let planner_dir = root.join(".cursor").join("7. planner");
     let cursor_dir = root.join(".cursor");
@@ -3178,14 +6131,14 @@ fn get_planner_folder_path() -> Result<String, String> {
     } else if cursor_dir.is_dir() {
         cursor_dir
     } else {
-        return Err("Planner folder (.cursor/7. planner or .cursor) not found.".to_string());
+        return Err(AppError::not_found("Planner folder (.cursor/7. planner or .cursor) not found."));
     };
     Ok(path.to_string_lossy().to_string())
 }
 
 /// Opens the app repo's .cursor/milestones folder (or .cursor if that subfolder is missing) in the system file manager.
 #[tauri::command]
-fn open_milestones_folder() -> Result<(), String> {
+fn open_milestones_folder() -> AppResult<()> {
     let root = project_root()?;
     let milestones_dir = root.join(".cursor").join("milestones");
     let cursor_dir = root.join(".cursor");
@@ -3194,14 +6147,14 @@ fn open_milestones_folder() -> Result<(), String> {
     } else if cursor_dir.is_dir() {
         cursor_dir
     } else {
-        return Err("Milestones folder (.cursor/milestones or .cursor) not found.".to_string());
+        return Err(AppError::not_found("Milestones folder (.cursor/milestones or .cursor) not found."));
     };
     open_dir_in_file_manager(&to_open)
 }
 
 /// Returns the app repo's .cursor/milestones folder path (or .cursor if that subfolder is missing). Used for copy-to-clipboard.
 #[tauri::command]
-fn get_milestones_folder_path() -> Result<String, String> {
+fn get_milestones_folder_path() -> AppResult<String> {
     let root = project_root()?;
     let milestones_dir = root.join(".cursor").join("milestones");
     let cursor_dir = root.join(".cursor");
@@ -3210,7 +6163,7 @@ fn get_milestones_folder_path() -> Result<String, String> {
     } else if cursor_dir.is_dir() {
         cursor_dir
     } else {
-        return Err("Milestones folder (.cursor/milestones or .cursor) not found.".to_string());
+        return Err(AppError::not_found("Milestones folder (.cursor/milestones or .cursor) not found."));
     };
     Ok(path.to_string_lossy().to_string())
 }
@@ -3222,7 +6175,7 @@ async fn run_implement_all(
     project_path: String,
     slot: Option<u8>,
     prompt_content: Option<String>,
-) -> Result<RunIdResponse, String> {
+) -> AppResult<RunIdResponse> {
     let ws = project_root()?;
     let run_id = gen_run_id();
     let label = match slot {
@@ -3236,7 +6189,8 @@ async fn run_implement_all(
 }
 
 /// Args for run_run_terminal_agent; accept camelCase from frontend (projectPath, promptContent, label, agentMode).
-#[derive(serde::Deserialize)]
+#[derive(serde::Deserialize, TS)]
+#[ts(export)]
 struct RunTerminalAgentArgs {
     #[serde(alias = "projectPath")]
     project_path: String,
@@ -3253,7 +6207,7 @@ async fn run_run_terminal_agent(
     app: AppHandle,
     state: State<'_, RunningState>,
     args: RunTerminalAgentArgs,
-) -> Result<RunIdResponse, String> {
+) -> AppResult<RunIdResponse> {
     let RunTerminalAgentArgs {
         project_path,
         prompt_content,
@@ -3262,14 +6216,14 @@ async fn run_run_terminal_agent(
     } = args;
     let project_path = project_path.trim();
     if project_path.is_empty() {
-        return Err("Project path is required.".to_string());
+        return Err(AppError::invalid("Project path is required."));
     }
     let project_path_buf = PathBuf::from(project_path);
     if !project_path_buf.is_dir() {
-        return Err(format!(
+        return Err(AppError::not_found(format!(
             "Project path is not a directory or does not exist: {}",
             project_path
-        ));
+        )));
     }
     let project_path = project_path.to_string();
     // #region agent log
@@ -3298,7 +6252,7 @@ async fn run_run_terminal_agent(
             let resource = app
                 .path()
                 .resolve("run_terminal_agent.sh", BaseDirectory::Resource)
-                .map_err(|e| format!("Worker script not found in app bundle: {}", e))?;
+                .map_err(|e| AppError::not_found(format!("Worker script not found in app bundle: {}", e)))?;
             let dir = resource
                 .parent()
                 .map(|p| p.to_path_buf())
@@ -3337,7 +6291,7 @@ async fn run_analysis_script(
     app: AppHandle,
     state: State<'_, RunningState>,
     project_path: String,
-) -> Result<RunIdResponse, String> {
+) -> AppResult<RunIdResponse> {
     let ws = project_root()?;
     let run_id = gen_run_id();
     let label = format!("Analysis: {}", Path::new(&project_path).file_name().and_then(|n| n.to_str()).unwrap_or("project"));
@@ -3352,20 +6306,20 @@ fn run_npm_script_inner(
     run_label: String,
     project_path: String,
     script_name: String,
-) -> Result<(), String> {
+) -> AppResult<()> {
     // Allow only safe script names (alphanumeric, hyphen, underscore, colon for e.g. dev:full)
     if script_name.is_empty()
         || !script_name
             .chars()
             .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_' || c == ':')
     {
-        return Err("Invalid script name: only letters, numbers, hyphen, underscore and colon allowed".to_string());
+        return Err(AppError::invalid("Invalid script name: only letters, numbers, hyphen, underscore and colon allowed"));
     }
     let dir = Path::new(&project_path)
         .canonicalize()
-        .map_err(|e| format!("Project path invalid: {}", e))?;
+        .map_err(|e| AppError::invalid(format!("Project path invalid: {}", e)))?;
     if !dir.is_dir() {
-        return Err("Project path is not a directory".to_string());
+        return Err(AppError::invalid("Project path is not a directory"));
     }
 
     let run_label_clone = run_label.clone();
@@ -3378,12 +6332,12 @@ fn run_npm_script_inner(
     #[cfg(unix)]
     cmd.process_group(0);
 
-    let mut child = cmd.spawn().map_err(|e| e.to_string())?;
+    let mut child = cmd.spawn()?;
     let stdout = child.stdout.take().ok_or("no stdout")?;
     let stderr = child.stderr.take().ok_or("no stderr")?;
 
     {
-        let mut guard = state.runs.lock().map_err(|e| e.to_string())?;
+        let mut guard = state.runs.lock()?;
         guard.insert(
             run_id.clone(),
             RunEntry {
@@ -3471,7 +6425,7 @@ async fn run_npm_script(
     state: State<'_, RunningState>,
     project_path: String,
     script_name: String,
-) -> Result<RunIdResponse, String> {
+) -> AppResult<RunIdResponse> {
     let run_id = gen_run_id();
     let label = format!("npm run {}", script_name);
     run_npm_script_inner(app, state, run_id.clone(), label, project_path, script_name)?;
@@ -3483,7 +6437,7 @@ async fn run_script(
     app: AppHandle,
     state: State<'_, RunningState>,
     args: RunScriptArgs,
-) -> Result<RunIdResponse, String> {
+) -> AppResult<RunIdResponse> {
     let ws = project_root()?;
     if args.run_label.is_none() {
         save_active_projects(args.active_projects.clone())?;
@@ -3507,8 +6461,8 @@ async fn run_script(
 }
 
 #[tauri::command]
-fn list_running_runs(state: State<'_, RunningState>) -> Result<Vec<RunningRunInfo>, String> {
-    let guard = state.runs.lock().map_err(|e| e.to_string())?;
+fn list_running_runs(state: State<'_, RunningState>) -> AppResult<Vec<RunningRunInfo>> {
+    let guard = state.runs.lock()?;
     Ok(guard
         .iter()
         .map(|(run_id, entry)| RunningRunInfo {
@@ -3519,8 +6473,8 @@ fn list_running_runs(state: State<'_, RunningState>) -> Result<Vec<RunningRunInf
 }
 
 #[tauri::command]
-fn stop_run(state: State<'_, RunningState>, run_id: String) -> Result<(), String> {
-    let mut guard = state.runs.lock().map_err(|e| e.to_string())?;
+fn stop_run(state: State<'_, RunningState>, run_id: String) -> AppResult<()> {
+    let mut guard = state.runs.lock()?;
     if let Some(mut entry) = guard.remove(&run_id) {
         let pid = entry.child.id() as i32;
         #[cfg(unix)]
@@ -3533,8 +6487,8 @@ fn stop_run(state: State<'_, RunningState>, run_id: String) -> Result<(), String
 }
 
 #[tauri::command]
-fn stop_script(state: State<'_, RunningState>) -> Result<(), String> {
-    let mut guard = state.runs.lock().map_err(|e| e.to_string())?;
+fn stop_script(state: State<'_, RunningState>) -> AppResult<()> {
+    let mut guard = state.runs.lock()?;
     for (_run_id, mut entry) in guard.drain() {
         let pid = entry.child.id() as i32;
         #[cfg(unix)]
@@ -3551,6 +6505,8 @@ pub fn run() {
     tauri::Builder::default()
         .plugin(tauri_plugin_dialog::init())
         .manage(RunningState::default())
+        .manage(db_state())
+        .manage(PlannerWatchState::default())
         .on_window_event(|window, event| {
             if let tauri::WindowEvent::CloseRequested { .. } = event {
                 let app = window.app_handle();
@@ -3569,6 +6525,11 @@ pub fn run() {
             }
         })
         .setup(|app| {
+            // Open the database (migrations, seeding) up front rather than inside the first command.
+            if let Err(e) = app.state::<Arc<DbState>>().with(|_| Ok(())) {
+                eprintln!("Failed to open database: {}", e);
+            }
+            spawn_backup_scheduler();
             // Workaround for macOS/Tauri bug: WebView often shows white instead of devUrl.
             // 1) Load a local loader HTML first (shows "kwcode" then redirects to dev server).
             // 2) Retry navigating to app URL at 2s, 4s, 6s in case loader redirect fails.
@@ -3639,8 +6600,26 @@ pub fn run() {
             unzip_cursor_init,
             write_spec_file,
             archive_cursor_file,
+            get_plan_workflow,
+            set_plan_workflow,
+            move_plan_ticket,
+            get_plan_ticket_dependencies,
+            add_plan_ticket_dependency,
+            remove_plan_ticket_dependency,
+            get_ready_plan_tickets,
+            patch_plan_ticket,
+            bulk_update_plan_tickets,
+            claim_plan_ticket,
+            get_plan_ticket_history,
+            get_plan_ticket_activity,
+            sync_planner_from_markdown,
+            sync_planner_to_markdown,
+            watch_planner_markdown,
             get_git_info,
             get_git_head,
+            list_git_repos,
+            list_submodules,
+            update_submodules,
             get_git_diff_name_status,
             get_implementation_log_entries,
             get_project_tickets,
@@ -3652,14 +6631,45 @@ pub fn run() {
             delete_plan_ticket,
             get_ideas_list,
             create_idea,
+            update_idea,
+            delete_idea,
+            convert_idea_to_plan,
+            rank_ideas,
+            search,
             create_project_milestone,
+            update_project_milestone,
+            delete_project_milestone,
+            merge_project_milestones,
+            get_milestone_progress,
+            reorder_project_milestones,
+            get_roadmap,
+            export_roadmap,
             update_implementation_log_entry_status,
             append_implementation_log_entry,
             get_git_file_view,
             git_fetch,
             git_pull,
             git_push,
+            answer_git_prompt,
+            git_bulk_status,
+            git_bulk_fetch,
+            git_bulk_pull,
             git_commit,
+            suggest_commit_message,
+            get_commit_template,
+            set_commit_template,
+            install_commit_msg_hook,
+            uninstall_commit_msg_hook,
+            set_commit_ticket,
+            scan_commit_ticket_refs,
+            get_plan_ticket_commits,
+            generate_release_notes,
+            get_git_conflict_state,
+            get_conflict_file_versions,
+            resolve_conflict,
+            git_merge_abort,
+            git_merge_continue,
+            run_conflict_resolution_agent,
             analyze_project_for_tickets,
             get_all_projects,
             list_projects,
@@ -3667,6 +6677,10 @@ pub fn run() {
             create_project,
             update_project,
             delete_project,
+            get_project_dependents,
+            list_trash,
+            restore_from_trash,
+            empty_trash,
             get_project_resolved,
             get_project_export,
             list_february_folders,
@@ -3708,6 +6722,14 @@ pub fn run() {
             stop_run,
             stop_script,
             get_kv_store_entries,
+            get_schema_status,
+            get_db_health,
+            repair_db,
+            create_backup,
+            list_backups,
+            get_backup_schedule,
+            set_backup_schedule,
+            restore_backup,
             get_data_dir,
             get_february_dir_config_path,
             get_dashboard_metrics,
@@ -3717,4 +6739,328 @@ pub fn run() {
         ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
-}
\ No newline at end of file
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_git_progress_reads_phase_percent_and_counts() {
+        assert_eq!(
+            parse_git_progress("Receiving objects:  45% (9/20)"),
+            Some(("Receiving objects".to_string(), 45, Some(9), Some(20)))
+        );
+        assert_eq!(
+            parse_git_progress("remote: Counting objects: 100% (3/3), done."),
+            Some(("Counting objects".to_string(), 100, Some(3), Some(3)))
+        );
+        assert_eq!(
+            parse_git_progress("Resolving deltas:   7%"),
+            Some(("Resolving deltas".to_string(), 7, None, None))
+        );
+        assert_eq!(parse_git_progress("Already up to date."), None);
+        assert_eq!(parse_git_progress("Bogus: 250% (1/2)"), None);
+        assert_eq!(parse_git_progress(": 50%"), None);
+    }
+
+    fn git(dir: &Path, args: &[&str]) {
+        let out = Command::new("git").args(args).current_dir(dir).output().expect("git");
+        assert!(out.status.success(), "git {:?}: {}", args, String::from_utf8_lossy(&out.stderr));
+    }
+
+    fn clone_with_identity(url: &str, dest: &Path) {
+        git(dest.parent().unwrap(), &["clone", "-q", url, dest.to_str().unwrap()]);
+        git(dest, &["config", "user.name", "Test"]);
+        git(dest, &["config", "user.email", "test@example.com"]);
+    }
+
+    type Progress = (String, u8, Option<u64>, Option<u64>);
+
+    /// Run a network git command the way run_git_network_inner does and return (success, progress updates).
+    fn run_network(repo: &Path, args: &[&str]) -> (bool, Vec<Progress>) {
+        let askpass = create_git_askpass().unwrap();
+        let args: Vec<String> = args.iter().map(|a| a.to_string()).collect();
+        let mut child = git_network_command(repo, &args, &askpass).spawn().unwrap();
+        let stderr = child.stderr.take().unwrap();
+        let mut progress = vec![];
+        for_each_git_output_segment(stderr, |line| progress.extend(parse_git_progress(&line)));
+        (child.wait().unwrap().success(), progress)
+    }
+
+    #[test]
+    fn git_network_push_and_pull_against_bare_repo() {
+        let root = tempfile::tempdir().unwrap();
+        let bare = root.path().join("remote.git");
+        git(root.path(), &["init", "-q", "--bare", bare.to_str().unwrap()]);
+        let url = format!("file://{}", bare.display());
+        let a = root.path().join("a");
+        let b = root.path().join("b");
+        clone_with_identity(&url, &a);
+        std::fs::write(a.join("one.txt"), "1").unwrap();
+        git(&a, &["add", "-A"]);
+        git(&a, &["commit", "-q", "-m", "one"]);
+
+        let (ok, progress) = run_network(&a, &["push", "--progress", "origin", "HEAD"]);
+        assert!(ok);
+        assert!(progress.iter().any(|(_, percent, _, _)| *percent == 100));
+
+        clone_with_identity(&url, &b);
+        std::fs::write(a.join("two.txt"), "2").unwrap();
+        git(&a, &["add", "-A"]);
+        git(&a, &["commit", "-q", "-m", "two"]);
+        assert!(run_network(&a, &["push", "--progress", "origin", "HEAD"]).0);
+        assert!(run_network(&b, &["pull", "--progress"]).0);
+        assert_eq!(std::fs::read_to_string(b.join("two.txt")).unwrap(), "2");
+
+        // Pushing a diverged branch is rejected and reported as a failure.
+        std::fs::write(b.join("three.txt"), "3").unwrap();
+        git(&b, &["add", "-A"]);
+        git(&b, &["commit", "-q", "-m", "three"]);
+        std::fs::write(a.join("four.txt"), "4").unwrap();
+        git(&a, &["add", "-A"]);
+        git(&a, &["commit", "-q", "-m", "four"]);
+        assert!(run_network(&a, &["push", "--progress", "origin", "HEAD"]).0);
+        assert!(!run_network(&b, &["push", "--progress", "origin", "HEAD"]).0);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn git_bulk_fetch_reports_each_repo_and_kills_stalled_remotes() {
+        let root = tempfile::tempdir().unwrap();
+        let bare = root.path().join("remote.git");
+        git(root.path(), &["init", "-q", "--bare", bare.to_str().unwrap()]);
+        let url = format!("file://{}", bare.display());
+        let ok = root.path().join("ok");
+        clone_with_identity(&url, &ok);
+        std::fs::write(ok.join("one.txt"), "1").unwrap();
+        git(&ok, &["add", "-A"]);
+        git(&ok, &["commit", "-q", "-m", "one"]);
+        git(&ok, &["push", "-q", "origin", "HEAD"]);
+
+        let gone = root.path().join("gone");
+        git(root.path(), &["clone", "-q", &url, gone.to_str().unwrap()]);
+        git(&gone, &["remote", "set-url", "origin", root.path().join("missing.git").to_str().unwrap()]);
+        // A remote that never answers: the ext transport runs `sleep`, which must die with git.
+        let stalled = root.path().join("stalled");
+        git(root.path(), &["clone", "-q", &url, stalled.to_str().unwrap()]);
+        git(&stalled, &["config", "protocol.ext.allow", "always"]);
+        git(&stalled, &["remote", "set-url", "origin", "ext::sleep 60"]);
+
+        let repo = |name: &str, path: &Path| RegisteredRepo {
+            project_id: None,
+            name: name.to_string(),
+            path: path.to_path_buf(),
+        };
+        let repos = vec![
+            repo("stalled", &stalled),
+            repo("ok", &ok),
+            repo("gone", &gone),
+            repo("nowhere", &root.path().join("nowhere")),
+        ];
+        let seen = Mutex::new(vec![]);
+        let started = std::time::Instant::now();
+        let results = git_bulk_op(repos, "fetch", 4, 2, |r| seen.lock().unwrap().push(r.name.clone()));
+        assert!(started.elapsed() < std::time::Duration::from_secs(20));
+
+        let names: Vec<&str> = results.iter().map(|r| r.name.as_str()).collect();
+        assert_eq!(names, ["stalled", "ok", "gone", "nowhere"]);
+        assert_eq!(seen.into_inner().unwrap().len(), 4);
+        assert_eq!(results.iter().map(|r| r.ok).collect::<Vec<_>>(), [false, true, false, false]);
+        assert!(results[0].error.as_deref().unwrap().contains("timed out after 2s"));
+    }
+
+    #[test]
+    fn commit_type_matches_keyword_forms() {
+        let ty = |title: &str| commit_type_for("", title, "P2", &[], true);
+        assert_eq!(ty("Add tests for parser"), "test");
+        assert_eq!(ty("Update docs for sync"), "docs");
+        assert_eq!(ty("Fixes login redirect"), "fix");
+        assert_eq!(ty("Fixed bugs in export"), "fix");
+        assert_eq!(ty("Editor crashes on paste"), "fix");
+        assert_eq!(ty("Refactoring the planner"), "refactor");
+        assert_eq!(ty("Run CI on pull requests"), "ci");
+        assert_eq!(ty("Build release bundles"), "build");
+        // Words that merely start like a keyword do not count.
+        assert_eq!(ty("Add docker image and test fixtures"), "test");
+        assert_eq!(ty("Add docker image"), "feat");
+        assert_eq!(ty("Support circle avatars"), "feat");
+        assert_eq!(commit_type_for("", "Outage", "P0", &[], true), "fix");
+        assert_eq!(commit_type_for("", "", "", &["README.md".to_string()], false), "docs");
+        assert_eq!(commit_type_for("", "", "", &["src/a.rs".to_string()], false), "chore");
+    }
+
+    #[test]
+    fn commit_template_is_filled_in_one_pass() {
+        let values = [
+            ("type", "feat".to_string()),
+            ("scope_paren", String::new()),
+            ("summary", String::new()),
+            ("files", "a.rs\nb.rs".to_string()),
+            ("refs", "Refs #3".to_string()),
+        ];
+        let (message, subject) = render_commit_message(DEFAULT_COMMIT_TEMPLATE, &values, "show {files} and {summary}");
+        assert_eq!(subject, "show {files} and {summary}");
+        assert_eq!(message, "feat: show {files} and {summary}\n\nRefs #3");
+        assert_eq!(render_commit_template("{type} {unknown} {", &values), "feat {unknown} {");
+    }
+
+    #[test]
+    fn commit_header_fits_72_characters() {
+        let values = [("type", "refactor".to_string()), ("scope_paren", "(planner)".to_string())];
+        let long = "move the markdown reconciliation into its own module and add more tests";
+        let (message, subject) = render_commit_message("{type}{scope_paren}: {subject}\n\nbody", &values, long);
+        let header = message.lines().next().unwrap();
+        assert!(header.chars().count() <= COMMIT_HEADER_MAX_CHARS, "{}", header);
+        assert!(subject.ends_with('…'));
+        assert_eq!(header, format!("refactor(planner): {}", subject));
+        assert!(message.ends_with("\n\nbody"));
+
+        let (message, subject) = render_commit_message("{type}: {subject}", &values, "short");
+        assert_eq!((message.as_str(), subject.as_str()), ("refactor: short", "short"));
+    }
+
+    #[test]
+    fn conflict_entries_are_parsed_from_nul_separated_output() {
+        let ls_files = "100644 aaa 1\tdir/my file.txt\0100644 bbb 2\tdir/my file.txt\0100644 ccc 3\tdir/my file.txt\0\
+                        100644 ddd 2\tnaïve.md\0";
+        let mut files = parse_unmerged_entries(ls_files);
+        assert_eq!(files.len(), 2);
+        assert_eq!(files[0].path, "dir/my file.txt");
+        assert_eq!(
+            (files[0].base_blob.as_deref(), files[0].ours_blob.as_deref(), files[0].theirs_blob.as_deref()),
+            (Some("aaa"), Some("bbb"), Some("ccc"))
+        );
+        assert_eq!((files[1].path.as_str(), files[1].theirs_blob.as_deref()), ("naïve.md", None));
+
+        // The rename's source path ("naïve.md" here) must not be read as an entry of its own.
+        let status = "R  renamed.txt\0naïve.md\0UU dir/my file.txt\0AU naïve.md\0?? other\0";
+        apply_porcelain_status(&mut files, status);
+        assert_eq!(files[0].status, "UU");
+        assert_eq!(files[1].status, "AU");
+    }
+
+    /// Repo `dir` on branch "main" where merging "other" conflicts in "dir/my file.txt" and "naïve.md".
+    fn conflicted_repo(dir: &Path) -> PathBuf {
+        let repo = dir.join("repo");
+        std::fs::create_dir_all(repo.join("dir")).unwrap();
+        git(&repo, &["init", "-q", "-b", "main"]);
+        git(&repo, &["config", "user.name", "Test"]);
+        git(&repo, &["config", "user.email", "test@example.com"]);
+        let write = |name: &str, text: &str| std::fs::write(repo.join(name), text).unwrap();
+        let commit = |msg: &str| {
+            git(&repo, &["add", "-A"]);
+            git(&repo, &["commit", "-q", "-m", msg]);
+        };
+        write("dir/my file.txt", "  base\n");
+        write("naïve.md", "base\n");
+        commit("base");
+        git(&repo, &["checkout", "-q", "-b", "other"]);
+        write("dir/my file.txt", "  theirs\n\tkept\n");
+        write("naïve.md", "theirs\n");
+        commit("theirs");
+        git(&repo, &["checkout", "-q", "main"]);
+        write("dir/my file.txt", "  ours\n");
+        write("naïve.md", "ours\n");
+        commit("ours");
+        let out = Command::new("git").args(["merge", "other"]).current_dir(&repo).output().unwrap();
+        assert!(!out.status.success());
+        repo
+    }
+
+    #[test]
+    fn merge_conflicts_are_resolved_and_the_merge_continued() {
+        let root = tempfile::tempdir().unwrap();
+        let repo = conflicted_repo(root.path());
+        let project = repo.to_string_lossy().to_string();
+
+        let state = get_git_conflict_state(project.clone()).unwrap();
+        assert_eq!(state.operation.as_deref(), Some("merge"));
+        let mut paths: Vec<(&str, &str)> = state.files.iter().map(|f| (f.path.as_str(), f.status.as_str())).collect();
+        paths.sort();
+        assert_eq!(paths, [("dir/my file.txt", "UU"), ("naïve.md", "UU")]);
+        assert!(git_merge_continue(project.clone()).is_err());
+
+        // Stage contents keep their leading whitespace and trailing newline, so writing one back is lossless.
+        let versions = get_conflict_file_versions(project.clone(), "dir/my file.txt".to_string()).unwrap();
+        assert_eq!(versions.base.as_deref(), Some("  base\n"));
+        assert_eq!(versions.ours.as_deref(), Some("  ours\n"));
+        assert_eq!(versions.theirs.as_deref(), Some("  theirs\n\tkept\n"));
+        let state = resolve_conflict(project.clone(), "dir/my file.txt".to_string(), None, versions.theirs).unwrap();
+        assert_eq!(state.files.len(), 1);
+        let state = resolve_conflict(project.clone(), "naïve.md".to_string(), Some("ours".to_string()), None).unwrap();
+        assert!(state.files.is_empty());
+
+        git_merge_continue(project.clone()).unwrap();
+        assert_eq!(std::fs::read_to_string(repo.join("dir/my file.txt")).unwrap(), "  theirs\n\tkept\n");
+        assert_eq!(std::fs::read_to_string(repo.join("naïve.md")).unwrap(), "ours\n");
+        assert_eq!(get_git_conflict_state(project).unwrap().operation, None);
+        assert!(run_git(&repo, &["rev-parse", "--verify", "HEAD^2"]).is_ok());
+    }
+
+    #[test]
+    fn conflicted_bulk_pull_reports_a_conflict() {
+        let root = tempfile::tempdir().unwrap();
+        let bare = root.path().join("remote.git");
+        git(root.path(), &["init", "-q", "--bare", bare.to_str().unwrap()]);
+        let url = format!("file://{}", bare.display());
+        let (a, b) = (root.path().join("a"), root.path().join("b"));
+        clone_with_identity(&url, &a);
+        std::fs::write(a.join("f.txt"), "base\n").unwrap();
+        git(&a, &["add", "-A"]);
+        git(&a, &["commit", "-q", "-m", "base"]);
+        git(&a, &["push", "-q", "origin", "HEAD"]);
+        clone_with_identity(&url, &b);
+        git(&b, &["config", "pull.rebase", "false"]);
+        for (repo, text) in [(&a, "a\n"), (&b, "b\n")] {
+            std::fs::write(repo.join("f.txt"), text).unwrap();
+            git(repo, &["commit", "-q", "-am", text.trim()]);
+        }
+        git(&a, &["push", "-q", "origin", "HEAD"]);
+
+        let repos = vec![RegisteredRepo { project_id: None, name: "b".to_string(), path: b.clone() }];
+        let results = git_bulk_op(repos, "pull", 1, 60, |_| {});
+        assert!(!results[0].ok);
+        assert_eq!(results[0].error_code, Some(error::ErrorCode::Conflict));
+        assert!(results[0].error.as_deref().unwrap().contains("f.txt"));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn git_askpass_is_private_and_answers_through_the_fifo() {
+        use std::os::unix::fs::PermissionsExt;
+        let askpass = create_git_askpass().unwrap();
+        let mode = |p: &Path| std::fs::metadata(p).unwrap().permissions().mode() & 0o777;
+        assert_eq!(mode(askpass.dir.path()), 0o700);
+        assert_eq!(mode(&askpass.script), 0o700);
+
+        let ask = |prompt: &str, answer: Option<&str>| {
+            let child = Command::new(&askpass.script)
+                .arg(prompt)
+                .env("KWCODE_ASKPASS_DIR", askpass.dir.path())
+                .stdout(Stdio::piped())
+                .spawn()
+                .unwrap();
+            let request_id = (0..100)
+                .find_map(|_| {
+                    let found = std::fs::read_dir(askpass.dir.path()).unwrap().flatten().find_map(|e| {
+                        e.file_name().to_string_lossy().strip_suffix(".prompt").map(str::to_string)
+                    });
+                    if found.is_none() {
+                        thread::sleep(std::time::Duration::from_millis(50));
+                    }
+                    found
+                })
+                .expect("prompt published");
+            let dir = askpass.dir.path();
+            assert_eq!(std::fs::read_to_string(dir.join(format!("{}.prompt", request_id))).unwrap(), prompt);
+            answer_git_askpass(dir, &request_id, answer).unwrap();
+            let out = child.wait_with_output().unwrap();
+            // The prompt and FIFO are gone once answered; nothing of the answer stays on disk.
+            assert_eq!(std::fs::read_dir(dir).unwrap().count(), 1);
+            (out.status.success(), String::from_utf8(out.stdout).unwrap())
+        };
+        assert_eq!(ask("Password for 'https://me@host': ", Some("s3cret")), (true, "s3cret\n".to_string()));
+        assert_eq!(ask("Username for 'https://host': ", None), (false, String::new()));
+        assert!(answer_git_askpass(askpass.dir.path(), "missing", Some("x")).is_err());
+    }
+}
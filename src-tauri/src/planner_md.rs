@@ -0,0 +1,675 @@
+//! Parser and writer for `.cursor/7. planner/tickets.md`, and the three-way reconciliation used to sync it with
+//! plan_tickets. Format (same as src/lib/todos-kanban.ts): `### P0 — …` priority sections, `#### Feature: Name`
+//! headings, and `- [ ] #N Title — description — @agent` items.
+
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeSet, HashMap};
+use ts_rs::TS;
+
+/// Path of the planner tickets file inside a project repo.
+pub const TICKETS_MD_PATH: &str = ".cursor/7. planner/tickets.md";
+
+const PRIORITIES: [&str; 4] = ["P0", "P1", "P2", "P3"];
+const TICKETS_HEADING: &str = "## Prioritized work items";
+
+/// The part of a plan ticket that tickets.md carries. Status beyond done/not done, milestone and idea stay DB-only.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct MdTicket {
+    #[ts(as = "f64")]
+    pub number: i64,
+    pub title: String,
+    pub description: Option<String>,
+    pub priority: String,
+    pub feature_name: String,
+    pub done: bool,
+    pub agents: Vec<String>,
+}
+
+impl MdTicket {
+    /// Stable comparison key, stored as the sync baseline.
+    pub fn fingerprint(&self) -> String {
+        serde_json::to_string(self).unwrap_or_default()
+    }
+}
+
+impl From<&crate::db::PlanTicket> for MdTicket {
+    fn from(t: &crate::db::PlanTicket) -> Self {
+        Self {
+            number: t.number,
+            title: t.title.trim().to_string(),
+            description: t.description.as_deref().map(str::trim).filter(|s| !s.is_empty()).map(String::from),
+            priority: t.priority.clone(),
+            feature_name: t.feature_name.clone(),
+            done: t.done,
+            agents: t.agents.clone().unwrap_or_default(),
+        }
+    }
+}
+
+pub struct ParsedTicketsMd {
+    pub tickets: Vec<MdTicket>,
+    /// A ticket section was found (work items heading, priority or feature heading, or a checklist item). Without one
+    /// the file is empty, truncated or not a planner file.
+    pub has_ticket_section: bool,
+    /// Lines that look like tickets but were skipped (missing number, duplicate number).
+    pub warnings: Vec<String>,
+}
+
+/// Parse tickets.md. Items before the first priority heading count as P0, before the first feature heading as
+/// "General" (as in the frontend parser).
+pub fn parse_tickets_md(content: &str) -> ParsedTicketsMd {
+    let mut tickets: Vec<MdTicket> = vec![];
+    let mut warnings = vec![];
+    let mut priority = "P0";
+    let mut feature = "General".to_string();
+    let mut has_ticket_section = false;
+    for (i, line) in content.lines().enumerate() {
+        if line.starts_with(TICKETS_HEADING) {
+            has_ticket_section = true;
+            continue;
+        }
+        if let Some(p) = parse_priority_heading(line) {
+            has_ticket_section = true;
+            priority = p;
+            continue;
+        }
+        if let Some(rest) = line.strip_prefix("####") {
+            if let Some(name) = rest.trim_start().strip_prefix("Feature:") {
+                feature = name.trim().to_string();
+                has_ticket_section = true;
+            }
+            continue;
+        }
+        let Some((done, rest)) = parse_checkbox(line) else {
+            continue;
+        };
+        has_ticket_section = true;
+        let Some((number, rest)) = parse_ticket_number(rest) else {
+            warnings.push(format!("Line {}: checklist item without #number ignored", i + 1));
+            continue;
+        };
+        if tickets.iter().any(|t| t.number == number) {
+            warnings.push(format!("Line {}: duplicate ticket #{} ignored", i + 1, number));
+            continue;
+        }
+        let (rest, agents) = split_agents(rest);
+        let (title, description) = match rest.split_once(" — ") {
+            Some((title, desc)) => (title.trim(), Some(desc.trim()).filter(|s| !s.is_empty())),
+            None => (rest.trim(), None),
+        };
+        tickets.push(MdTicket {
+            number,
+            title: title.to_string(),
+            description: description.map(String::from),
+            priority: priority.to_string(),
+            feature_name: feature.clone(),
+            done,
+            agents,
+        });
+    }
+    ParsedTicketsMd {
+        tickets,
+        has_ticket_section,
+        warnings,
+    }
+}
+
+/// `### P1 — …` → "P1". `####` headings are not priority headings.
+fn parse_priority_heading(line: &str) -> Option<&'static str> {
+    let rest = line.strip_prefix("###")?;
+    if rest.starts_with('#') {
+        return None;
+    }
+    let rest = rest.trim_start();
+    PRIORITIES
+        .iter()
+        .find(|p| rest.strip_prefix(**p).is_some_and(|after| after.is_empty() || after.starts_with(char::is_whitespace)))
+        .copied()
+}
+
+/// `- [x] rest` → (true, "rest").
+fn parse_checkbox(line: &str) -> Option<(bool, &str)> {
+    let rest = line.strip_prefix('-')?.trim_start().strip_prefix('[')?;
+    let mut chars = rest.chars();
+    let done = match chars.next()? {
+        ' ' => false,
+        'x' | 'X' => true,
+        _ => return None,
+    };
+    let rest = chars.as_str().strip_prefix(']')?;
+    rest.starts_with(char::is_whitespace).then(|| (done, rest.trim_start()))
+}
+
+/// `#12 Title…` → (12, "Title…"). The title must not be empty.
+fn parse_ticket_number(rest: &str) -> Option<(i64, &str)> {
+    let rest = rest.strip_prefix('#')?;
+    let digits = rest.find(|c: char| !c.is_ascii_digit()).unwrap_or(rest.len());
+    let number = rest[..digits].parse().ok()?;
+    let title = &rest[digits..];
+    (title.starts_with(char::is_whitespace) && !title.trim().is_empty()).then(|| (number, title.trim()))
+}
+
+/// Trailing ` — @frontend-dev @backend-dev` → agent names without the `@`.
+fn split_agents(rest: &str) -> (&str, Vec<String>) {
+    let Some((head, tail)) = rest.rsplit_once('—') else {
+        return (rest, vec![]);
+    };
+    let agents: Vec<&str> = tail.split_whitespace().collect();
+    let valid = !agents.is_empty()
+        && agents.iter().all(|a| {
+            a.strip_prefix('@')
+                .is_some_and(|name| !name.is_empty() && name.chars().all(|c| c.is_alphanumeric() || c == '_' || c == '-'))
+        });
+    if !valid {
+        return (rest, vec![]);
+    }
+    (head.trim_end(), agents.iter().map(|a| a[1..].to_string()).collect())
+}
+
+fn priority_label(priority: &str) -> &'static str {
+    match priority {
+        "P0" => "Critical / foundation",
+        "P1" => "High / quality and maintainability",
+        "P2" => "Medium / polish and scale",
+        _ => "Lower / later",
+    }
+}
+
+fn ticket_line(t: &MdTicket) -> String {
+    let mut line = format!("- [{}] #{} {}", if t.done { "x" } else { " " }, t.number, t.title);
+    if let Some(desc) = &t.description {
+        line.push_str(" — ");
+        line.push_str(desc);
+    }
+    if !t.agents.is_empty() {
+        let agents: Vec<String> = t.agents.iter().map(|a| format!("@{}", a)).collect();
+        line.push_str(" — ");
+        line.push_str(&agents.join(" "));
+    }
+    line
+}
+
+/// Priority sections with feature groups (in first-seen order), tickets by number.
+fn ticket_section(tickets: &[MdTicket]) -> Vec<String> {
+    let mut lines = vec![];
+    for p in PRIORITIES {
+        let mut in_priority: Vec<&MdTicket> = tickets.iter().filter(|t| t.priority == p).collect();
+        if in_priority.is_empty() {
+            continue;
+        }
+        in_priority.sort_by_key(|t| t.number);
+        lines.push(format!("### {} — {}", p, priority_label(p)));
+        lines.push(String::new());
+        let mut features: Vec<&str> = vec![];
+        for t in &in_priority {
+            let name = t.feature_name.as_str();
+            if !features.contains(&name) {
+                features.push(name);
+            }
+        }
+        for feature in features {
+            lines.push(format!("#### Feature: {}", if feature.is_empty() { "General" } else { feature }));
+            lines.push(String::new());
+            lines.extend(in_priority.iter().filter(|t| t.feature_name == feature).map(|t| ticket_line(t)));
+            lines.push(String::new());
+        }
+    }
+    lines
+}
+
+/// Render tickets.md. With existing content only the ticket section is replaced (from the "Prioritized work items"
+/// heading, or the first priority heading / item, up to the next `## ` heading) so summaries written by agents stay;
+/// the `**Last updated:**` line is refreshed. Without existing content the full template is written.
+pub fn write_tickets_md(existing: Option<&str>, tickets: &[MdTicket], project_name: &str) -> String {
+    let date = chrono::Utc::now().format("%Y-%m-%d").to_string();
+    let existing = existing.filter(|s| !s.trim().is_empty());
+    let Some(existing) = existing else {
+        let mut lines: Vec<String> = vec![
+            format!("# Work items (tickets) — {}", project_name),
+            String::new(),
+            format!("**Project:** {}", project_name),
+            "**Source:** Kanban".to_string(),
+            format!("**Last updated:** {}", date),
+            String::new(),
+            "---".to_string(),
+            String::new(),
+            "## Summary: Done vs missing".to_string(),
+            String::new(),
+            "### Done".to_string(),
+            String::new(),
+            "| Area | What's implemented |".to_string(),
+            "|------|--------------------|".to_string(),
+            String::new(),
+            "### Missing or incomplete".to_string(),
+            String::new(),
+            "| Area | Gap |".to_string(),
+            String::new(),
+            "---".to_string(),
+            String::new(),
+            format!("{} (tickets)", TICKETS_HEADING),
+            String::new(),
+        ];
+        lines.extend(ticket_section(tickets));
+        lines.extend(["## Next steps", "", "1. Add or update tickets in the Kanban.", ""].map(String::from));
+        return collapse_blank_lines(&lines);
+    };
+
+    let lines: Vec<&str> = existing.lines().collect();
+    let is_ticket_line =
+        |l: &str| parse_priority_heading(l).is_some() || l.starts_with("#### Feature:") || parse_checkbox(l).is_some();
+    let (start, heading) = match lines.iter().position(|l| l.starts_with(TICKETS_HEADING)) {
+        Some(i) => (i + 1, true),
+        None => match lines.iter().position(|l| is_ticket_line(l)) {
+            Some(i) => (i, false),
+            None => (
+                lines.iter().position(|l| l.starts_with("## Next steps")).unwrap_or(lines.len()),
+                false,
+            ),
+        },
+    };
+    let end = lines[start..]
+        .iter()
+        .position(|l| l.starts_with("## "))
+        .map(|i| start + i)
+        .unwrap_or(lines.len());
+
+    let mut out: Vec<String> = lines[..start]
+        .iter()
+        .map(|l| {
+            if l.starts_with("**Last updated:**") {
+                format!("**Last updated:** {}", date)
+            } else {
+                l.to_string()
+            }
+        })
+        .collect();
+    if !heading && !lines.iter().any(|l| is_ticket_line(l)) {
+        out.push(String::new());
+        out.push(format!("{} (tickets)", TICKETS_HEADING));
+    }
+    out.push(String::new());
+    out.extend(ticket_section(tickets));
+    out.extend(lines[end..].iter().map(|l| l.to_string()));
+    collapse_blank_lines(&out)
+}
+
+fn collapse_blank_lines(lines: &[String]) -> String {
+    let mut out = String::new();
+    let mut blank = 0;
+    for line in lines {
+        blank = if line.trim().is_empty() { blank + 1 } else { 0 };
+        if blank > 1 {
+            continue;
+        }
+        out.push_str(line);
+        out.push('\n');
+    }
+    format!("{}\n", out.trim_end())
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SyncDirection {
+    FromMarkdown,
+    ToMarkdown,
+}
+
+/// What to do with a ticket changed on both sides since the last sync.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConflictStrategy {
+    /// Leave both sides as they are and list the ticket in the report.
+    Report,
+    Markdown,
+    Database,
+}
+
+impl ConflictStrategy {
+    pub fn parse(s: Option<&str>) -> Result<Self, String> {
+        match s.map(str::trim).unwrap_or("report") {
+            "" | "report" => Ok(Self::Report),
+            "markdown" => Ok(Self::Markdown),
+            "database" => Ok(Self::Database),
+            other => Err(format!("Unknown conflict strategy '{}': use report, markdown or database", other)),
+        }
+    }
+}
+
+/// How a from_markdown sync treats tickets that disappeared from tickets.md.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MarkdownDeletes {
+    /// List them in the report as pending and keep them (the file watcher: a half-written file must not delete).
+    Report,
+    /// Delete them, unless that would remove more than MAX_DELETE_SHARE of the project's tickets.
+    Apply,
+    /// Delete them whatever the share (explicit confirmation after a refused sync).
+    Force,
+}
+
+/// Largest share of a project's tickets one from_markdown sync deletes without `force`.
+pub const MAX_DELETE_SHARE: f64 = 0.25;
+/// Deleting up to this many tickets is always allowed, so small projects can still drop a ticket or two.
+const MAX_DELETE_ALWAYS: usize = 2;
+
+/// Refuse a from_markdown sync that looks like it read a broken file: no ticket section while the project has tickets,
+/// or (unless forced) deleting more than MAX_DELETE_SHARE of them.
+pub fn check_markdown_deletes(
+    parsed: &ParsedTicketsMd,
+    database_tickets: usize,
+    deletes: usize,
+    mode: MarkdownDeletes,
+) -> Result<(), String> {
+    if !parsed.has_ticket_section && database_tickets > 0 {
+        return Err(format!(
+            "tickets.md has no ticket section; refusing to sync it over {} ticket(s)",
+            database_tickets
+        ));
+    }
+    if mode == MarkdownDeletes::Apply
+        && deletes > MAX_DELETE_ALWAYS
+        && deletes as f64 > database_tickets as f64 * MAX_DELETE_SHARE
+    {
+        return Err(format!(
+            "Syncing tickets.md would delete {} of {} tickets; sync again with force to confirm",
+            deletes, database_tickets
+        ));
+    }
+    Ok(())
+}
+
+/// A ticket changed in tickets.md and in the database since the last sync, or a tickets.md change the board refused.
+/// None = deleted on that side.
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct PlannerSyncConflict {
+    #[ts(as = "f64")]
+    pub number: i64,
+    pub markdown: Option<MdTicket>,
+    pub database: Option<MdTicket>,
+    /// Why the Markdown version was not applied (e.g. a disallowed move or a full column); None for edits on both
+    /// sides.
+    #[serde(default)]
+    pub reason: Option<String>,
+}
+
+/// Outcome of one sync. created/updated/deleted are ticket numbers on the target side (DB for from_markdown,
+/// tickets.md for to_markdown).
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct PlannerSyncReport {
+    pub direction: String,
+    pub path: String,
+    #[ts(as = "Vec<f64>")]
+    pub created: Vec<i64>,
+    #[ts(as = "Vec<f64>")]
+    pub updated: Vec<i64>,
+    #[ts(as = "Vec<f64>")]
+    pub deleted: Vec<i64>,
+    /// Tickets missing from tickets.md that were kept (watcher syncs); an explicit sync deletes them.
+    #[ts(as = "Vec<f64>")]
+    pub pending_deletes: Vec<i64>,
+    pub unchanged: usize,
+    pub conflicts: Vec<PlannerSyncConflict>,
+    pub warnings: Vec<String>,
+    /// True when tickets.md was rewritten (to_markdown only).
+    pub written: bool,
+}
+
+/// Result of reconcile: the target-side changes to apply and the new baseline fingerprints.
+pub struct SyncPlan {
+    /// Tickets to insert or update in plan_tickets (used by from_markdown).
+    pub db_upserts: Vec<MdTicket>,
+    /// Ticket numbers to delete from plan_tickets (used by from_markdown).
+    pub db_deletes: Vec<i64>,
+    /// Full ticket list tickets.md should contain (to_markdown).
+    pub markdown: Vec<MdTicket>,
+    /// Baseline updates; None removes the entry.
+    pub baseline: Vec<(i64, Option<String>)>,
+    pub created: Vec<i64>,
+    pub updated: Vec<i64>,
+    pub deleted: Vec<i64>,
+    pub unchanged: usize,
+    pub conflicts: Vec<PlannerSyncConflict>,
+}
+
+/// Three-way merge by ticket number against the baseline recorded at the last sync. Changes on the source side are
+/// applied to the target; changes only on the target side are kept and wait for the opposite sync. A conflict
+/// resolved in favour of the target records the source version as baseline, so the target's version counts as the
+/// pending change for the next sync in the other direction.
+pub fn reconcile(
+    markdown: &[MdTicket],
+    database: &[MdTicket],
+    baseline: &HashMap<i64, String>,
+    direction: SyncDirection,
+    strategy: ConflictStrategy,
+) -> SyncPlan {
+    let md: HashMap<i64, &MdTicket> = markdown.iter().map(|t| (t.number, t)).collect();
+    let db: HashMap<i64, &MdTicket> = database.iter().map(|t| (t.number, t)).collect();
+    let numbers: BTreeSet<i64> = md.keys().chain(db.keys()).chain(baseline.keys()).copied().collect();
+    let mut plan = SyncPlan {
+        db_upserts: vec![],
+        db_deletes: vec![],
+        markdown: vec![],
+        baseline: vec![],
+        created: vec![],
+        updated: vec![],
+        deleted: vec![],
+        unchanged: 0,
+        conflicts: vec![],
+    };
+    let source_wins = match direction {
+        SyncDirection::FromMarkdown => ConflictStrategy::Markdown,
+        SyncDirection::ToMarkdown => ConflictStrategy::Database,
+    };
+    for n in numbers {
+        let (m, d) = (md.get(&n).copied(), db.get(&n).copied());
+        let (source, target) = match direction {
+            SyncDirection::FromMarkdown => (m, d),
+            SyncDirection::ToMarkdown => (d, m),
+        };
+        let fp = |t: Option<&MdTicket>| t.map(MdTicket::fingerprint);
+        let base = baseline.get(&n).cloned();
+        let source_changed = fp(source) != base;
+        let target_changed = fp(target) != base;
+
+        let apply_source = if !source_changed {
+            false
+        } else if !target_changed {
+            true
+        } else if fp(source) == fp(target) {
+            plan.baseline.push((n, fp(source)));
+            false
+        } else if strategy == ConflictStrategy::Report {
+            plan.conflicts.push(PlannerSyncConflict {
+                number: n,
+                markdown: m.cloned(),
+                database: d.cloned(),
+                reason: None,
+            });
+            false
+        } else if strategy == source_wins {
+            true
+        } else {
+            plan.baseline.push((n, fp(source)));
+            false
+        };
+
+        let kept = if apply_source { source } else { target };
+        if let Some(t) = kept {
+            plan.markdown.push(t.clone());
+        }
+        if !apply_source {
+            if !source_changed && !target_changed {
+                plan.unchanged += 1;
+            }
+            continue;
+        }
+        plan.baseline.push((n, fp(source)));
+        match (source, target) {
+            (Some(s), None) => {
+                plan.created.push(n);
+                plan.db_upserts.push(s.clone());
+            }
+            (Some(s), Some(_)) => {
+                plan.updated.push(n);
+                plan.db_upserts.push(s.clone());
+            }
+            (None, Some(_)) => {
+                plan.deleted.push(n);
+                plan.db_deletes.push(n);
+            }
+            (None, None) => {}
+        }
+    }
+    plan
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ticket(number: i64, title: &str) -> MdTicket {
+        MdTicket {
+            number,
+            title: title.to_string(),
+            description: None,
+            priority: "P1".to_string(),
+            feature_name: "General".to_string(),
+            done: false,
+            agents: vec![],
+        }
+    }
+
+    fn baseline_of(tickets: &[MdTicket]) -> HashMap<i64, String> {
+        tickets.iter().map(|t| (t.number, t.fingerprint())).collect()
+    }
+
+    #[test]
+    fn round_trips_a_new_file() {
+        let tickets = vec![
+            MdTicket {
+                description: Some("Use the refresh token".to_string()),
+                agents: vec!["backend-dev".to_string(), "qa".to_string()],
+                ..ticket(1, "Fix login redirect")
+            },
+            MdTicket {
+                priority: "P0".to_string(),
+                feature_name: "Auth".to_string(),
+                done: true,
+                ..ticket(2, "Add SSO")
+            },
+            MdTicket {
+                priority: "P3".to_string(),
+                feature_name: "Docs".to_string(),
+                ..ticket(7, "Write the README")
+            },
+        ];
+        let content = write_tickets_md(None, &tickets, "Demo");
+        let parsed = parse_tickets_md(&content);
+        assert!(parsed.has_ticket_section);
+        assert!(parsed.warnings.is_empty());
+        let mut expected = tickets.clone();
+        expected.sort_by_key(|t| (t.priority.clone(), t.number));
+        assert_eq!(parsed.tickets, expected);
+        // Writing what was parsed gives the same file back.
+        assert_eq!(write_tickets_md(Some(&content), &parsed.tickets, "Demo"), content);
+    }
+
+    #[test]
+    fn rewrite_keeps_text_around_the_ticket_section() {
+        let existing = "# Work items\n\nNotes by an agent.\n\n### P1 — High\n\n- [ ] #1 Old title\n\n## Next steps\n\n1. Ship it.\n";
+        let tickets = vec![ticket(1, "New title"), ticket(2, "Another")];
+        let content = write_tickets_md(Some(existing), &tickets, "Demo");
+        assert!(content.contains("Notes by an agent."));
+        assert!(content.contains("## Next steps\n\n1. Ship it."));
+        assert!(!content.contains("Old title"));
+        assert_eq!(parse_tickets_md(&content).tickets, tickets);
+    }
+
+    #[test]
+    fn parser_skips_unnumbered_and_duplicate_items() {
+        let parsed = parse_tickets_md("### P2 — Medium\n- [ ] no number\n- [x] #3 Done thing\n- [ ] #3 Again\n");
+        assert_eq!(parsed.tickets.len(), 1);
+        assert!(parsed.tickets[0].done);
+        assert_eq!(parsed.tickets[0].priority, "P2");
+        assert_eq!(parsed.warnings.len(), 2);
+        assert!(!parse_tickets_md("").has_ticket_section);
+        assert!(!parse_tickets_md("# Work items\n\nJust prose.\n").has_ticket_section);
+    }
+
+    #[test]
+    fn truncated_file_plans_deletes_that_the_guard_refuses() {
+        let database: Vec<MdTicket> = (1..=10).map(|n| ticket(n, &format!("Ticket {}", n))).collect();
+        let baseline = baseline_of(&database);
+        let full = write_tickets_md(None, &database, "Demo");
+        // An editor or agent left only the first ticket line behind.
+        let cut = full.find("- [ ] #2").unwrap();
+        let parsed = parse_tickets_md(&full[..cut]);
+        assert_eq!(parsed.tickets.len(), 1);
+
+        let plan = reconcile(
+            &parsed.tickets,
+            &database,
+            &baseline,
+            SyncDirection::FromMarkdown,
+            ConflictStrategy::Report,
+        );
+        assert_eq!(plan.db_deletes, (2..=10).collect::<Vec<_>>());
+        assert!(plan.db_upserts.is_empty());
+
+        let deletes = plan.db_deletes.len();
+        assert!(check_markdown_deletes(&parsed, database.len(), deletes, MarkdownDeletes::Apply).is_err());
+        assert!(check_markdown_deletes(&parsed, database.len(), deletes, MarkdownDeletes::Report).is_ok());
+        assert!(check_markdown_deletes(&parsed, database.len(), deletes, MarkdownDeletes::Force).is_ok());
+
+        // An emptied file has no ticket section: refused in every mode, including the watcher's.
+        let empty = parse_tickets_md("");
+        for mode in [MarkdownDeletes::Report, MarkdownDeletes::Apply, MarkdownDeletes::Force] {
+            assert!(check_markdown_deletes(&empty, database.len(), 10, mode).is_err());
+        }
+    }
+
+    #[test]
+    fn deleting_a_few_tickets_is_allowed() {
+        let database: Vec<MdTicket> = (1..=10).map(|n| ticket(n, "t")).collect();
+        let markdown: Vec<MdTicket> = database[..8].to_vec();
+        let content = write_tickets_md(None, &markdown, "Demo");
+        let parsed = parse_tickets_md(&content);
+        let plan = reconcile(
+            &parsed.tickets,
+            &database,
+            &baseline_of(&database),
+            SyncDirection::FromMarkdown,
+            ConflictStrategy::Report,
+        );
+        assert_eq!(plan.db_deletes, vec![9, 10]);
+        assert!(check_markdown_deletes(&parsed, database.len(), 2, MarkdownDeletes::Apply).is_ok());
+    }
+
+    #[test]
+    fn reconcile_reports_conflicts_and_keeps_target_only_changes() {
+        let base = vec![ticket(1, "One"), ticket(2, "Two")];
+        let markdown = vec![ticket(1, "One (md)"), ticket(2, "Two")];
+        let database = vec![ticket(1, "One (db)"), ticket(2, "Two (db)")];
+        let plan = reconcile(
+            &markdown,
+            &database,
+            &baseline_of(&base),
+            SyncDirection::FromMarkdown,
+            ConflictStrategy::Report,
+        );
+        assert_eq!(plan.conflicts.len(), 1);
+        assert_eq!(plan.conflicts[0].number, 1);
+        // #2 changed only in the database: nothing to apply from Markdown.
+        assert!(plan.db_upserts.is_empty());
+        assert!(plan.db_deletes.is_empty());
+
+        let plan = reconcile(
+            &markdown,
+            &database,
+            &baseline_of(&base),
+            SyncDirection::FromMarkdown,
+            ConflictStrategy::Markdown,
+        );
+        assert_eq!(plan.db_upserts, vec![ticket(1, "One (md)")]);
+    }
+}
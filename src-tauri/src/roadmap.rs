@@ -0,0 +1,203 @@
+//! Roadmap export: an iCalendar file with one all-day event per milestone and a Gantt-style JSON file, both built
+//! from db::get_roadmap.
+
+use crate::db::{Roadmap, RoadmapMilestone};
+use chrono::{Duration, NaiveDate, Utc};
+
+/// Directory inside a project repo the export files are written to.
+pub const ROADMAP_DIR: &str = ".cursor/roadmap";
+pub const ICS_FILE: &str = "roadmap.ics";
+pub const JSON_FILE: &str = "roadmap.json";
+
+fn date(value: Option<&str>) -> Option<NaiveDate> {
+    value.and_then(|v| NaiveDate::parse_from_str(v, "%Y-%m-%d").ok())
+}
+
+/// Date span shown for a milestone: from its start (or the end of the previous milestone, or today) to its due date
+/// (or projected completion, or the start).
+fn span(m: &RoadmapMilestone, previous_end: Option<NaiveDate>, today: NaiveDate) -> (NaiveDate, NaiveDate) {
+    let end = date(m.milestone.due_date.as_deref()).or(date(m.projected_completion.as_deref()));
+    let start = date(m.milestone.start_date.as_deref())
+        .or(previous_end)
+        .unwrap_or(today)
+        .min(end.unwrap_or(NaiveDate::MAX));
+    (start, end.unwrap_or(start))
+}
+
+fn spans(roadmap: &Roadmap) -> Vec<(NaiveDate, NaiveDate)> {
+    let today = Utc::now().date_naive();
+    let mut previous_end = None;
+    roadmap
+        .milestones
+        .iter()
+        .map(|m| {
+            let (start, end) = span(m, previous_end, today);
+            previous_end = Some(end);
+            (start, end)
+        })
+        .collect()
+}
+
+fn escape_ics(text: &str) -> String {
+    text.replace('\\', "\\\\")
+        .replace(';', "\\;")
+        .replace(',', "\\,")
+        .replace("\r\n", "\\n")
+        .replace('\n', "\\n")
+}
+
+/// Fold a content line to 75 octets per RFC 5545 (continuation lines start with a space).
+fn fold_ics(line: &str) -> String {
+    let mut out = String::new();
+    let mut width = 0;
+    for c in line.chars() {
+        if width + c.len_utf8() > 75 {
+            out.push_str("\r\n ");
+            width = 1;
+        }
+        out.push(c);
+        width += c.len_utf8();
+    }
+    out
+}
+
+/// iCalendar with an all-day event per milestone that has a due, start or projected date. Event ids are stable per
+/// milestone so calendar apps update events on re-import.
+pub fn to_ics(roadmap: &Roadmap, project_name: &str) -> String {
+    let stamp = Utc::now().format("%Y%m%dT%H%M%SZ").to_string();
+    let mut lines = vec![
+        "BEGIN:VCALENDAR".to_string(),
+        "VERSION:2.0".to_string(),
+        "PRODID:-//KWCode//Roadmap//EN".to_string(),
+        format!("X-WR-CALNAME:{}", escape_ics(&format!("{} roadmap", project_name))),
+    ];
+    for (m, (start, end)) in roadmap.milestones.iter().zip(spans(roadmap)) {
+        let dated = m.milestone.due_date.is_some() || m.milestone.start_date.is_some();
+        if !dated && m.projected_completion.is_none() {
+            continue;
+        }
+        let mut description = format!("Status: {}\n{}/{} tickets done", m.milestone.status, m.done, m.total);
+        if let Some(projected) = &m.projected_completion {
+            description.push_str(&format!("\nProjected completion: {}", projected));
+        }
+        if let Some(due) = &m.milestone.due_date {
+            description.push_str(&format!("\nDue: {}", due));
+        }
+        lines.extend([
+            "BEGIN:VEVENT".to_string(),
+            format!("UID:milestone-{}-{}@kwcode", roadmap.project_id, m.milestone.id),
+            format!("DTSTAMP:{}", stamp),
+            format!("DTSTART;VALUE=DATE:{}", start.format("%Y%m%d")),
+            // DTEND of an all-day event is exclusive.
+            format!("DTEND;VALUE=DATE:{}", (end + Duration::days(1)).format("%Y%m%d")),
+            format!("SUMMARY:{}", escape_ics(&m.milestone.name)),
+            format!("DESCRIPTION:{}", escape_ics(&description)),
+            format!("STATUS:{}", if m.milestone.status == "shipped" { "CONFIRMED" } else { "TENTATIVE" }),
+            "END:VEVENT".to_string(),
+        ]);
+    }
+    lines.push("END:VCALENDAR".to_string());
+    lines.iter().map(|l| fold_ics(l)).collect::<Vec<_>>().join("\r\n") + "\r\n"
+}
+
+/// Gantt-style JSON: one task per milestone with start/end dates, progress (0–100) and a dependency on the previous
+/// milestone, plus the projection inputs.
+pub fn to_gantt_json(roadmap: &Roadmap, project_name: &str) -> serde_json::Value {
+    let mut previous: Option<String> = None;
+    let tasks: Vec<serde_json::Value> = roadmap
+        .milestones
+        .iter()
+        .zip(spans(roadmap))
+        .map(|(m, (start, end))| {
+            let id = format!("milestone-{}", m.milestone.id);
+            let progress = if m.total > 0 { m.done * 100 / m.total } else { 0 };
+            let task = serde_json::json!({
+                "id": id,
+                "name": m.milestone.name,
+                "start": start.format("%Y-%m-%d").to_string(),
+                "end": end.format("%Y-%m-%d").to_string(),
+                "progress": progress,
+                "dependencies": previous.iter().collect::<Vec<_>>(),
+                "status": m.milestone.status,
+                "due_date": m.milestone.due_date,
+                "projected_completion": m.projected_completion,
+                "on_track": m.on_track,
+                "tickets": { "total": m.total, "done": m.done },
+            });
+            previous = Some(id);
+            task
+        })
+        .collect();
+    serde_json::json!({
+        "project": { "id": roadmap.project_id, "name": project_name },
+        "generated_at": Utc::now().to_rfc3339(),
+        "throughput_per_day": roadmap.throughput_per_day,
+        "throughput_window_days": roadmap.throughput_window_days,
+        "tasks": tasks,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::Milestone;
+
+    fn milestone(id: i64, name: &str, status: &str, projected: Option<&str>) -> RoadmapMilestone {
+        RoadmapMilestone {
+            milestone: Milestone {
+                id,
+                project_id: "p".to_string(),
+                name: name.to_string(),
+                slug: String::new(),
+                content: None,
+                start_date: None,
+                due_date: None,
+                position: id,
+                status: status.to_string(),
+                created_at: String::new(),
+                updated_at: String::new(),
+            },
+            total: 4,
+            done: 1,
+            projected_completion: projected.map(str::to_string),
+            on_track: None,
+        }
+    }
+
+    #[test]
+    fn folding_never_splits_a_multi_byte_character() {
+        let line = format!("SUMMARY:{}", "é€😀".repeat(20));
+        let folded = fold_ics(&line);
+        for part in folded.split("\r\n") {
+            assert!(part.len() <= 75, "{} octets: {}", part.len(), part);
+        }
+        assert!(folded.split("\r\n").count() > 1);
+        assert_eq!(folded.replace("\r\n ", ""), line);
+        assert_eq!(fold_ics("SUMMARY:short"), "SUMMARY:short");
+    }
+
+    #[test]
+    fn ics_has_events_for_projected_milestones_only() {
+        let roadmap = Roadmap {
+            project_id: "p".to_string(),
+            throughput_per_day: 0.5,
+            throughput_window_days: 28,
+            milestones: vec![
+                milestone(1, "Shipped, no dates", "shipped", None),
+                milestone(2, "Projected", "active", Some("2999-01-10")),
+                milestone(3, "No throughput", "planned", None),
+            ],
+        };
+        let ics = to_ics(&roadmap, "Demo").replace("\r\n ", "");
+        assert_eq!(ics.matches("BEGIN:VEVENT").count(), 1);
+        assert!(ics.contains("UID:milestone-p-2@kwcode"));
+        assert!(ics.contains("SUMMARY:Projected"));
+        let today = Utc::now().date_naive().format("%Y%m%d").to_string();
+        assert!(ics.contains(&format!("DTSTART;VALUE=DATE:{}", today)));
+        // DTEND is the day after the projected completion.
+        assert!(ics.contains("DTEND;VALUE=DATE:29990111"));
+        assert!(ics.contains("Projected completion: 2999-01-10"));
+        assert!(ics.contains("STATUS:TENTATIVE"));
+        assert!(ics.ends_with("END:VCALENDAR\r\n"));
+    }
+}
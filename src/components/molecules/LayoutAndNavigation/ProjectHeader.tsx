@@ -171,7 +171,7 @@ export function ProjectHeader({ project, projectId }: ProjectHeaderProps) {
             <DialogTitle>Delete project?</DialogTitle>
           </DialogHeader>
           <p className="text-sm text-muted-foreground">
-            This project will be removed from the app. This cannot be undone.
+            This project and its plan tickets, milestones, ideas and implementation log will be moved to the trash.
           </p>
           <DialogFooter>
             <Button variant="outline" onClick={() => setDeleteConfirmOpen(false)}>
@@ -180,7 +180,7 @@ export function ProjectHeader({ project, projectId }: ProjectHeaderProps) {
             <Button
               variant="destructive"
               onClick={async () => {
-                await deleteProject(projectId);
+                await deleteProject(projectId, "cascade");
                 setDeleteConfirmOpen(false);
                 router.replace("/projects");
                 toast.success("Project deleted");
@@ -4,12 +4,18 @@
 import { useState, useCallback, useEffect, useRef, useMemo } from "react";
 import Link from "next/link";
 import { Button } from "@/components/ui/button";
-import { Loader2, RefreshCw, GitBranch, FolderGit2, GitPullRequest, GitCommit, Upload, Copy, FileText } from "lucide-react";
-import { invoke, isTauri } from "@/lib/tauri";
+import { Loader2, RefreshCw, GitBranch, FolderGit2, GitPullRequest, GitCommit, Upload, Copy, FileText, GitMerge } from "lucide-react";
+import { invoke, isTauri, listen } from "@/lib/tauri";
 import { toast } from "sonner";
 import type { Project } from "@/types/project";
 import type { GitInfo } from "@/types/git";
 import type { CursorTreeNode, CursorTreeFolder } from "@/types/file-tree";
+import type { GitProgressPayload } from "@/types/bindings/GitProgressPayload";
+import type { GitCredentialRequestPayload } from "@/types/bindings/GitCredentialRequestPayload";
+import type { ScriptLogPayload } from "@/types/bindings/ScriptLogPayload";
+import type { ScriptExitedPayload } from "@/types/bindings/ScriptExitedPayload";
+import type { GitConflictPayload } from "@/types/bindings/GitConflictPayload";
+import type { GitConflictState } from "@/types/bindings/GitConflictState";
 import { Card } from "@/components/ui/card";
 import { ScrollArea } from "@/components/ui/scroll-area";
 import { Accordion, AccordionContent, AccordionItem, AccordionTrigger } from "@/components/ui/accordion";
@@ -64,6 +70,26 @@ function getStatusStyle(status: string): { label: string; className: string } {
 
 type GitAction = "pull" | "push" | "commit" | null;
 
+type GitNetworkOp = "pull" | "push";
+
+/** Events of a supervised git pull/push run, buffered until invoke returns its run id. */
+type GitRunEvent =
+  | { type: "progress"; payload: GitProgressPayload }
+  | { type: "credential"; payload: GitCredentialRequestPayload }
+  | { type: "log"; payload: ScriptLogPayload }
+  | { type: "conflict"; payload: GitConflictPayload }
+  | { type: "exited"; payload: ScriptExitedPayload };
+
+function gitRunEventRunId(event: GitRunEvent): string {
+  return event.payload.run_id;
+}
+
+/** Last meaningful stderr line of a failed run (git prints the reason last), without the "[stderr] " prefix. */
+function gitFailureReason(stderrLines: string[]): string | undefined {
+  const lines = stderrLines.map((l) => l.replace(/^\[stderr\]\s*/, "").trim()).filter(Boolean);
+  return lines.reverse().find((l) => /^(fatal|error|hint: Updates were rejected|!)/i.test(l)) ?? lines[0];
+}
+
 export function ProjectGitTab({ project, projectId }: ProjectGitTabProps) {
   const [gitInfo, setGitInfo] = useState<GitInfo | null>(null);
   const [loading, setLoading] = useState(false);
@@ -79,8 +105,21 @@ export function ProjectGitTab({ project, projectId }: ProjectGitTabProps) {
   /** Paths of expanded folders in the current project files tree (macOS-style). */
   const [projectFilesExpanded, setProjectFilesExpanded] = useState<Set<string>>(new Set());
 
+  const [gitProgress, setGitProgress] = useState<GitProgressPayload | null>(null);
+  const [credentialRequest, setCredentialRequest] = useState<GitCredentialRequestPayload | null>(null);
+  const [credentialAnswer, setCredentialAnswer] = useState("");
+  /** Merge/rebase in progress with unmerged paths, from get_git_conflict_state or a pull's git-conflict event. */
+  const [conflictState, setConflictState] = useState<GitConflictState | null>(null);
+  const [conflictBusy, setConflictBusy] = useState(false);
+
   const repoPath = project.repoPath?.trim() ?? "";
 
+  /** Pull/push in flight: null run id while git_pull/git_push is still being invoked. */
+  const networkRunRef = useRef<{ op: GitNetworkOp; runId: string | null } | null>(null);
+  const pendingRunEventsRef = useRef<GitRunEvent[]>([]);
+  const runStderrRef = useRef<string[]>([]);
+  const runConflictRef = useRef<GitConflictState | null>(null);
+
   const cancelledRef = useRef(false);
   const cancelledAllFilesRef = useRef(false);
   const versioningFilterInputRef = useRef<HTMLInputElement>(null);
@@ -160,13 +199,23 @@ export function ProjectGitTab({ project, projectId }: ProjectGitTabProps) {
     }
   }, [project.repoPath]);
 
+  const fetchConflictState = useCallback(async () => {
+    if (!repoPath || !isTauri) return;
+    try {
+      setConflictState(await invoke<GitConflictState>("get_git_conflict_state", { projectPath: repoPath }));
+    } catch {
+      setConflictState(null);
+    }
+  }, [repoPath]);
+
   useEffect(() => {
     cancelledRef.current = false;
     fetchGitInfo(() => cancelledRef.current);
+    void fetchConflictState();
     return () => {
       cancelledRef.current = true;
     };
-  }, [fetchGitInfo]);
+  }, [fetchGitInfo, fetchConflictState]);
 
   const fetchAllProjectFiles = useCallback(async () => {
     if (!repoPath || !projectId) return;
@@ -197,33 +246,172 @@ export function ProjectGitTab({ project, projectId }: ProjectGitTabProps) {
     };
   }, [fetchAllProjectFiles, projectId, repoPath]);
 
-  const handlePull = useCallback(async () => {
-    if (!repoPath) return;
-    setActionLoading("pull");
-    try {
-      await invoke<string>("git_pull", { projectPath: repoPath });
-      toast.success("Pulled successfully.");
-      await fetchGitInfo();
-    } catch (e) {
-      toast.error(e instanceof Error ? e.message : String(e));
-    } finally {
+  const finishNetworkRun = useCallback(
+    (exitCode: number | undefined) => {
+      const run = networkRunRef.current;
+      if (!run) return;
+      const label = run.op === "pull" ? "Pull" : "Push";
+      if (exitCode === 0) {
+        toast.success(`${label} completed.`);
+      } else if (runConflictRef.current) {
+        const count = runConflictRef.current.files.length;
+        toast.error(`${label} stopped with conflicts in ${count} file${count !== 1 ? "s" : ""}. Resolve them below.`);
+      } else {
+        const reason = gitFailureReason(runStderrRef.current);
+        toast.error(reason ? `${label} failed: ${reason}` : `${label} failed.`);
+      }
+      networkRunRef.current = null;
+      pendingRunEventsRef.current = [];
+      runStderrRef.current = [];
+      runConflictRef.current = null;
+      setGitProgress(null);
+      setCredentialRequest(null);
       setActionLoading(null);
-    }
-  }, [repoPath, fetchGitInfo]);
+      void fetchGitInfo();
+      void fetchConflictState();
+    },
+    [fetchGitInfo, fetchConflictState]
+  );
 
-  const handlePush = useCallback(async () => {
-    if (!repoPath) return;
-    setActionLoading("push");
-    try {
-      await invoke<string>("git_push", { projectPath: repoPath });
-      toast.success("Pushed successfully.");
-      await fetchGitInfo();
-    } catch (e) {
-      toast.error(e instanceof Error ? e.message : String(e));
-    } finally {
-      setActionLoading(null);
-    }
-  }, [repoPath, fetchGitInfo]);
+  const applyRunEvent = useCallback(
+    (event: GitRunEvent) => {
+      switch (event.type) {
+        case "progress":
+          setGitProgress(event.payload);
+          break;
+        case "credential":
+          setCredentialAnswer("");
+          setCredentialRequest(event.payload);
+          break;
+        case "log":
+          if (event.payload.line.startsWith("[stderr]")) {
+            runStderrRef.current = [...runStderrRef.current.slice(-19), event.payload.line];
+          }
+          break;
+        case "conflict":
+          runConflictRef.current = event.payload.state;
+          setConflictState(event.payload.state);
+          break;
+        case "exited":
+          finishNetworkRun(event.payload.exit_code ?? undefined);
+          break;
+      }
+    },
+    [finishNetworkRun]
+  );
+
+  const handleRunEvent = useCallback(
+    (event: GitRunEvent) => {
+      const run = networkRunRef.current;
+      if (!run) return;
+      // Events can arrive before git_pull/git_push returns the run id; keep them until it does.
+      if (run.runId === null) {
+        pendingRunEventsRef.current.push(event);
+        return;
+      }
+      if (gitRunEventRunId(event) === run.runId) applyRunEvent(event);
+    },
+    [applyRunEvent]
+  );
+
+  useEffect(() => {
+    if (!isTauri) return;
+    let cancelled = false;
+    const unlisteners: Array<() => void> = [];
+    const subscribe = <T,>(name: string, toEvent: (payload: T) => GitRunEvent) => {
+      listen<T>(name, (e) => handleRunEvent(toEvent(e.payload))).then((fn) => {
+        if (cancelled) fn();
+        else unlisteners.push(fn);
+      });
+    };
+    subscribe<GitProgressPayload>("git-progress", (payload) => ({ type: "progress", payload }));
+    subscribe<GitCredentialRequestPayload>("git-credential-request", (payload) => ({ type: "credential", payload }));
+    subscribe<ScriptLogPayload>("script-log", (payload) => ({ type: "log", payload }));
+    subscribe<GitConflictPayload>("git-conflict", (payload) => ({ type: "conflict", payload }));
+    subscribe<ScriptExitedPayload>("script-exited", (payload) => ({ type: "exited", payload }));
+    return () => {
+      cancelled = true;
+      unlisteners.forEach((fn) => fn());
+    };
+  }, [handleRunEvent]);
+
+  const startNetworkRun = useCallback(
+    async (op: GitNetworkOp) => {
+      if (!repoPath || networkRunRef.current) return;
+      networkRunRef.current = { op, runId: null };
+      pendingRunEventsRef.current = [];
+      runStderrRef.current = [];
+      runConflictRef.current = null;
+      setActionLoading(op);
+      try {
+        const { run_id } = await invoke<{ run_id: string }>(op === "pull" ? "git_pull" : "git_push", {
+          projectPath: repoPath,
+        });
+        networkRunRef.current = { op, runId: run_id };
+        const pending = pendingRunEventsRef.current.filter((e) => gitRunEventRunId(e) === run_id);
+        pendingRunEventsRef.current = [];
+        pending.forEach(applyRunEvent);
+      } catch (e) {
+        networkRunRef.current = null;
+        setActionLoading(null);
+        toast.error(e instanceof Error ? e.message : String(e));
+      }
+    },
+    [repoPath, applyRunEvent]
+  );
+
+  const handlePull = useCallback(() => startNetworkRun("pull"), [startNetworkRun]);
+  const handlePush = useCallback(() => startNetworkRun("push"), [startNetworkRun]);
+
+  const answerCredentialRequest = useCallback(
+    async (answer: string | null) => {
+      const request = credentialRequest;
+      if (!request) return;
+      setCredentialRequest(null);
+      setCredentialAnswer("");
+      try {
+        await invoke("answer_git_prompt", { runId: request.run_id, requestId: request.request_id, answer });
+      } catch (e) {
+        toast.error(e instanceof Error ? e.message : String(e));
+      }
+    },
+    [credentialRequest]
+  );
+
+  const resolveConflictFile = useCallback(
+    async (path: string, strategy: "ours" | "theirs") => {
+      setConflictBusy(true);
+      try {
+        setConflictState(
+          await invoke<GitConflictState>("resolve_conflict", { projectPath: repoPath, path, strategy })
+        );
+      } catch (e) {
+        toast.error(e instanceof Error ? e.message : String(e));
+      } finally {
+        setConflictBusy(false);
+      }
+    },
+    [repoPath]
+  );
+
+  const finishConflictOperation = useCallback(
+    async (command: "git_merge_abort" | "git_merge_continue") => {
+      setConflictBusy(true);
+      try {
+        await invoke<string>(command, { projectPath: repoPath });
+        toast.success(command === "git_merge_abort" ? "Aborted." : "Completed.");
+      } catch (e) {
+        toast.error(e instanceof Error ? e.message : String(e));
+      } finally {
+        setConflictBusy(false);
+        void fetchGitInfo();
+        void fetchConflictState();
+      }
+    },
+    [repoPath, fetchGitInfo, fetchConflictState]
+  );
+
+  const credentialIsSecret = /password|passphrase|token/i.test(credentialRequest?.prompt ?? "");
 
   const DEFAULT_COMMIT_MESSAGE = "Update";
 
@@ -363,6 +551,39 @@ export function ProjectGitTab({ project, projectId }: ProjectGitTabProps) {
           />
         </div>
       </Dialog>
+      <Dialog
+        title="Git credentials"
+        isOpen={credentialRequest !== null}
+        onClose={() => void answerCredentialRequest(null)}
+        actions={
+          <>
+            <Button variant="outline" size="sm" onClick={() => void answerCredentialRequest(null)}>
+              Cancel
+            </Button>
+            <Button size="sm" onClick={() => void answerCredentialRequest(credentialAnswer)}>
+              Continue
+            </Button>
+          </>
+        }
+      >
+        <div className={classes[22]}>
+          <Label htmlFor="git-credential-answer">{credentialRequest?.prompt.trim() || "Git needs a credential"}</Label>
+          <Input
+            id="git-credential-answer"
+            type={credentialIsSecret ? "password" : "text"}
+            autoComplete="off"
+            autoFocus
+            value={credentialAnswer}
+            onChange={(e) => setCredentialAnswer(e.target.value)}
+            onKeyDown={(e) => {
+              if (e.key === "Enter") {
+                e.preventDefault();
+                void answerCredentialRequest(credentialAnswer);
+              }
+            }}
+          />
+        </div>
+      </Dialog>
       <div className={classes[23]}>
         <ButtonGroup alignment="right">
           <Button
@@ -416,8 +637,93 @@ export function ProjectGitTab({ project, projectId }: ProjectGitTabProps) {
             Refresh
           </Button>
         </ButtonGroup>
+        {(actionLoading === "pull" || actionLoading === "push") && (
+          <div className="mt-2 space-y-1" aria-live="polite">
+            <p className="text-xs text-muted-foreground">
+              {gitProgress
+                ? `${gitProgress.phase}: ${gitProgress.percent}%${
+                    gitProgress.current != null && gitProgress.total != null
+                      ? ` (${gitProgress.current}/${gitProgress.total})`
+                      : ""
+                  }`
+                : actionLoading === "pull"
+                  ? "Pulling…"
+                  : "Pushing…"}
+            </p>
+            <div className="h-1 w-full overflow-hidden rounded bg-muted">
+              <div
+                className="h-full bg-primary transition-all"
+                style={{ width: `${gitProgress?.percent ?? 0}%` }}
+              />
+            </div>
+          </div>
+        )}
       </div>
 
+      {conflictState?.operation && (
+        <Card className={cn(classes[37], "border-destructive/40")}>
+          <div className="flex flex-wrap items-center justify-between gap-2 mb-2">
+            <h3 className="flex items-center gap-2 text-sm font-semibold text-foreground">
+              <GitMerge className="size-4 text-destructive" />
+              {conflictState.files.length > 0
+                ? `${conflictState.operation} stopped: ${conflictState.files.length} conflicted file${
+                    conflictState.files.length !== 1 ? "s" : ""
+                  }`
+                : `${conflictState.operation} in progress: all conflicts resolved`}
+            </h3>
+            <ButtonGroup alignment="right">
+              <Button
+                variant="outline"
+                size="sm"
+                disabled={conflictBusy}
+                onClick={() => void finishConflictOperation("git_merge_abort")}
+              >
+                Abort {conflictState.operation}
+              </Button>
+              <Button
+                size="sm"
+                disabled={conflictBusy || conflictState.files.length > 0}
+                onClick={() => void finishConflictOperation("git_merge_continue")}
+              >
+                Continue
+              </Button>
+            </ButtonGroup>
+          </div>
+          <ul className="space-y-1">
+            {conflictState.files.map((file) => (
+              <li key={file.path} className="flex flex-wrap items-center justify-between gap-2 text-sm">
+                <span className="flex items-center gap-2 min-w-0">
+                  <span className="rounded px-1.5 py-0.5 bg-destructive/15 text-destructive font-mono text-xs">
+                    {file.status || "U"}
+                  </span>
+                  <span className="font-mono truncate" title={file.path}>
+                    {file.path}
+                  </span>
+                </span>
+                <span className="flex gap-1">
+                  <Button
+                    variant="ghost"
+                    size="sm"
+                    disabled={conflictBusy}
+                    onClick={() => void resolveConflictFile(file.path, "ours")}
+                  >
+                    Keep ours
+                  </Button>
+                  <Button
+                    variant="ghost"
+                    size="sm"
+                    disabled={conflictBusy}
+                    onClick={() => void resolveConflictFile(file.path, "theirs")}
+                  >
+                    Take theirs
+                  </Button>
+                </span>
+              </li>
+            ))}
+          </ul>
+        </Card>
+      )}
+
       <div className={classes[35]}>
         {/* Focus: repo path + current branch */}
         <div className={classes[36]}>
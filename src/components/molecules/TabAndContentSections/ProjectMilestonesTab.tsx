@@ -13,6 +13,7 @@ import { listProjectFiles, type FileEntry } from "@/lib/api-projects";
 import { fetchProjectMilestones } from "@/lib/fetch-project-milestones";
 import { openProjectMilestonesFolderInFileManager } from "@/lib/open-project-milestones-folder";
 import { useRunStore } from "@/store/run-store";
+import { invoke, isTauri } from "@/lib/tauri";
 import {
   Table,
   TableBody,
@@ -168,21 +169,26 @@ export function ProjectMilestonesTab({
     if (selectedMilestoneId == null) return;
     setEditSaving(true);
     try {
-      const res = await fetch(
-        `/api/data/projects/${projectId}/milestones/${selectedMilestoneId}`,
-        {
-          method: "PATCH",
-          headers: { "Content-Type": "application/json" },
-          body: JSON.stringify({
-            name: editName.trim(),
-            slug: editSlug.trim() || undefined,
-            content: editContent.trim() || undefined,
-          }),
+      const patch = {
+        name: editName.trim(),
+        slug: editSlug.trim() || undefined,
+        content: editContent.trim() || undefined,
+      };
+      if (isTauri) {
+        await invoke("update_project_milestone", { args: { projectId, milestoneId: selectedMilestoneId, patch } });
+      } else {
+        const res = await fetch(
+          `/api/data/projects/${projectId}/milestones/${selectedMilestoneId}`,
+          {
+            method: "PATCH",
+            headers: { "Content-Type": "application/json" },
+            body: JSON.stringify(patch),
+          }
+        );
+        if (!res.ok) {
+          const err = await res.json();
+          throw new Error(err.error || "Failed to update milestone");
         }
-      );
-      if (!res.ok) {
-        const err = await res.json();
-        throw new Error(err.error || "Failed to update milestone");
       }
       await loadMilestones();
       setEditOpen(false);
@@ -198,13 +204,18 @@ export function ProjectMilestonesTab({
     if (selectedMilestoneId == null) return;
     setDeleteSaving(true);
     try {
-      const res = await fetch(
-        `/api/data/projects/${projectId}/milestones/${selectedMilestoneId}`,
-        { method: "DELETE" }
-      );
-      if (!res.ok) {
-        const err = await res.json();
-        throw new Error(err.error || "Failed to delete milestone");
+      if (isTauri) {
+        // Tickets of the milestone move to General Development.
+        await invoke("delete_project_milestone", { args: { projectId, milestoneId: selectedMilestoneId } });
+      } else {
+        const res = await fetch(
+          `/api/data/projects/${projectId}/milestones/${selectedMilestoneId}`,
+          { method: "DELETE" }
+        );
+        if (!res.ok) {
+          const err = await res.json();
+          throw new Error(err.error || "Failed to delete milestone");
+        }
       }
       setEditOpen(false);
       setSelectedMilestoneId(null);
@@ -6,6 +6,7 @@ import type { Project } from "@/types/project";
 import type { NightShiftCirclePhase, RunInfo } from "@/types/run";
 import { readProjectFileOrEmpty, listProjectFiles, updateProject } from "@/lib/api-projects";
 import { fetchProjectTicketsAndKanban } from "@/lib/fetch-project-tickets-and-kanban";
+import type { PlanTicket } from "@/types/bindings/PlanTicket";
 import { invoke, isTauri, projectIdArgPayload, projectIdArgOptionalPayload, createPlanTicketPayload, setPlanKanbanStatePayload } from "@/lib/tauri";
 import { fetchProjectMilestones } from "@/lib/fetch-project-milestones";
 import {
@@ -994,12 +995,27 @@ export function ProjectRunTab({ project, projectId }: ProjectRunTabProps) {
     const repoPath = project.repoPath?.trim();
     const projectPath = repoPath ?? "";
     if (!projectPath) return;
-    const tickets = kanbanData?.columns?.in_progress?.items ?? [];
-    if (tickets.length === 0) {
+    const inProgress = kanbanData?.columns?.in_progress?.items ?? [];
+    if (inProgress.length === 0) {
       toast.error("No tickets in progress. Move tickets to In Progress in the Planner tab.");
       return;
     }
     try {
+      let tickets = inProgress;
+      if (isTauri) {
+        // Only run tickets whose dependencies are done, foundations first.
+        const ready = await invoke<PlanTicket[]>("get_ready_plan_tickets", projectIdArgPayload(projectId));
+        const order = new Map(ready.map((t, i) => [t.id, i]));
+        tickets = inProgress.filter((t) => order.has(t.id)).sort((a, b) => order.get(a.id)! - order.get(b.id)!);
+        const blocked = inProgress.filter((t) => !order.has(t.id));
+        if (tickets.length === 0) {
+          toast.error(`All tickets in progress are blocked by unfinished dependencies (${blocked.map((t) => `#${t.number}`).join(", ")}).`);
+          return;
+        }
+        if (blocked.length > 0) {
+          toast.info(`Skipping blocked ticket(s): ${blocked.map((t) => `#${t.number}`).join(", ")}.`);
+        }
+      }
       const implementAllMd = repoPath
         ? (await readProjectFileOrEmpty(projectId, WORKER_IMPLEMENT_ALL_PROMPT_PATH, repoPath))?.trim() ?? ""
         : "";
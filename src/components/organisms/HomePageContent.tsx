@@ -139,9 +139,10 @@ export function HomePageContent() {
     };
   }, [tab]);
 
-  const saveTickets = async (next: Ticket[]) => {
+  /** `clearAll` confirms a save that leaves out every ticket (deleting the last one); the backend refuses it otherwise. */
+  const saveTickets = async (next: Ticket[], clearAll = false) => {
     try {
-      await invoke("save_tickets", { tickets: next });
+      await invoke("save_tickets", { tickets: next, clearAll });
       setTickets(next);
     } catch (e) {
       const msg = e instanceof Error ? e.message : String(e);
@@ -157,7 +158,7 @@ export function HomePageContent() {
   };
 
   const deleteTicket = async (id: string) => {
-    await saveTickets(tickets.filter((t) => t.id !== id));
+    await saveTickets(tickets.filter((t) => t.id !== id), true);
   };
 
   const handleTabChange = useCallback(
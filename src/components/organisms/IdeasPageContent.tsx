@@ -4,6 +4,7 @@
 import { useState, useCallback, useEffect, useRef, useMemo } from "react";
 import { Copy, FileJson, FileText, FolderOpen, Lightbulb, Loader2, RefreshCw, RotateCcw, Search, Table, X } from "lucide-react";
 import { toast } from "sonner";
+import { invoke, isTauri } from "@/lib/tauri";
 import { PrintButton } from "@/components/atoms/buttons/PrintButton";
 import { Breadcrumb } from "@/components/shared/Breadcrumb";
 import { Button } from "@/components/ui/button";
@@ -217,19 +218,23 @@ export function IdeasPageContent() {
     if (formId === undefined || !formTitle.trim()) return;
     setSaveLoading(true);
     try {
-      const res = await fetch("/api/data/ideas", {
-        method: "POST",
-        headers: { "Content-Type": "application/json" },
-        body: JSON.stringify({
-          id: formId,
-          title: formTitle.trim(),
-          description: formDescription.trim(),
-          category: formCategory,
-        }),
-      });
-      if (!res.ok) {
-        const err = await res.json().catch(() => ({}));
-        throw new Error(err.error || res.statusText);
+      const patch = {
+        title: formTitle.trim(),
+        description: formDescription.trim(),
+        category: formCategory,
+      };
+      if (isTauri) {
+        await invoke("update_idea", { args: { ideaId: formId, patch } });
+      } else {
+        const res = await fetch("/api/data/ideas", {
+          method: "POST",
+          headers: { "Content-Type": "application/json" },
+          body: JSON.stringify({ id: formId, ...patch }),
+        });
+        if (!res.ok) {
+          const err = await res.json().catch(() => ({}));
+          throw new Error(err.error || res.statusText);
+        }
       }
       await loadIdeas();
       setEditOpen(false);
@@ -271,10 +276,14 @@ export function IdeasPageContent() {
   const handleDelete = useCallback(
     async (ideaId: number) => {
       try {
-        const res = await fetch(`/api/data/ideas/${ideaId}`, { method: "DELETE" });
-        if (!res.ok) {
-          const err = await res.json().catch(() => ({}));
-          throw new Error(err.error || res.statusText);
+        if (isTauri) {
+          await invoke("delete_idea", { args: { ideaId } });
+        } else {
+          const res = await fetch(`/api/data/ideas/${ideaId}`, { method: "DELETE" });
+          if (!res.ok) {
+            const err = await res.json().catch(() => ({}));
+            throw new Error(err.error || res.statusText);
+          }
         }
         await loadIdeas();
         setEditOpen(false);
@@ -422,7 +422,7 @@ export function ProjectDetailsPageContent(props: ProjectDetailsPageContentProps
                   <DialogTitle>Delete project?</DialogTitle>
                 </DialogHeader>
                 <p className="text-sm text-muted-foreground">
-                  This project will be removed from the app. This cannot be undone.
+                  This project and its plan tickets, milestones, ideas and implementation log will be moved to the trash.
                 </p>
                 <DialogFooter>
                   <Button variant="outline" onClick={() => setDeleteConfirmOpen(false)}>
@@ -431,7 +431,7 @@ export function ProjectDetailsPageContent(props: ProjectDetailsPageContentProps
                   <Button
                     variant="destructive"
                     onClick={async () => {
-                      await deleteProject(projectId);
+                      await deleteProject(projectId, "cascade");
                       setDeleteConfirmOpen(false);
                       toast.success("Project deleted");
                       if (onBack) onBack();
@@ -1043,7 +1043,7 @@ export function ProjectDetailsPageContent(props: ProjectDetailsPageContentProps
             <DialogTitle>Delete project?</DialogTitle>
           </DialogHeader>
           <p className="text-sm text-muted-foreground">
-            This project will be removed from the app. This cannot be undone.
+            This project and its plan tickets, milestones, ideas and implementation log will be moved to the trash.
           </p>
           <DialogFooter>
             <Button variant="outline" onClick={() => setDeleteConfirmOpen(false)}>
@@ -1052,7 +1052,7 @@ export function ProjectDetailsPageContent(props: ProjectDetailsPageContentProps
             <Button
               variant="destructive"
               onClick={async () => {
-                await deleteProject(projectId);
+                await deleteProject(projectId, "cascade");
                 setDeleteConfirmOpen(false);
                 toast.success("Project deleted");
                 if (onBack) onBack();
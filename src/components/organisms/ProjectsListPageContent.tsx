@@ -119,7 +119,7 @@ export function ProjectsListPageContent() {
     e.stopPropagation();
     setError(null);
     try {
-      await deleteProject(projectId);
+      await deleteProject(projectId, "cascade");
       refetch();
     } catch (e: any) {
       setError(e instanceof Error ? e.message : String(e));
@@ -83,7 +83,9 @@ import { openProjectFolderInFileManager } from "@/lib/open-project-folder";
 import { openProjectInEditor } from "@/lib/open-project-in-editor";
 import { openProjectInSystemTerminal } from "@/lib/open-project-in-terminal";
 import { listProjects } from "@/lib/api-projects";
-import { invoke, isTauri } from "@/lib/tauri";
+import { invoke, isTauri, searchPayload } from "@/lib/tauri";
+import type { SearchHit } from "@/types/bindings/SearchHit";
+import type { SearchKind } from "@/types/bindings/SearchKind";
 import { parseFirstRemoteUrl } from "@/lib/parse-first-remote-url";
 import type { GitInfo } from "@/types/git";
 import { getRecentProjectIds } from "@/lib/recent-projects";
@@ -163,9 +165,13 @@ import { useRunStore } from "@/store/run-store";
 import { toast } from "sonner";
 import { CommandPaletteAnnouncer } from "@/components/shared/CommandPaletteAnnouncer";
 
-export type CommandPaletteEntry =
+export type CommandPaletteEntry = (
   | { href: string; label: string; icon: React.ComponentType<{ className?: string }>; onSelect?: never }
-  | { href?: never; label: string; icon: React.ComponentType<{ className?: string }>; onSelect: () => void };
+  | { href?: never; label: string; icon: React.ComponentType<{ className?: string }>; onSelect: () => void }
+) & {
+  /** Full-text search hit: title and snippet with matches in <mark> (HTML-escaped by the backend). */
+  highlight?: { title: string; snippet: string };
+};
 
 /** Nav entries aligned with SidebarNavigation (Dashboard, Tools, Work, System). */
 const NAV_ENTRIES: CommandPaletteEntry[] = [
@@ -187,6 +193,60 @@ const NAV_ENTRIES: CommandPaletteEntry[] = [
   { href: "/loading-screen", label: "Loading", icon: Moon },
 ];
 
+const SEARCH_KIND_ICONS: Record<SearchKind, React.ComponentType<{ className?: string }>> = {
+  plan_ticket: ListTodo,
+  ticket: ClipboardList,
+  idea: Lightbulb,
+  milestone: Flag,
+  prompt: MessageSquare,
+  run_log: Activity,
+};
+
+/** Where a search hit opens: the matching project tab, or the global page for unassigned rows. */
+function searchHitHref(hit: SearchHit): string {
+  const project = hit.project_id ? `/projects/${hit.project_id}` : null;
+  switch (hit.kind) {
+    case "plan_ticket":
+      return project ? `${project}?tab=todo` : "/planner";
+    case "milestone":
+      return project ? `${project}?tab=milestones` : "/planner";
+    case "idea":
+      return project ? `${project}?tab=ideas` : "/ideas";
+    case "run_log":
+      return project ? `${project}?tab=control` : "/run";
+    case "ticket":
+      return "/planner";
+    case "prompt":
+      return "/prompts";
+  }
+}
+
+function decodeEntities(text: string): string {
+  return text
+    .replace(/&lt;/g, "<")
+    .replace(/&gt;/g, ">")
+    .replace(/&quot;/g, '"')
+    .replace(/&amp;/g, "&");
+}
+
+/** Render backend highlight markup (escaped text with <mark> around matches) without injecting HTML. */
+function HighlightedText({ html, className }: { html: string; className?: string }) {
+  const parts = html.split(/<\/?mark>/);
+  return (
+    <span className={className}>
+      {parts.map((part, i) =>
+        i % 2 === 1 ? (
+          <mark key={i} className="bg-primary/20 text-foreground rounded-sm">
+            {decodeEntities(part)}
+          </mark>
+        ) : (
+          decodeEntities(part)
+        )
+      )}
+    </span>
+  );
+}
+
 function filterEntries(entries: CommandPaletteEntry[], query: string): CommandPaletteEntry[] {
   const q = query.trim().toLowerCase();
   if (!q) return entries;
@@ -210,6 +270,7 @@ export function CommandPalette() {
   const effectiveTheme = isValidUIThemeId(uiTheme) ? uiTheme : "light";
   const [open, setOpen] = useState(false);
   const [query, setQuery] = useState("");
+  const [searchHits, setSearchHits] = useState<SearchHit[]>([]);
   const [selectedIndex, setSelectedIndex] = useState(0);
   const [projects, setProjects] = useState<Project[] | null>(null);
   const [clearRunHistoryConfirmOpen, setClearRunHistoryConfirmOpen] = useState(false);
@@ -1701,7 +1762,41 @@ export function CommandPalette() {
     [actionEntries, projectEntries]
   );
 
-  const filtered = filterEntries(allEntries, query);
+  // Full-text search over tickets, ideas, milestones, prompts and run logs (Tauri only), debounced while typing.
+  useEffect(() => {
+    const q = query.trim();
+    if (!open || !isTauri || q.length < 2) {
+      setSearchHits([]);
+      return;
+    }
+    let cancelled = false;
+    const timer = setTimeout(() => {
+      invoke<SearchHit[]>("search", searchPayload(q, { limit: 20 }))
+        .then((hits) => {
+          if (!cancelled) setSearchHits(hits);
+        })
+        .catch(() => {
+          if (!cancelled) setSearchHits([]);
+        });
+    }, 150);
+    return () => {
+      cancelled = true;
+      clearTimeout(timer);
+    };
+  }, [open, query]);
+
+  const searchEntries = useMemo<CommandPaletteEntry[]>(
+    () =>
+      searchHits.map((hit) => ({
+        href: searchHitHref(hit),
+        label: decodeEntities(hit.title.replace(/<\/?mark>/g, "")),
+        icon: SEARCH_KIND_ICONS[hit.kind],
+        highlight: { title: hit.title, snippet: hit.snippet },
+      })),
+    [searchHits]
+  );
+
+  const filtered = [...filterEntries(allEntries, query), ...searchEntries];
   const selectedEntry = filtered[selectedIndex] ?? null;
 
   const openPalette = useCallback(() => {
@@ -2226,7 +2321,7 @@ export function CommandPalette() {
           <Input
             ref={inputRef}
             type="text"
-            placeholder="Search pages and projects…"
+            placeholder="Search pages, projects, tickets and ideas…"
             value={query}
             onChange={(e) => setQuery(e.target.value)}
             className="border-0 focus-visible:ring-0 h-9 flex-1"
@@ -2257,11 +2352,15 @@ export function CommandPalette() {
               </div>
             )}
             {filtered.length === 0 ? (
-              <div className="py-6 text-center text-sm text-muted-foreground">No matching pages.</div>
+              <div className="py-6 text-center text-sm text-muted-foreground">No matching pages or items.</div>
             ) : (
               filtered.map((entry, i) => {
                 const isSelected = i === selectedIndex;
-                const key = "href" in entry && entry.href ? entry.href + entry.label : "action-" + entry.label;
+                const key = entry.highlight
+                  ? "search-" + i + entry.label
+                  : "href" in entry && entry.href
+                    ? entry.href + entry.label
+                    : "action-" + entry.label;
                 return (
                   <button
                     key={key}
@@ -2278,7 +2377,19 @@ export function CommandPalette() {
                     onMouseEnter={() => setSelectedIndex(i)}
                   >
                     <entry.icon className="h-4 w-4 shrink-0 text-muted-foreground" />
-                    <span>{entry.label}</span>
+                    {entry.highlight ? (
+                      <span className="flex min-w-0 flex-col">
+                        <HighlightedText html={entry.highlight.title} className="truncate" />
+                        {entry.highlight.snippet && (
+                          <HighlightedText
+                            html={entry.highlight.snippet}
+                            className="truncate text-xs text-muted-foreground"
+                          />
+                        )}
+                      </span>
+                    ) : (
+                      <span>{entry.label}</span>
+                    )}
                   </button>
                 );
               })
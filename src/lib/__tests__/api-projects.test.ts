@@ -187,9 +187,9 @@ describe("api-projects dual-mode", () => {
       mockInvoke.mockResolvedValue(undefined);
 
       const { deleteProject } = await import("../api-projects");
-      await deleteProject("proj-1");
+      await deleteProject("proj-1", "cascade");
 
-      expect(mockInvoke).toHaveBeenCalledWith("delete_project", { id: "proj-1" });
+      expect(mockInvoke).toHaveBeenCalledWith("delete_project", { id: "proj-1", policy: "cascade" });
     });
 
     it("calls fetch when isTauri is false with DELETE", async () => {
@@ -197,7 +197,7 @@ describe("api-projects dual-mode", () => {
       globalThis.fetch = fetchMock;
 
       const { deleteProject } = await import("../api-projects");
-      await deleteProject("proj-1");
+      await deleteProject("proj-1", "cascade");
 
       expect(fetchMock).toHaveBeenCalledWith("/api/data/projects/proj-1", { method: "DELETE" });
     });
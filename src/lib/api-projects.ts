@@ -96,8 +96,11 @@ export async function updateProject(id: string, body: Partial<CreateProjectBody>
   });
 }
 
-export async function deleteProject(id: string): Promise<void> {
-  return tauriOrFetch<void>("delete_project", { id }, `/api/data/projects/${id}`, { method: "DELETE" });
+/** "cascade" trashes the project's plan tickets, milestones, ideas and implementation log with it; "restrict" refuses while any exist. */
+export type ProjectDeletePolicy = "cascade" | "restrict";
+
+export async function deleteProject(id: string, policy: ProjectDeletePolicy): Promise<void> {
+  return tauriOrFetch<void>("delete_project", { id, policy }, `/api/data/projects/${id}`, { method: "DELETE" });
 }
 
 export async function getProjectExport(id: string, category: keyof ResolvedProject): Promise<string> {
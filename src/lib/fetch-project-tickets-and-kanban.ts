@@ -5,20 +5,17 @@
 
 import { invoke, isTauri, projectIdArgPayload } from "@/lib/tauri";
 import type { ParsedTicket } from "@/lib/todos-kanban";
+import type { KanbanState } from "@/types/bindings/KanbanState";
+import type { PlanTicket } from "@/types/bindings/PlanTicket";
 
-type TicketRow = {
-  id: string;
-  number: number;
-  title: string;
-  description?: string;
-  priority: string;
+/** Tauri returns PlanTicket; the /api route returns the raw plan_tickets row (same snake_case columns). */
+type TicketRow = Pick<PlanTicket, "id" | "number" | "title" | "priority" | "done" | "status"> & {
+  description?: string | null;
   feature_name?: string;
   featureName?: string;
-  done: boolean;
-  status: string;
-  agents?: string[];
-  milestone_id?: number;
-  idea_id?: number;
+  agents?: string[] | null;
+  milestone_id?: number | null;
+  idea_id?: number | null;
 };
 
 function mapRowToParsedTicket(t: TicketRow): ParsedTicket {
@@ -26,14 +23,14 @@ function mapRowToParsedTicket(t: TicketRow): ParsedTicket {
     id: t.id,
     number: t.number,
     title: t.title,
-    description: t.description,
+    description: t.description ?? undefined,
     priority: (t.priority as ParsedTicket["priority"]) || "P1",
     featureName: t.featureName ?? t.feature_name ?? "General",
     done: t.done,
     status: (t.status as ParsedTicket["status"]) || "Todo",
-    agents: t.agents,
-    milestoneId: t.milestone_id,
-    ideaId: t.idea_id,
+    agents: t.agents ?? undefined,
+    milestoneId: t.milestone_id ?? undefined,
+    ideaId: t.idea_id ?? undefined,
   };
 }
 
@@ -54,8 +51,8 @@ export async function fetchProjectTicketsAndKanban(
 
   if (isTauri) {
     const [ticketsList, kanbanState] = await Promise.all([
-      invoke<TicketRow[]>("get_project_tickets", projectIdArgPayload(projectId)),
-      invoke<{ inProgressIds: string[] }>("get_project_kanban_state", projectIdArgPayload(projectId)),
+      invoke<PlanTicket[]>("get_project_tickets", projectIdArgPayload(projectId)),
+      invoke<KanbanState>("get_project_kanban_state", projectIdArgPayload(projectId)),
     ]);
     apiTickets = ticketsList ?? [];
     inProgressIds = kanbanState?.inProgressIds ?? [];
@@ -69,7 +66,7 @@ export async function fetchProjectTicketsAndKanban(
       throw new Error((err as { error?: string }).error || "Failed to load tickets");
     }
     apiTickets = (await ticketsRes.json()) as TicketRow[];
-    const state = (await stateRes.json()) as { inProgressIds: string[] };
+    const state = (await stateRes.json()) as KanbanState;
     inProgressIds = state.inProgressIds ?? [];
   }
 
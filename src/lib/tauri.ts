@@ -1,6 +1,13 @@
 /**
  * Tauri detection and invoke/listen helpers. Used by run-store and components to call Rust commands or fall back to fetch in browser.
  */
+import type { AppError } from "@/types/bindings/AppError";
+import type { ErrorCode } from "@/types/bindings/ErrorCode";
+import type { JsonValue } from "@/types/bindings/serde_json/JsonValue";
+import type { PlanTicketPatch } from "@/types/bindings/PlanTicketPatch";
+import type { SearchKind } from "@/types/bindings/SearchKind";
+import type { TrashKind } from "@/types/bindings/TrashKind";
+
 /** Detect Tauri at runtime (WebView has __TAURI_INTERNALS__ or __TAURI__) or via env (when dev server is started with NEXT_PUBLIC_IS_TAURI=true). */
 function detectTauri(): boolean {
   if (typeof window === "undefined") return process.env.NEXT_PUBLIC_IS_TAURI === "true";
@@ -108,6 +115,104 @@ export function setPlanKanbanStatePayload(projectId: string, inProgressIds: stri
   return { args: { project_id: projectId, in_progress_ids: inProgressIds } };
 }
 
+/** Payload for move_plan_ticket. In the built app, the IPC expects the parameter key `args`. Omit position to append. */
+export function movePlanTicketPayload(
+  projectId: string,
+  ticketId: string,
+  column: string,
+  position?: number
+): { args: { projectId: string; ticketId: string; column: string; position?: number } } {
+  return { args: { projectId, ticketId, column, ...(position !== undefined ? { position } : {}) } };
+}
+
+/** Payload for claim_plan_ticket (records an agent run picking up a ticket). In the built app, the IPC expects the parameter key `args`. */
+export function claimPlanTicketPayload(
+  projectId: string,
+  ticketId: string,
+  runId: string
+): { args: { projectId: string; ticketId: string; runId: string } } {
+  return { args: { projectId, ticketId, runId } };
+}
+
+/** Payload for patch_plan_ticket (only the fields in `patch` change). In the built app, the IPC expects the parameter key `args`. */
+export function patchPlanTicketPayload(
+  projectId: string,
+  ticketId: string,
+  patch: PlanTicketPatch
+): { args: { projectId: string; ticketId: string; patch: PlanTicketPatch } } {
+  return { args: { projectId, ticketId, patch } };
+}
+
+/** Payload for bulk_update_plan_tickets (board multi-select). Pass column to also move the tickets. In the built app, the IPC expects the parameter key `args`. */
+export function bulkUpdatePlanTicketsPayload(
+  projectId: string,
+  ticketIds: string[],
+  patch: PlanTicketPatch,
+  column?: string
+): { args: { projectId: string; ticketIds: string[]; patch: PlanTicketPatch; column?: string } } {
+  return { args: { projectId, ticketIds, patch, ...(column ? { column } : {}) } };
+}
+
+/** Payload for search (full-text over tickets, ideas, milestones, prompts and run logs). In the built app, the IPC expects the parameter key `args`. */
+export function searchPayload(
+  query: string,
+  options: { kinds?: SearchKind[]; projectId?: string; limit?: number } = {}
+): { args: { query: string; kinds?: SearchKind[]; projectId?: string; limit?: number } } {
+  return { args: { query, ...options } };
+}
+
+/** Payload for restore_from_trash. In the built app, the IPC expects the parameter key `args`. */
+export function restoreFromTrashPayload(kind: TrashKind, id: string): { args: { kind: TrashKind; id: string } } {
+  return { args: { kind, id } };
+}
+
+/** Payload for empty_trash (all kinds unless `kind` is set). In the built app, the IPC expects the parameter key `args`. */
+export function emptyTrashPayload(
+  options: { kind?: TrashKind; olderThanDays?: number } = {}
+): { args: { kind?: TrashKind; olderThanDays?: number } } {
+  return { args: { ...options } };
+}
+
+/** Payload for sync_planner_from_markdown / sync_planner_to_markdown. In the built app, the IPC expects the parameter key `args`. */
+export function syncPlannerMarkdownPayload(
+  projectId: string,
+  strategy?: "report" | "markdown" | "database",
+  force?: boolean
+): { args: { projectId: string; strategy?: string; force?: boolean } } {
+  return { args: { projectId, ...(strategy ? { strategy } : {}), ...(force ? { force } : {}) } };
+}
+
+/**
+ * Rejection from a Tauri command. Commands return `{ code, message, details }` (see src-tauri/src/error.rs);
+ * `message` and `toString()` keep existing `String(e)` / message checks working, `code` is for branching.
+ */
+export class TauriCommandError extends Error {
+  readonly code: ErrorCode;
+  readonly details?: JsonValue;
+  readonly command: string;
+
+  constructor(error: AppError, command: string) {
+    super(error.message);
+    this.name = "TauriCommandError";
+    this.command = command;
+    this.code = error.code;
+    this.details = error.details;
+  }
+
+  toString(): string {
+    return this.message;
+  }
+}
+
+function isAppError(value: unknown): value is AppError {
+  return (
+    typeof value === "object" &&
+    value !== null &&
+    typeof (value as AppError).code === "string" &&
+    typeof (value as AppError).message === "string"
+  );
+}
+
 export const invoke = async <T>(cmd: string, args?: Record<string, unknown>): Promise<T> => {
   if (isTauri && invokeReadyPromise) {
     const timeout = new Promise<never>((_, reject) =>
@@ -125,7 +230,11 @@ export const invoke = async <T>(cmd: string, args?: Record<string, unknown>): Pr
     console.warn(msg);
     return Promise.reject(new Error(msg));
   }
-  return tauriInvoke(cmd, args) as Promise<T>;
+  try {
+    return (await tauriInvoke(cmd, args)) as T;
+  } catch (e) {
+    throw isAppError(e) ? new TauriCommandError(e, cmd) : e;
+  }
 };
 
 export const listen = async <T>(event: string, handler: (event: { payload: T }) => void): Promise<() => void> => {
@@ -6,7 +6,7 @@
  */
 import { create } from "zustand";
 import { useShallow } from "zustand/react/shallow";
-import { invoke, isTauri, runRunTerminalAgentPayload } from "@/lib/tauri";
+import { claimPlanTicketPayload, invoke, isTauri, runRunTerminalAgentPayload } from "@/lib/tauri";
 import { isImplementAllRun, getNextFreeSlotOrNull } from "@/lib/run-helpers";
 import { getApiErrorMessage } from "@/lib/utils";
 import { toast } from "sonner";
@@ -573,6 +573,10 @@ export const useRunStore = create<RunStore>()((set, get) => ({
           promptContent: promptContent.trim() || null,
         });
         if (firstRunId == null) firstRunId = run_id;
+        if (meta?.projectId && meta.ticketId) {
+          // Audit trail only; a failed claim must not stop the run.
+          invoke("claim_plan_ticket", claimPlanTicketPayload(meta.projectId, meta.ticketId, run_id)).catch(() => {});
+        }
         set((s) => ({
           runningRuns: [
             ...s.runningRuns,
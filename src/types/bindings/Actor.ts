@@ -0,0 +1,7 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { ActorKind } from "./ActorKind";
+
+/**
+ * Who made a change. `id` is the run id for agents and the commit hash for tickets closed by a commit.
+ */
+export type Actor = { kind: ActorKind, id: string | null, };
@@ -0,0 +1,8 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { BackupKind } from "./BackupKind";
+
+export type BackupInfo = { path: string, file_name: string, kind: BackupKind, 
+/**
+ * File modification time (RFC 3339).
+ */
+created_at: string, size_bytes: number, };
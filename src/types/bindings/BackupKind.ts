@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * How a backup file came to be, from its file name.
+ */
+export type BackupKind = "manual" | "scheduled" | "migration" | "pre_restore" | "pre_repair";
@@ -0,0 +1,10 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Automatic backup settings (kv_store), checked hourly by the background thread started in run().
+ */
+export type BackupSchedule = { enabled: boolean, interval_hours: number, 
+/**
+ * Scheduled backups to keep; older ones are deleted after each new one.
+ */
+keep: number, };
@@ -0,0 +1,12 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { Actor } from "./Actor";
+import type { PlanTicketPatch } from "./PlanTicketPatch";
+
+/**
+ * Args for bulk_update_plan_tickets. In built app, IPC expects payload key `args`.
+ */
+export type BulkUpdatePlanTicketsArgs = { project_id: string, ticket_ids: Array<string>, patch: PlanTicketPatch, 
+/**
+ * Also move every ticket to the end of this column.
+ */
+column: string | null, actor: Actor | null, };
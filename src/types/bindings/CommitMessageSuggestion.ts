@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Suggested Conventional Commits message plus the parts it was built from.
+ */
+export type CommitMessageSuggestion = { message: string, type: string, scope: string | null, subject: string, ticket_number: number | null, files: Array<string>, };
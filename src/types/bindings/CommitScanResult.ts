@@ -0,0 +1,7 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { TicketCommitLink } from "./TicketCommitLink";
+
+/**
+ * Result of scan_commit_ticket_refs.
+ */
+export type CommitScanResult = { commits_scanned: number, links_added: number, tickets_closed: Array<number>, links: Array<TicketCommitLink>, };
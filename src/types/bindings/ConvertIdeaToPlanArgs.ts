@@ -0,0 +1,10 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Args for convert_idea_to_plan. In built app, IPC expects payload key `args`.
+ */
+export type ConvertIdeaToPlanArgs = { idea_id: number, project_id: string, 
+/**
+ * Empty = the idea's title.
+ */
+milestone_name: string, ticket_titles: Array<string>, };
@@ -0,0 +1,3 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+export type CreateIdeaArgs = { project_id: string | null, title: string, description: string | null, category: string | null, source: string | null, };
@@ -0,0 +1,11 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { Actor } from "./Actor";
+
+/**
+ * Args for create_plan_ticket; accept camelCase from frontend. In built app, IPC expects payload key `args`.
+ */
+export type CreatePlanTicketArgs = { project_id: string, title: string, description: string | null, priority: string | null, feature_name: string | null, milestone_id: number, idea_id: number | null, agents: string | null, 
+/**
+ * Who is creating the ticket; defaults to the user.
+ */
+actor: Actor | null, };
@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Aggregated counts for the dashboard metrics view.
+ */
+export type DashboardMetrics = { tickets_count: number, features_count: number, prompts_count: number, designs_count: number, active_projects_count: number, all_projects_count: number, };
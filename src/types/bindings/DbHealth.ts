@@ -0,0 +1,15 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { HealthIssue } from "./HealthIssue";
+
+/**
+ * Result of check_db_health (run on startup and by get_db_health).
+ */
+export type DbHealth = { checked_at: string, 
+/**
+ * No issues found.
+ */
+ok: boolean, schema_version: number, latest_version: number, issues: Array<HealthIssue>, 
+/**
+ * Rows currently in the quarantine table.
+ */
+quarantined: number, };
@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Args for delete_idea. In built app, IPC expects payload key `args`.
+ */
+export type DeleteIdeaArgs = { idea_id: number, };
@@ -0,0 +1,11 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { MilestoneTicketPolicy } from "./MilestoneTicketPolicy";
+
+/**
+ * Args for delete_project_milestone. In built app, IPC expects payload key `args`.
+ */
+export type DeleteProjectMilestoneArgs = { project_id: string, milestone_id: number, 
+/**
+ * What to do with the milestone's tickets; defaults to moving them to General Development.
+ */
+tickets: MilestoneTicketPolicy, };
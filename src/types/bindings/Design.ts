@@ -0,0 +1,3 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+export type Design = { id: string, name: string, description: string | null, image_url: string | null, created_at: string, updated_at: string, };
@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Entry for one directory listing (one level under a root). Matches frontend FileEntry shape.
+ */
+export type DirListingEntry = { name: string, isDirectory: boolean, size: number, updatedAt: string, };
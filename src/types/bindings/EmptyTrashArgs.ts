@@ -0,0 +1,15 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { TrashKind } from "./TrashKind";
+
+/**
+ * Args for empty_trash. In built app, IPC expects payload key `args`.
+ */
+export type EmptyTrashArgs = { 
+/**
+ * Only this kind; everything when omitted.
+ */
+kind: TrashKind | null, 
+/**
+ * Only rows deleted more than this many days ago.
+ */
+older_than_days: number | null, };
@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Stable, machine-readable error category.
+ */
+export type ErrorCode = "not_found" | "invalid_argument" | "not_a_git_repo" | "path_outside_root" | "process_spawn_failed" | "git" | "conflict" | "db" | "db_unavailable" | "io" | "serialization" | "internal";
@@ -0,0 +1,10 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Args for export_roadmap. In built app, IPC expects payload key `args`.
+ */
+export type ExportRoadmapArgs = { project_id: string, 
+/**
+ * "ics", "json" or "all" (default).
+ */
+format: string | null, };
@@ -0,0 +1,10 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * A milestone that has to be done in an application. One feature has many tickets and must have at least one.
+ */
+export type Feature = { id: string, title: string, 
+/**
+ * At least one ticket; a feature groups work items (tickets) for this milestone.
+ */
+ticket_ids: Array<string>, prompt_ids: Array<number>, project_paths: Array<string>, created_at: string, updated_at: string, };
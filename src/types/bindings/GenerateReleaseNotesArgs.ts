@@ -0,0 +1,19 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+export type GenerateReleaseNotesArgs = { project_id: string, milestone_id: number | null, 
+/**
+ * Ref range alternative to milestone_id: commits in from_ref..to_ref (to_ref defaults to HEAD).
+ */
+from_ref: string | null, to_ref: string | null, 
+/**
+ * "markdown" (default) or "keep-a-changelog".
+ */
+format: string | null, 
+/**
+ * Heading / version label; defaults to the milestone name or to_ref.
+ */
+version: string | null, write_changelog: boolean, 
+/**
+ * Create an annotated tag with this name whose message is the release notes.
+ */
+tag: string | null, };
@@ -0,0 +1,11 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { ErrorCode } from "./ErrorCode";
+
+/**
+ * Per-repo outcome of git_bulk_fetch / git_bulk_pull. Also emitted as `git-bulk-result` as each repo finishes.
+ */
+export type GitBulkOpResult = { operation: string, project_id: string | null, name: string, path: string, ok: boolean, output: string, error: string | null, 
+/**
+ * "conflict" when a pull stopped on unmerged paths (listed in `error`).
+ */
+error_code: ErrorCode | null, };
@@ -0,0 +1,10 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * One unmerged path with the index blobs for each stage (1 = base, 2 = ours, 3 = theirs).
+ */
+export type GitConflictFile = { path: string, 
+/**
+ * Porcelain XY code, e.g. "UU" (both modified), "AA" (both added), "DU" (deleted by us).
+ */
+status: string, base_blob: string | null, ours_blob: string | null, theirs_blob: string | null, };
@@ -0,0 +1,11 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Contents of each side of a conflicted file, byte for byte. Missing stages (e.g. deleted by them) and sides that are
+ * not UTF-8 text are None.
+ */
+export type GitConflictFileVersions = { path: string, base: string | null, ours: string | null, theirs: string | null, 
+/**
+ * Working tree file with conflict markers.
+ */
+working: string | null, };
@@ -0,0 +1,7 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { GitConflictState } from "./GitConflictState";
+
+/**
+ * Conflicts a pull run stopped on. Emitted as `git-conflict` right before the run's `script-exited`.
+ */
+export type GitConflictPayload = { run_id: string, state: GitConflictState, };
@@ -0,0 +1,7 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { GitConflictFile } from "./GitConflictFile";
+
+/**
+ * Operation the repo is in the middle of (merge / rebase / cherry-pick / revert) and its conflicted files.
+ */
+export type GitConflictState = { operation: string | null, files: Array<GitConflictFile>, };
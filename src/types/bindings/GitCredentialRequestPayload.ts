@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Credential prompt raised by the GIT_ASKPASS helper. Answer with `answer_git_prompt`.
+ */
+export type GitCredentialRequestPayload = { run_id: string, request_id: string, prompt: string, };
@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Return git diff and full file content for a changed file. Used when clicking a file in the Git tab.
+ */
+export type GitFileView = { diff: string, full_content: string | null, };
@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Git repository info for the project details Git tab.
+ */
+export type GitInfo = { current_branch: string, branches: Array<string>, remotes: string, status_short: string, last_commits: Array<string>, head_ref: string, config_preview: string, };
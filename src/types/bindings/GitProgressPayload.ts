@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Progress update parsed from git's `--progress` output (e.g. "Receiving objects:  45% (9/20)").
+ */
+export type GitProgressPayload = { run_id: string, phase: string, percent: number, current: number | null, total: number | null, };
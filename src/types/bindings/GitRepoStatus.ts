@@ -0,0 +1,10 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * One row of the multi-repo git dashboard.
+ */
+export type GitRepoStatus = { project_id: string | null, name: string, path: string, branch: string, dirty_count: number, ahead: number, behind: number, has_upstream: boolean, 
+/**
+ * Dirty, ahead/behind, or unreadable.
+ */
+needs_attention: boolean, error: string | null, };
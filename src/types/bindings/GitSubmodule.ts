@@ -0,0 +1,14 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * One submodule from `git submodule status --recursive`.
+ */
+export type GitSubmodule = { path: string, name: string | null, url: string | null, commit: string, 
+/**
+ * "up_to_date", "uninitialized", "modified" (checked-out commit differs from the recorded one) or "conflict".
+ */
+status: string, 
+/**
+ * `git describe` of the checked-out commit, when available.
+ */
+describe: string | null, };
@@ -0,0 +1,9 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { HealthIssueKind } from "./HealthIssueKind";
+import type { HealthRepair } from "./HealthRepair";
+
+export type HealthIssue = { kind: HealthIssueKind, table: string | null, 
+/**
+ * SQLite rowid of the offending row.
+ */
+row_id: number | null, message: string, repair: HealthRepair, };
@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Category of a problem found by check_db_health.
+ */
+export type HealthIssueKind = "integrity" | "schema" | "foreign_key" | "orphan" | "dangling_link" | "missing_milestone";
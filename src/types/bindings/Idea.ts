@@ -0,0 +1,34 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Idea (ideas row). project_id None = not tied to a project.
+ */
+export type Idea = { id: number, project_id: string | null, title: string, description: string, category: string, source: string, 
+/**
+ * "new", "accepted", "rejected" or "converted".
+ */
+status: string, 
+/**
+ * People or events affected per period (RICE).
+ */
+reach: number | null, impact: number | null, 
+/**
+ * Percent, 0–100.
+ */
+confidence: number | null, 
+/**
+ * Person-weeks (RICE).
+ */
+effort: number | null, 
+/**
+ * 0–10 (ICE).
+ */
+ease: number | null, 
+/**
+ * reach × impact × confidence% ÷ effort; None while a factor is missing.
+ */
+rice_score: number | null, 
+/**
+ * impact × confidence (as 0–10) × ease; None while a factor is missing.
+ */
+ice_score: number | null, created_at: string, updated_at: string, };
@@ -0,0 +1,9 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { Idea } from "./Idea";
+import type { Milestone } from "./Milestone";
+import type { PlanTicket } from "./PlanTicket";
+
+/**
+ * Result of convert_idea_to_plan.
+ */
+export type IdeaConversion = { idea: Idea, milestone: Milestone, tickets: Array<PlanTicket>, };
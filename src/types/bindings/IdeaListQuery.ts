@@ -0,0 +1,16 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { IdeaScoreMethod } from "./IdeaScoreMethod";
+import type { IdeaSort } from "./IdeaSort";
+
+/**
+ * Filters and order for get_ideas_list. All fields are optional.
+ */
+export type IdeaListQuery = { category?: string, source?: string, status?: string, 
+/**
+ * Score method used by `sort: "score"` and `min_score`.
+ */
+score_method?: IdeaScoreMethod, 
+/**
+ * Only ideas scoring at least this much (unscored ideas are dropped).
+ */
+min_score?: number, sort?: IdeaSort, descending?: boolean, };
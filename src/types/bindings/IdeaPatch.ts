@@ -0,0 +1,14 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Partial update of an idea. `project_id` can be cleared with null to untie the idea from its project.
+ */
+export type IdeaPatch = { title?: string, description?: string, category?: string, project_id?: string | null, 
+/**
+ * "new", "accepted", "rejected" or "converted".
+ */
+status?: string, 
+/**
+ * Scoring factors (see Idea); null clears.
+ */
+reach?: number | null, impact?: number | null, confidence?: number | null, effort?: number | null, ease?: number | null, };
@@ -0,0 +1,7 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+export type ImplementationLogEntry = { id: number, project_id: string, run_id: string, ticket_number: number, ticket_title: string, milestone_id: number | null, idea_id: number | null, completed_at: string, files_changed: string, summary: string, created_at: string, status: string, 
+/**
+ * Commit that referenced this ticket, recorded by scan_commit_ticket_refs.
+ */
+commit_hash: string | null, };
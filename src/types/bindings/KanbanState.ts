@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Planner kanban state: the tickets in the "In progress" column.
+ */
+export type KanbanState = { inProgressIds: Array<string>, };
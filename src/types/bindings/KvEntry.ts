@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Key-value pair for kv_store table (for Data view).
+ */
+export type KvEntry = { key: string, value: string, };
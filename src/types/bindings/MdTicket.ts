@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * The part of a plan ticket that tickets.md carries. Status beyond done/not done, milestone and idea stay DB-only.
+ */
+export type MdTicket = { number: number, title: string, description: string | null, priority: string, feature_name: string, done: boolean, agents: Array<string>, };
@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Args for merge_project_milestones. In built app, IPC expects payload key `args`.
+ */
+export type MergeProjectMilestonesArgs = { project_id: string, source_ids: Array<number>, target_id: number, };
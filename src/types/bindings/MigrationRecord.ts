@@ -0,0 +1,10 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Applied migration as recorded in schema_migrations (shown on the Configuration page).
+ */
+export type MigrationRecord = { version: number, name: string, applied_at: string, 
+/**
+ * Copy of app.db taken right before this migration ran (None for a fresh database).
+ */
+backup_path: string | null, };
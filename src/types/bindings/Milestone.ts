@@ -0,0 +1,22 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Milestone (milestones row).
+ */
+export type Milestone = { id: number, project_id: string, name: string, slug: string, content: string | null, 
+/**
+ * YYYY-MM-DD.
+ */
+start_date: string | null, 
+/**
+ * YYYY-MM-DD.
+ */
+due_date: string | null, 
+/**
+ * Order in the milestone list and roadmap.
+ */
+position: number, 
+/**
+ * "planned", "active" or "shipped".
+ */
+status: string, created_at: string, updated_at: string, };
@@ -0,0 +1,22 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Partial update of a milestone. `content` can be cleared with null.
+ */
+export type MilestonePatch = { name?: string, 
+/**
+ * Empty = derive from the name.
+ */
+slug?: string, content?: string | null, 
+/**
+ * YYYY-MM-DD; null clears.
+ */
+start_date?: string | null, 
+/**
+ * YYYY-MM-DD; null clears.
+ */
+due_date?: string | null, 
+/**
+ * "planned", "active" or "shipped".
+ */
+status?: string, };
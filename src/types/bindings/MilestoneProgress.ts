@@ -0,0 +1,15 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { PriorityCounts } from "./PriorityCounts";
+
+/**
+ * Ticket counts for one milestone (get_milestone_progress).
+ */
+export type MilestoneProgress = { milestone_id: number, name: string, total: number, done: number, 
+/**
+ * Open tickets in the "In progress" column.
+ */
+in_progress: number, open_by_priority: PriorityCounts, 
+/**
+ * Done tickets completed within the past 7 / 30 days (see get_milestone_progress).
+ */
+done_last_7_days: number, done_last_30_days: number, };
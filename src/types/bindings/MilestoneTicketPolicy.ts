@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * What happens to a deleted milestone's tickets.
+ */
+export type MilestoneTicketPolicy = { "mode": "general" } | { "mode": "milestone", milestone_id: number, } | { "mode": "delete_tickets" };
@@ -0,0 +1,11 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { Actor } from "./Actor";
+
+/**
+ * Args for move_plan_ticket. In built app, IPC expects payload key `args`.
+ */
+export type MovePlanTicketArgs = { project_id: string, ticket_id: string, column: string, 
+/**
+ * 0-based index in the target column; omitted = last.
+ */
+position: number | null, actor: Actor | null, };
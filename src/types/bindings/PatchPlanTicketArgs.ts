@@ -0,0 +1,8 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { Actor } from "./Actor";
+import type { PlanTicketPatch } from "./PlanTicketPatch";
+
+/**
+ * Args for patch_plan_ticket. In built app, IPC expects payload key `args`.
+ */
+export type PatchPlanTicketArgs = { project_id: string, ticket_id: string, patch: PlanTicketPatch, actor: Actor | null, };
@@ -0,0 +1,18 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Planner ticket (plan_tickets row). snake_case like the /api/data routes, which return the raw rows.
+ */
+export type PlanTicket = { id: string, project_id: string, number: number, title: string, description: string | null, 
+/**
+ * "P0".."P3".
+ */
+priority: string, feature_name: string, done: boolean, 
+/**
+ * Workflow column the ticket is in (see Workflow).
+ */
+status: string, 
+/**
+ * Order within its column.
+ */
+position: number, milestone_id: number | null, idea_id: number | null, agents: Array<string> | null, created_at: string, updated_at: string, };
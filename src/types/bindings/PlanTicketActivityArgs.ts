@@ -0,0 +1,14 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Args for get_plan_ticket_activity. In built app, IPC expects payload key `args`.
+ */
+export type PlanTicketActivityArgs = { project_id: string, 
+/**
+ * Page size (default 50, at most 500).
+ */
+limit: number | null, 
+/**
+ * Only events older than this id (the last id of the previous page).
+ */
+before_id: number | null, };
@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * `ticket_id` is blocked by `depends_on_id` until that ticket is done.
+ */
+export type PlanTicketDependency = { ticket_id: string, depends_on_id: string, };
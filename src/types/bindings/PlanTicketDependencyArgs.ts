@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Args for add/remove_plan_ticket_dependency. In built app, IPC expects payload key `args`.
+ */
+export type PlanTicketDependencyArgs = { project_id: string, ticket_id: string, depends_on_id: string, };
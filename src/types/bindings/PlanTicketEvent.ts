@@ -0,0 +1,12 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { Actor } from "./Actor";
+import type { JsonValue } from "./serde_json/JsonValue";
+
+/**
+ * One entry of a plan ticket's audit trail (plan_ticket_events row). Kept after the ticket is deleted.
+ */
+export type PlanTicketEvent = { id: number, project_id: string, ticket_id: string, ticket_number: number, 
+/**
+ * "created", "updated" (one field), "moved" (status/position), "claimed" (by an agent run) or "deleted".
+ */
+kind: string, field: string | null, old_value: JsonValue | null, new_value: JsonValue | null, actor: Actor, created_at: string, };
@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Args for get_plan_ticket_history. In built app, IPC expects payload key `args`.
+ */
+export type PlanTicketHistoryArgs = { project_id: string, ticket_id: string, };
@@ -0,0 +1,18 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Partial update of a plan ticket: omitted fields are left alone. `description` and `idea_id` can be cleared with null.
+ */
+export type PlanTicketPatch = { title?: string, description?: string | null, 
+/**
+ * "P0".."P3".
+ */
+priority?: string, feature_name?: string, 
+/**
+ * Must be a milestone of the ticket's project.
+ */
+milestone_id?: number, idea_id?: number | null, 
+/**
+ * JSON list of agent names, as in create_plan_ticket; "" clears.
+ */
+agents?: string, };
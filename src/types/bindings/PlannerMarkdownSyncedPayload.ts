@@ -0,0 +1,7 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { PlannerSyncReport } from "./PlannerSyncReport";
+
+/**
+ * Payload of "planner-markdown-synced", emitted after a watched tickets.md changed and was synced into plan_tickets.
+ */
+export type PlannerMarkdownSyncedPayload = { project_id: string, report: PlannerSyncReport | null, error: string | null, };
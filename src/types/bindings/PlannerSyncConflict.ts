@@ -0,0 +1,13 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { MdTicket } from "./MdTicket";
+
+/**
+ * A ticket changed in tickets.md and in the database since the last sync, or a tickets.md change the board refused.
+ * None = deleted on that side.
+ */
+export type PlannerSyncConflict = { number: number, markdown: MdTicket | null, database: MdTicket | null, 
+/**
+ * Why the Markdown version was not applied (e.g. a disallowed move or a full column); None for edits on both
+ * sides.
+ */
+reason: string | null, };
@@ -0,0 +1,16 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { PlannerSyncConflict } from "./PlannerSyncConflict";
+
+/**
+ * Outcome of one sync. created/updated/deleted are ticket numbers on the target side (DB for from_markdown,
+ * tickets.md for to_markdown).
+ */
+export type PlannerSyncReport = { direction: string, path: string, created: Array<number>, updated: Array<number>, deleted: Array<number>, 
+/**
+ * Tickets missing from tickets.md that were kept (watcher syncs); an explicit sync deletes them.
+ */
+pending_deletes: Array<number>, unchanged: number, conflicts: Array<PlannerSyncConflict>, warnings: Array<string>, 
+/**
+ * True when tickets.md was rewritten (to_markdown only).
+ */
+written: boolean, };
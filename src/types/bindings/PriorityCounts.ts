@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Open tickets of a milestone by priority.
+ */
+export type PriorityCounts = { p0: number, p1: number, p2: number, p3: number, };
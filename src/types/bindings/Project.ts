@@ -0,0 +1,15 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { JsonValue } from "./serde_json/JsonValue";
+
+/**
+ * Project record (camelCase for frontend). Stored in the projects table; the id lists live in project_* link tables.
+ */
+export type Project = { 
+/**
+ * Optional on create; generated if missing or empty.
+ */
+id: string, 
+/**
+ * Default so partial updates (e.g. { runPort }) deserialize without sending name.
+ */
+name: string, description: string | null, repoPath: string | null, runPort?: number | null, promptIds: Array<number>, ticketIds: Array<string>, featureIds: Array<string>, ideaIds: Array<number>, designIds?: Array<string> | null, architectureIds?: Array<string> | null, entityCategories: JsonValue | null, specFiles: Array<JsonValue> | null, specFilesTickets: Array<string> | null, specFilesFeatures: Array<string> | null, createdAt: string | null, updatedAt: string | null, };
@@ -0,0 +1,7 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * List JSON files in data/ directory.
+ * Result of analyzing a project directory for AI ticket generation.
+ */
+export type ProjectAnalysis = { name: string, path: string, package_json: string | null, readme_snippet: string | null, top_level_dirs: Array<string>, top_level_files: Array<string>, config_snippet: string | null, };
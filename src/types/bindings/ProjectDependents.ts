@@ -0,0 +1,10 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Data owned by a project; with the restrict policy a project can only be deleted when all counts are zero.
+ */
+export type ProjectDependents = { plan_tickets: number, 
+/**
+ * Excludes an empty "General Development" milestone, which is created automatically.
+ */
+milestones: number, ideas: number, implementation_log: number, };
@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Args for project-scoped commands; accept camelCase projectId from frontend.
+ */
+export type ProjectIdArg = { project_id: string, };
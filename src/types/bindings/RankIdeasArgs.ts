@@ -0,0 +1,19 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { IdeaScoreMethod } from "./IdeaScoreMethod";
+
+/**
+ * Args for rank_ideas. In built app, IPC expects payload key `args`.
+ */
+export type RankIdeasArgs = { 
+/**
+ * Project ideas plus unassigned ones; all ideas when omitted.
+ */
+project_id: string | null, 
+/**
+ * "rice" (default) or "ice".
+ */
+method: IdeaScoreMethod, 
+/**
+ * Default 10.
+ */
+limit: number | null, };
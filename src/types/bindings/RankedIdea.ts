@@ -0,0 +1,12 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { Idea } from "./Idea";
+import type { IdeaScoreMethod } from "./IdeaScoreMethod";
+
+/**
+ * One idea in rank_ideas with the factors behind its score.
+ */
+export type RankedIdea = { rank: number, score: number, method: IdeaScoreMethod, 
+/**
+ * Each factor and how it enters the score, e.g. "reach 500 × impact 2 × confidence 80% ÷ effort 4".
+ */
+breakdown: string, idea: Idea, };
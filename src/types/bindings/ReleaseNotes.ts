@@ -0,0 +1,3 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+export type ReleaseNotes = { content: string, format: string, ticket_count: number, changelog_path: string | null, tag: string | null, };
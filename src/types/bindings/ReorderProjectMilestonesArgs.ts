@@ -0,0 +1,10 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Args for reorder_project_milestones. In built app, IPC expects payload key `args`.
+ */
+export type ReorderProjectMilestonesArgs = { project_id: string, 
+/**
+ * New order; milestones not listed keep their relative order after these.
+ */
+milestone_ids: Array<number>, };
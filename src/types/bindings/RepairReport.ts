@@ -0,0 +1,23 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { DbHealth } from "./DbHealth";
+
+/**
+ * What repair_db did, and the health check that followed.
+ */
+export type RepairReport = { 
+/**
+ * Copy of the database taken before the repair (set by the repair_db command).
+ */
+backup_path: string | null, 
+/**
+ * Issues fixed in place.
+ */
+fixed: number, 
+/**
+ * Rows moved to the quarantine table.
+ */
+quarantined: number, 
+/**
+ * Issues left for a manual fix or a restore.
+ */
+manual: number, health: DbHealth, };
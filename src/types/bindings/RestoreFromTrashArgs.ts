@@ -0,0 +1,8 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { Actor } from "./Actor";
+import type { TrashKind } from "./TrashKind";
+
+/**
+ * Args for restore_from_trash. In built app, IPC expects payload key `args`.
+ */
+export type RestoreFromTrashArgs = { kind: TrashKind, id: string, actor: Actor | null, };
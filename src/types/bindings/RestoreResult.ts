@@ -0,0 +1,15 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { BackupInfo } from "./BackupInfo";
+
+/**
+ * Result of restore_backup.
+ */
+export type RestoreResult = { 
+/**
+ * Copy of the database as it was before the restore.
+ */
+safety_copy: BackupInfo, 
+/**
+ * Schema version of the restored backup; newer migrations run when the database is reopened.
+ */
+schema_version: number, };
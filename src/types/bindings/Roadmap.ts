@@ -0,0 +1,8 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { RoadmapMilestone } from "./RoadmapMilestone";
+
+export type Roadmap = { project_id: string, 
+/**
+ * Tickets completed per day over the throughput window.
+ */
+throughput_per_day: number, throughput_window_days: number, milestones: Array<RoadmapMilestone>, };
@@ -0,0 +1,16 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { Milestone } from "./Milestone";
+
+/**
+ * A milestone on the roadmap with its projected completion.
+ */
+export type RoadmapMilestone = { milestone: Milestone, total: number, done: number, 
+/**
+ * YYYY-MM-DD the open tickets of this and all earlier milestones should be finished at the current throughput.
+ * None when shipped or when there is no throughput to project from.
+ */
+projected_completion: string | null, 
+/**
+ * projected_completion is on or before due_date (None without both).
+ */
+on_track: boolean | null, };
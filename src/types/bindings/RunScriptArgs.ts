@@ -0,0 +1,4 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { TimingParams } from "./TimingParams";
+
+export type RunScriptArgs = { promptIds: Array<number>, combinedPrompt: string | null, activeProjects: Array<string>, timing: TimingParams, runLabel: string | null, };
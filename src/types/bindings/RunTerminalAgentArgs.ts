@@ -0,0 +1,10 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Args for run_run_terminal_agent; accept camelCase from frontend (projectPath, promptContent, label, agentMode).
+ */
+export type RunTerminalAgentArgs = { project_path: string, prompt_content: string, label: string, 
+/**
+ * Cursor CLI mode: ask | plan | debug. Omit or "agent" = normal.
+ */
+agent_mode: string | null, };
@@ -0,0 +1,11 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+export type ScanCommitTicketRefsArgs = { project_id: string, 
+/**
+ * Revision range passed to git log (e.g. "v1.2..HEAD"). Default: the whole current branch.
+ */
+rev_range: string | null, 
+/**
+ * Mark tickets referenced with fixes/closes/resolves #N as Done.
+ */
+auto_close: boolean, };
@@ -0,0 +1,4 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { MigrationRecord } from "./MigrationRecord";
+
+export type SchemaStatus = { current_version: number, latest_version: number, history: Array<MigrationRecord>, };
@@ -0,0 +1,7 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+export type ScriptExitedPayload = { run_id: string, label: string, 
+/**
+ * Exit code of the script process when available (e.g. 0 = success, non-zero = failure).
+ */
+exit_code: number | null, };
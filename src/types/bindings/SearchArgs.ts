@@ -0,0 +1,19 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { SearchKind } from "./SearchKind";
+
+/**
+ * Args for search. In built app, IPC expects payload key `args`.
+ */
+export type SearchArgs = { query: string, 
+/**
+ * Limit hits to these kinds; all kinds when omitted or empty.
+ */
+kinds: Array<SearchKind> | null, 
+/**
+ * Rows of this project plus the tickets, prompts and ideas linked to it; everything when omitted.
+ */
+project_id: string | null, 
+/**
+ * Default 20, at most 100.
+ */
+limit: number | null, };
@@ -0,0 +1,15 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { SearchKind } from "./SearchKind";
+
+/**
+ * One ranked search result. `title` and `snippet` are HTML-escaped with matches wrapped in `<mark>`.
+ */
+export type SearchHit = { kind: SearchKind, 
+/**
+ * Row id in the source table (plan ticket / ticket / prompt uuid, or the integer id as text).
+ */
+id: string, project_id: string | null, title: string, snippet: string, 
+/**
+ * Higher is better (negated bm25).
+ */
+score: number, };
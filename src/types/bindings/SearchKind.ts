@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Source of a search hit (one per indexed table).
+ */
+export type SearchKind = "plan_ticket" | "ticket" | "idea" | "milestone" | "prompt" | "run_log";
@@ -0,0 +1,7 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { Actor } from "./Actor";
+
+/**
+ * Args for set_plan_kanban_state; accept camelCase from frontend. In built app, IPC expects payload key `args`.
+ */
+export type SetPlanKanbanStateArgs = { project_id: string, in_progress_ids: Array<string>, actor: Actor | null, };
@@ -0,0 +1,7 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { Workflow } from "./Workflow";
+
+/**
+ * Args for set_plan_workflow. In built app, IPC expects payload key `args`.
+ */
+export type SetPlanWorkflowArgs = { project_id: string, workflow: Workflow, };
@@ -0,0 +1,3 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+export type SuggestCommitMessageArgs = { project_id: string, ticket_id: string | null, };
@@ -0,0 +1,14 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Args for sync_planner_from_markdown / sync_planner_to_markdown. In built app, IPC expects payload key `args`.
+ */
+export type SyncPlannerMarkdownArgs = { project_id: string, 
+/**
+ * Tickets changed on both sides: "report" (default) leaves them alone, "markdown" or "database" picks that side.
+ */
+strategy: string | null, 
+/**
+ * from_markdown only: delete tickets missing from tickets.md even when that is more than a quarter of them.
+ */
+force: boolean | null, };
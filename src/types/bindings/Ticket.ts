@@ -0,0 +1,3 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+export type Ticket = { id: string, title: string, description: string, status: string, priority: number, created_at: string, updated_at: string, prompt_ids?: Array<number> | null, project_paths?: Array<string> | null, };
@@ -0,0 +1,10 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * A commit that references a plan ticket (from `git log` scanning).
+ */
+export type TicketCommitLink = { project_id: string, ticket_number: number, commit_hash: string, subject: string, 
+/**
+ * Referenced with a closing keyword (fixes/closes/resolves #N).
+ */
+closes: boolean, committed_at: string, };
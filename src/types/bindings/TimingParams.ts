@@ -0,0 +1,3 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+export type TimingParams = { sleep_after_open_project: number, sleep_after_window_focus: number, sleep_between_shift_tabs: number, sleep_after_all_shift_tabs: number, sleep_after_cmd_n: number, sleep_before_paste: number, sleep_after_paste: number, sleep_after_enter: number, sleep_between_projects: number, sleep_between_rounds: number, };
@@ -0,0 +1,11 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { TrashKind } from "./TrashKind";
+
+/**
+ * A deleted row waiting in the trash.
+ */
+export type TrashItem = { kind: TrashKind, id: string, 
+/**
+ * The project itself for projects, the owning project for plan tickets; None for global rows.
+ */
+project_id: string | null, title: string, deleted_at: string, };
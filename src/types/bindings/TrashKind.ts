@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Kind of row in the trash (the tables with soft deletion).
+ */
+export type TrashKind = "project" | "plan_ticket" | "ticket" | "feature" | "prompt" | "design";
@@ -0,0 +1,7 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { IdeaPatch } from "./IdeaPatch";
+
+/**
+ * Args for update_idea. In built app, IPC expects payload key `args`.
+ */
+export type UpdateIdeaArgs = { idea_id: number, patch: IdeaPatch, };
@@ -0,0 +1,7 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { MilestonePatch } from "./MilestonePatch";
+
+/**
+ * Args for update_project_milestone. In built app, IPC expects payload key `args`.
+ */
+export type UpdateProjectMilestoneArgs = { project_id: string, milestone_id: number, patch: MilestonePatch, };
@@ -0,0 +1,9 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { WorkflowColumn } from "./WorkflowColumn";
+import type { WorkflowTransition } from "./WorkflowTransition";
+
+/**
+ * Per-project kanban workflow: ordered columns and the moves allowed between them. Reordering within a column is
+ * always allowed.
+ */
+export type Workflow = { columns: Array<WorkflowColumn>, transitions: Array<WorkflowTransition>, };
@@ -0,0 +1,14 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * One column of a project's planner workflow. `name` is also the status stored on the tickets in it.
+ */
+export type WorkflowColumn = { name: string, 
+/**
+ * Maximum number of tickets in the column; None = unlimited.
+ */
+wip_limit: number | null, 
+/**
+ * Tickets in this column count as done.
+ */
+done: boolean, };
@@ -3,12 +3,24 @@
  */
 export type IdeaCategory = "saas" | "iaas" | "paas" | "website" | "webapp" | "webshop" | "other";
 
+export type IdeaStatus = "new" | "accepted" | "rejected" | "converted";
+
 export type IdeaRecord = {
   id: number;
   title: string;
   description: string;
   category: IdeaCategory;
   source: "template" | "ai" | "manual";
+  /** Set by the Tauri backend; "converted" once promoted to a milestone and tickets. */
+  status?: IdeaStatus;
+  /** RICE/ICE factors and scores (Tauri backend; see src-tauri db::Idea). */
+  reach?: number | null;
+  impact?: number | null;
+  confidence?: number | null;
+  effort?: number | null;
+  ease?: number | null;
+  rice_score?: number | null;
+  ice_score?: number | null;
   created_at?: string;
   updated_at?: string;
 };
\ No newline at end of file
@@ -8,6 +8,12 @@ export type MilestoneRecord = {
   name: string;
   slug: string;
   content?: string | null;
+  /** YYYY-MM-DD. */
+  start_date?: string | null;
+  /** YYYY-MM-DD. */
+  due_date?: string | null;
+  position?: number;
+  status?: "planned" | "active" | "shipped";
   created_at?: string;
   updated_at?: string;
 };